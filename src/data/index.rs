@@ -107,7 +107,7 @@ impl Recipe {
 					operator,
 					Recipe::Ingredient(Ingredient::Column(column)),
 					Recipe::Ingredient(Ingredient::Value(value)),
-				) if operator as usize == Value::eq as usize => {
+				) if operator as usize == Value::eq as usize && incrementable(&value) => {
 					{
 						if let Some((table, index)) = indexed_columns.get(&column) {
 							let mut filters = HashMap::new();
@@ -137,7 +137,7 @@ impl Recipe {
 					operator,
 					Recipe::Ingredient(Ingredient::Column(column)),
 					Recipe::Ingredient(Ingredient::Value(value)),
-				) if operator as usize == Value::gt as usize => {
+				) if operator as usize == Value::gt as usize && incrementable(&value) => {
 					if let Some((table, index)) = indexed_columns.get(&column) {
 						let mut filters = HashMap::new();
 						filters.insert(table.clone(), MoreThan(index.clone(), value.inc()));
@@ -159,7 +159,7 @@ impl Recipe {
 					operator,
 					Recipe::Ingredient(Ingredient::Column(column)),
 					Recipe::Ingredient(Ingredient::Value(value)),
-				) if operator as usize == Value::lt_eq as usize => {
+				) if operator as usize == Value::lt_eq as usize && incrementable(&value) => {
 					if let Some((table, index)) = indexed_columns.get(&column) {
 						let mut filters = HashMap::new();
 						filters.insert(table.clone(), LessThan(index.clone(), value.inc()));
@@ -172,3 +172,8 @@ impl Recipe {
 		(self, None)
 	}
 }
+
+/// Whether [Value::inc] can find the value following this one, as some filters need
+fn incrementable(value: &Value) -> bool {
+	matches!(value, Value::Bool(false) | Value::I64(_) | Value::F64(_))
+}
@@ -1,6 +1,7 @@
 mod column;
 mod index;
 mod join;
+mod predicate;
 pub mod recipe;
 mod row;
 pub(crate) mod schema;
@@ -12,7 +13,8 @@ pub use {
 	column::*,
 	index::{Index, IndexFilter},
 	join::{join_iters, JoinType},
-	recipe::RecipeError,
+	predicate::{Comparison, Predicate, RowFilter},
+	recipe::{Accumulator, FunctionRegistry, RecipeError},
 	row::{Row, RowError},
 	schema::*,
 	table::{get_name, Table, TableError},
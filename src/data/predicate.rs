@@ -0,0 +1,307 @@
+use {
+	crate::{
+		recipe::{BinaryOperator, Ingredient, Method, PatternSyntax, PlannedRecipe, Recipe},
+		types::ColumnInfo,
+		Value,
+	},
+	regex::Regex,
+	serde::Serialize,
+};
+
+/// A simple condition on a single column, given to databases so that they may filter as they scan.
+///
+/// Predicates only ever narrow a scan: the engine still confirms the whole constraint for every row returned,
+/// so a database may return rows which don't match but must never leave out rows which do.
+#[derive(Clone, Debug, Serialize)]
+pub enum Predicate {
+	/// Column, Comparison, Value; as `column < value`
+	Compare(String, Comparison, Value),
+	/// Column, Values; equal to any
+	In(String, Vec<Value>),
+	/// Column, Pattern, Case insensitive; as SQL `LIKE` (or `ILIKE`)
+	Like(String, String, bool),
+	IsNull(String),
+	IsNotNull(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum Comparison {
+	Eq,
+	NotEq,
+	Lt,
+	LtEq,
+	Gt,
+	GtEq,
+}
+
+impl Comparison {
+	pub fn from_operator(operator: BinaryOperator) -> Option<Self> {
+		use Comparison::*;
+		[
+			(Value::eq as BinaryOperator, Eq),
+			(Value::not_eq, NotEq),
+			(Value::lt, Lt),
+			(Value::lt_eq, LtEq),
+			(Value::gt, Gt),
+			(Value::gt_eq, GtEq),
+		]
+		.into_iter()
+		.find_map(|(method, comparison)| {
+			(method as usize == operator as usize).then_some(comparison)
+		})
+	}
+	pub fn operator(&self) -> BinaryOperator {
+		use Comparison::*;
+		match self {
+			Eq => Value::eq,
+			NotEq => Value::not_eq,
+			Lt => Value::lt,
+			LtEq => Value::lt_eq,
+			Gt => Value::gt,
+			GtEq => Value::gt_eq,
+		}
+	}
+	/// The same comparison with its sides swapped: `value < column` as `column > value`
+	pub fn swapped(self) -> Self {
+		use Comparison::*;
+		match self {
+			Lt => Gt,
+			LtEq => GtEq,
+			Gt => Lt,
+			GtEq => LtEq,
+			symmetric => symmetric,
+		}
+	}
+	pub fn as_sql(&self) -> &'static str {
+		use Comparison::*;
+		match self {
+			Eq => "=",
+			NotEq => "<>",
+			Lt => "<",
+			LtEq => "<=",
+			Gt => ">",
+			GtEq => ">=",
+		}
+	}
+}
+
+impl Predicate {
+	pub fn column(&self) -> &str {
+		use Predicate::*;
+		match self {
+			Compare(column, ..)
+			| In(column, ..)
+			| Like(column, ..)
+			| IsNull(column)
+			| IsNotNull(column) => column,
+		}
+	}
+	/// Whether the predicate can be true of NULL, as `IS NULL` and `<>` are, by [RowFilter::confirm].
+	/// Only predicates which can't may be given for tables which a join may fill with NULLs.
+	pub fn accepts_null(&self) -> bool {
+		RowFilter::new(std::slice::from_ref(self), &[self.column().to_string()])
+			.confirm(&[Value::Null])
+	}
+}
+
+impl PlannedRecipe {
+	/// Those parts of the constraint, as joined by `AND`, which are [Predicate]s;
+	/// each with the index, of `columns`, of the column it concerns.
+	pub fn predicates(&self, columns: &[ColumnInfo]) -> Vec<(usize, Predicate)> {
+		let mut conjuncts = vec![];
+		conjuncts_of(&self.recipe, &mut conjuncts);
+		conjuncts
+			.into_iter()
+			.filter_map(|conjunct| {
+				let (column, predicate) = as_predicate(conjunct)?;
+				let index = (*self.needed_column_indexes.get(column)?)?;
+				let name = columns.get(index)?.name.clone();
+				Some((index, predicate(name)))
+			})
+			.collect()
+	}
+}
+
+fn conjuncts_of<'a>(recipe: &'a Recipe, conjuncts: &mut Vec<&'a Recipe>) {
+	if let Recipe::Method(method) = recipe {
+		if let Method::BinaryOperation(operator, left, right) = &**method {
+			if *operator as usize == Value::and as *const () as usize {
+				conjuncts_of(left, conjuncts);
+				conjuncts_of(right, conjuncts);
+				return;
+			}
+		}
+	}
+	conjuncts.push(recipe)
+}
+
+type PredicateOf = Box<dyn FnOnce(String) -> Predicate>;
+
+/// The column (by ingredient index) a recipe is a predicate of, and the predicate given the column's name
+fn as_predicate(recipe: &Recipe) -> Option<(usize, PredicateOf)> {
+	let method = match recipe {
+		Recipe::Method(method) => &**method,
+		Recipe::Ingredient(_) => return None,
+	};
+	match method {
+		Method::BinaryOperation(operator, left, right) => {
+			if *operator as usize == Value::or as *const () as usize {
+				let (column, values) = as_in(recipe)?;
+				return Some((column, Box::new(move |name| Predicate::In(name, values))));
+			}
+			let comparison = Comparison::from_operator(*operator)?;
+			let (column, comparison, value) = match (left, right) {
+				(
+					Recipe::Ingredient(Ingredient::Column(column)),
+					Recipe::Ingredient(Ingredient::Value(value)),
+				) => (*column, comparison, value.clone()),
+				(
+					Recipe::Ingredient(Ingredient::Value(value)),
+					Recipe::Ingredient(Ingredient::Column(column)),
+				) => (*column, comparison.swapped(), value.clone()),
+				_ => return None,
+			};
+			if matches!(value, Value::Null) {
+				return None;
+			}
+			Some((
+				column,
+				Box::new(move |name| Predicate::Compare(name, comparison, value)),
+			))
+		}
+		Method::UnaryOperation(operator, Recipe::Ingredient(Ingredient::Column(column)))
+			if *operator as usize == Value::is_null as *const () as usize =>
+		{
+			Some((*column, Box::new(Predicate::IsNull)))
+		}
+		Method::UnaryOperation(operator, Recipe::Method(inner))
+			if *operator as usize == Value::not as *const () as usize =>
+		{
+			match &**inner {
+				Method::UnaryOperation(
+					operator,
+					Recipe::Ingredient(Ingredient::Column(column)),
+				) if *operator as usize == Value::is_null as *const () as usize => {
+					Some((*column, Box::new(Predicate::IsNotNull)))
+				}
+				_ => None,
+			}
+		}
+		Method::Pattern(
+			pattern,
+			Recipe::Ingredient(Ingredient::Column(column)),
+			Recipe::Ingredient(Ingredient::Value(Value::Str(like))),
+			arguments,
+		) if pattern.operator as usize == Value::regexp_like as *const () as usize
+			&& arguments.is_empty() =>
+		{
+			if let PatternSyntax::Like { case_insensitive } = pattern.syntax {
+				let like = like.clone();
+				Some((
+					*column,
+					Box::new(move |name| Predicate::Like(name, like, case_insensitive)),
+				))
+			} else {
+				None
+			}
+		}
+		_ => None,
+	}
+}
+
+/// `column = a OR column = b ...`, as made of `column IN (a, b ...)`
+fn as_in(recipe: &Recipe) -> Option<(usize, Vec<Value>)> {
+	let method = match recipe {
+		Recipe::Method(method) => &**method,
+		Recipe::Ingredient(_) => return None,
+	};
+	match method {
+		Method::BinaryOperation(operator, left, right)
+			if *operator as usize == Value::or as *const () as usize =>
+		{
+			let (column, mut values) = as_in(left)?;
+			let (other_column, other_values) = as_in(right)?;
+			(column == other_column).then(|| {
+				values.extend(other_values);
+				(column, values)
+			})
+		}
+		Method::BinaryOperation(
+			operator,
+			Recipe::Ingredient(Ingredient::Column(column)),
+			Recipe::Ingredient(Ingredient::Value(value)),
+		) if *operator as usize == Value::eq as *const () as usize => {
+			Some((*column, vec![value.clone()]))
+		}
+		_ => None,
+	}
+}
+
+/// [Predicate]s made ready to check against rows of known columns,
+/// for databases which filter rows themselves as they read them.
+pub struct RowFilter(Vec<(usize, Check)>);
+
+enum Check {
+	Compare(BinaryOperator, Value),
+	In(Vec<Value>),
+	Like(Regex),
+	IsNull(bool),
+}
+
+impl RowFilter {
+	/// `columns` being the names of the columns of each row, in order.
+	/// Predicates which can't be checked are left out, to be left to the engine.
+	pub fn new(predicates: &[Predicate], columns: &[String]) -> Self {
+		RowFilter(
+			predicates
+				.iter()
+				.filter_map(|predicate| {
+					let index = columns
+						.iter()
+						.position(|column| column == predicate.column())?;
+					let check = match predicate {
+						Predicate::Compare(_, comparison, value) => {
+							Check::Compare(comparison.operator(), value.clone())
+						}
+						Predicate::In(_, values) => Check::In(values.clone()),
+						Predicate::Like(_, pattern, case_insensitive) => Check::Like(
+							Value::Str(pattern.clone())
+								.like_to_regex(*case_insensitive)
+								.ok()?,
+						),
+						Predicate::IsNull(_) => Check::IsNull(true),
+						Predicate::IsNotNull(_) => Check::IsNull(false),
+					};
+					Some((index, check))
+				})
+				.collect(),
+		)
+	}
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+	/// Whether the row may match, as it would were it given to the engine.
+	/// Where a check fails, rather than is untrue, the row is kept for the engine to decide.
+	pub fn confirm(&self, row: &[Value]) -> bool {
+		self.0.iter().all(|(index, check)| {
+			let value = match row.get(*index) {
+				Some(value) => value,
+				None => return true,
+			};
+			let result = match check {
+				Check::Compare(operator, other) => operator(value.clone(), other.clone()),
+				Check::In(values) => {
+					return values.iter().any(|other| {
+						!matches!(
+							Value::eq(value.clone(), other.clone()),
+							Ok(Value::Bool(false)) | Ok(Value::Null)
+						)
+					})
+				}
+				Check::Like(regex) => value.clone().regexp_like(regex, vec![]),
+				Check::IsNull(is_null) => return matches!(value, Value::Null) == *is_null,
+			};
+			!matches!(result, Ok(Value::Bool(false)) | Ok(Value::Null))
+		})
+	}
+}
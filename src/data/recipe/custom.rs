@@ -0,0 +1,101 @@
+use {
+	crate::{Result, Value},
+	std::{
+		collections::HashMap,
+		fmt::{Debug, Formatter},
+		sync::Arc,
+	},
+};
+
+/// An aggregate function defined outside of multisql, see [`crate::Glue::register_aggregate()`].
+///
+/// Rows are aggregated in parallel; each row's value is made into a state by [`Accumulator::initial()`],
+/// states are then combined, in no particular order, by [`Accumulator::merge()`].
+/// Lastly, the state of each group is made into a result by [`Accumulator::finish()`].
+pub trait Accumulator: Send + Sync {
+	fn initial(&self, value: Value) -> Result<Value>;
+	fn merge(&self, left: Value, right: Value) -> Result<Value>;
+	fn finish(&self, state: Value) -> Result<Value> {
+		Ok(state)
+	}
+}
+
+type ScalarFunction = dyn Fn(Vec<Value>) -> Result<Value> + Send + Sync;
+
+#[derive(Clone)]
+pub struct CustomFunction {
+	pub name: String,
+	pub function: Arc<ScalarFunction>,
+}
+
+#[derive(Clone)]
+pub struct CustomAggregate {
+	pub name: String,
+	pub accumulator: Arc<dyn Accumulator>,
+}
+
+impl Debug for CustomFunction {
+	fn fmt(&self, formatter: &mut Formatter) -> std::fmt::Result {
+		write!(formatter, "CustomFunction({})", self.name)
+	}
+}
+impl PartialEq for CustomFunction {
+	fn eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.function, &other.function)
+	}
+}
+impl Debug for CustomAggregate {
+	fn fmt(&self, formatter: &mut Formatter) -> std::fmt::Result {
+		write!(formatter, "CustomAggregate({})", self.name)
+	}
+}
+impl PartialEq for CustomAggregate {
+	fn eq(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.accumulator, &other.accumulator)
+	}
+}
+
+/// Functions available to queries in addition to, or instead of, those built in.
+/// Names are case insensitive.
+#[derive(Clone, Default)]
+pub struct FunctionRegistry {
+	scalars: HashMap<String, CustomFunction>,
+	aggregates: HashMap<String, CustomAggregate>,
+}
+
+impl FunctionRegistry {
+	pub fn register_function<Function>(&mut self, name: &str, function: Function)
+	where
+		Function: Fn(Vec<Value>) -> Result<Value> + Send + Sync + 'static,
+	{
+		let name = name.to_uppercase();
+		self.aggregates.remove(&name);
+		self.scalars.insert(
+			name.clone(),
+			CustomFunction {
+				name,
+				function: Arc::new(function),
+			},
+		);
+	}
+	pub fn register_aggregate<Aggregate>(&mut self, name: &str, accumulator: Aggregate)
+	where
+		Aggregate: Accumulator + 'static,
+	{
+		let name = name.to_uppercase();
+		self.scalars.remove(&name);
+		self.aggregates.insert(
+			name.clone(),
+			CustomAggregate {
+				name,
+				accumulator: Arc::new(accumulator),
+			},
+		);
+	}
+	pub fn get_function(&self, name: &str) -> Option<&CustomFunction> {
+		self.scalars.get(&name.to_uppercase())
+	}
+	pub fn get_aggregate(&self, name: &str) -> Option<&CustomAggregate> {
+		self.aggregates.get(&name.to_uppercase())
+	}
+}
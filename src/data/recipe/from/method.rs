@@ -1,6 +1,9 @@
 use {
 	crate::{
-		recipe::{AggregateOperator, BinaryOperator, FunctionOperator, RecipeError, UnaryOperator},
+		recipe::{
+			AggregateOperator, BinaryOperator, FunctionOperator, PatternOperator, RecipeError,
+			UnaryOperator,
+		},
 		Result, Value,
 	},
 	sqlparser::ast::{BinaryOperator as AstBinaryOperator, UnaryOperator as AstUnaryOperator},
@@ -22,10 +25,24 @@ impl TryIntoMethod<FunctionOperator> for String {
 			"LEFT" => Ok(Value::function_left),
 			"RIGHT" => Ok(Value::function_right),
 
-			"LEN" => Ok(Value::function_length),
+			"LEN" | "LENGTH" => Ok(Value::function_length),
 			"CONCAT" => Ok(Value::function_concat),
 			"REPLACE" => Ok(Value::function_replace),
 
+			"SUBSTRING" | "SUBSTR" => Ok(Value::function_substring),
+			"TRIM" => Ok(Value::function_trim),
+			"LTRIM" => Ok(Value::function_ltrim),
+			"RTRIM" => Ok(Value::function_rtrim),
+			"POSITION" => Ok(Value::function_position),
+			"INSTR" => Ok(Value::function_instr),
+			"LPAD" => Ok(Value::function_lpad),
+			"RPAD" => Ok(Value::function_rpad),
+			"REVERSE" => Ok(Value::function_reverse),
+			"SPLIT_PART" => Ok(Value::function_split_part),
+
+			"ENCODE" => Ok(Value::function_encode),
+			"DECODE" => Ok(Value::function_decode),
+
 			"NOW" => Ok(Value::function_now),
 
 			"YEAR" => Ok(Value::function_year),
@@ -40,6 +57,15 @@ impl TryIntoMethod<FunctionOperator> for String {
 
 			"ROUND" => Ok(Value::function_round),
 			"POW" => Ok(Value::function_pow),
+			"ABS" => Ok(Value::function_abs),
+			"SIGN" => Ok(Value::function_sign),
+			"CEIL" | "CEILING" => Ok(Value::function_ceil),
+			"FLOOR" => Ok(Value::function_floor),
+			"SQRT" => Ok(Value::function_sqrt),
+			"LN" => Ok(Value::function_ln),
+			"LOG" => Ok(Value::function_log),
+			"EXP" => Ok(Value::function_exp),
+			"MOD" => Ok(Value::function_mod),
 
 			"RAND" => Ok(Value::function_rand),
 			"UUID" => Ok(Value::function_rand),
@@ -47,6 +73,24 @@ impl TryIntoMethod<FunctionOperator> for String {
 			"IIF" => Ok(Value::function_iif),
 			"IFNULL" => Ok(Value::function_if_null),
 			"NULLIF" => Ok(Value::function_null_if),
+			"COALESCE" => Ok(Value::function_coalesce),
+			"GREATEST" => Ok(Value::function_greatest),
+			"LEAST" => Ok(Value::function_least),
+
+			unimplemented => {
+				Err(RecipeError::UnimplementedMethod(String::from(unimplemented)).into())
+			}
+		}
+	}
+}
+
+impl TryIntoMethod<PatternOperator> for String {
+	fn into_method(self) -> Result<PatternOperator> {
+		match self.to_uppercase().as_str() {
+			"REGEXP_LIKE" => Ok(Value::regexp_like),
+			"REGEXP_REPLACE" => Ok(Value::regexp_replace),
+			"REGEXP_SUBSTR" => Ok(Value::regexp_substr),
+			"REGEXP_COUNT" => Ok(Value::regexp_count),
 
 			unimplemented => {
 				Err(RecipeError::UnimplementedMethod(String::from(unimplemented)).into())
@@ -55,6 +99,25 @@ impl TryIntoMethod<FunctionOperator> for String {
 	}
 }
 
+impl TryIntoMethod<PatternOperator> for AstBinaryOperator {
+	fn into_method(self) -> Result<PatternOperator> {
+		match self {
+			AstBinaryOperator::PGRegexMatch
+			| AstBinaryOperator::PGRegexIMatch
+			| AstBinaryOperator::Like
+			| AstBinaryOperator::ILike => Ok(Value::regexp_like),
+			AstBinaryOperator::PGRegexNotMatch
+			| AstBinaryOperator::PGRegexNotIMatch
+			| AstBinaryOperator::NotLike
+			| AstBinaryOperator::NotILike => Ok(Value::regexp_not_like),
+
+			unimplemented => {
+				Err(RecipeError::UnimplementedMethod(format!("{:?}", unimplemented)).into())
+			}
+		}
+	}
+}
+
 impl TryIntoMethod<AggregateOperator> for String {
 	fn into_method(self) -> Result<AggregateOperator> {
 		match self.to_uppercase().as_str() {
@@ -111,3 +174,24 @@ impl TryIntoMethod<BinaryOperator> for AstBinaryOperator {
 		}
 	}
 }
+
+pub fn quantified_method(operator: AstBinaryOperator, all: bool) -> Result<BinaryOperator> {
+	Ok(match (operator, all) {
+		(AstBinaryOperator::Eq, false) => Value::any_eq,
+		(AstBinaryOperator::Eq, true) => Value::all_eq,
+		(AstBinaryOperator::NotEq, false) => Value::any_not_eq,
+		(AstBinaryOperator::NotEq, true) => Value::all_not_eq,
+		(AstBinaryOperator::Gt, false) => Value::any_gt,
+		(AstBinaryOperator::Gt, true) => Value::all_gt,
+		(AstBinaryOperator::GtEq, false) => Value::any_gt_eq,
+		(AstBinaryOperator::GtEq, true) => Value::all_gt_eq,
+		(AstBinaryOperator::Lt, false) => Value::any_lt,
+		(AstBinaryOperator::Lt, true) => Value::all_lt,
+		(AstBinaryOperator::LtEq, false) => Value::any_lt_eq,
+		(AstBinaryOperator::LtEq, true) => Value::all_lt_eq,
+
+		(unimplemented, _) => {
+			return Err(RecipeError::UnimplementedMethod(format!("{:?}", unimplemented)).into())
+		}
+	})
+}
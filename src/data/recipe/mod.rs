@@ -1,5 +1,7 @@
+mod custom;
 mod from;
 mod new;
+mod pattern;
 mod planned;
 mod resolve;
 
@@ -11,8 +13,10 @@ use {
 	thiserror::Error as ThisError,
 };
 pub use {
-	from::TryIntoMethod,
+	custom::{Accumulator, CustomAggregate, CustomFunction, FunctionRegistry},
+	from::{quantified_method, TryIntoMethod},
 	new::MetaRecipe,
+	pattern::{Pattern, PatternOperator, PatternSyntax},
 	planned::PlannedRecipe,
 	resolve::{Resolve, SimplifyBy},
 };
@@ -83,10 +87,13 @@ pub enum Ingredient {
 pub enum Method {
 	Value(Value), // Only occurs backwards for eval! Should never be returned outside of a recursive simplification!
 	Aggregate(AggregateOperator, Recipe), // Only occurs inside Ingredient::Aggregate. Perhaps this should not be a Method.
+	CustomAggregate(CustomAggregate, Recipe), // As above
 
 	UnaryOperation(UnaryOperator, Recipe),
 	BinaryOperation(BinaryOperator, Recipe, Recipe),
 	Function(FunctionOperator, Vec<Recipe>),
+	CustomFunction(CustomFunction, Vec<Recipe>),
+	Pattern(Pattern, Recipe, Recipe, Vec<Recipe>), // Value, pattern, other arguments
 
 	Cast(DataType, Recipe),
 
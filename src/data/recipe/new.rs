@@ -1,12 +1,18 @@
 use {
-	super::{Ingredient, Method, Recipe, RecipeError, TryIntoMethod},
+	super::{
+		quantified_method, FunctionOperator, FunctionRegistry, Ingredient, Method, Pattern,
+		PatternSyntax, Recipe, RecipeError, TryIntoMethod,
+	},
 	crate::{
-		query::JoinManual,
+		query::{JoinManual, Outer},
 		recipe::{Resolve, SimplifyBy},
-		types::ObjectName,
-		Result, TempDB, Value,
+		types::{ColumnInfo, ObjectName},
+		Result, TempDB, Value, ValueError,
+	},
+	sqlparser::ast::{
+		BinaryOperator as AstBinaryOperator, Expr, FunctionArg, FunctionArgExpr, Ident,
+		TrimWhereField,
 	},
-	sqlparser::ast::{Expr, FunctionArg, FunctionArgExpr, Ident},
 	std::convert::TryFrom,
 };
 // TODO: #50 - imports: JoinType, SelectItem, SetExpr, ComplexTableName
@@ -17,8 +23,8 @@ pub struct MetaRecipe {
 	pub meta: RecipeMeta,
 }
 impl MetaRecipe {
-	pub fn new(expression: Expr) -> Result<Self> {
-		let (recipe, meta) = Recipe::new_with_meta(expression)?;
+	pub fn new(expression: Expr, functions: &FunctionRegistry) -> Result<Self> {
+		let (recipe, meta) = Recipe::new_with_meta(expression, functions)?;
 		Ok(Self { recipe, meta })
 	}
 	pub fn simplify_by_tempdb(self, tempdb: &TempDB) -> Result<Self> {
@@ -44,6 +50,44 @@ impl MetaRecipe {
 		let recipe = self.recipe.simplify(SimplifyBy::OptRow(&row))?;
 		Ok(Self { recipe, meta })
 	}
+	/// Gives values to references which are not to any of `columns` but are to the [Outer] row
+	pub fn simplify_by_outer(
+		self,
+		columns: &[ColumnInfo],
+		outer: &Outer,
+		references: &mut Vec<ObjectName>,
+	) -> Result<Self> {
+		if matches!(outer, Outer::None) {
+			return Ok(self);
+		}
+		let meta_objects = self.meta.objects.clone();
+		let (meta_objects, row) = meta_objects
+			.into_iter()
+			.map(|object_name| match object_name {
+				Some(object_name) if !columns.iter().any(|column| column == &object_name) => {
+					let value = match outer {
+						Outer::Unknown => Some(Value::Null),
+						Outer::Row(outer_columns, outer_row) => outer_columns
+							.iter()
+							.position(|column| column == &object_name)
+							.and_then(|index| outer_row.get(index).cloned()),
+						Outer::None => None,
+					};
+					if value.is_some() {
+						references.push(object_name);
+						(None, value)
+					} else {
+						(Some(object_name), None)
+					}
+				}
+				object_name => (object_name, None),
+			})
+			.unzip();
+		let mut meta = self.meta;
+		meta.objects = meta_objects;
+		let recipe = self.recipe.simplify(SimplifyBy::OptRow(&row))?;
+		Ok(Self { recipe, meta })
+	}
 }
 impl MetaRecipe {
 	pub const NULL: Self = MetaRecipe {
@@ -114,6 +158,15 @@ impl RecipeMeta {
 		self.append_subquery(subquery);
 		Ok(result)
 	}*/
+	pub fn aggregate_array(&mut self, argument: Recipe) -> Recipe {
+		self.aggregate(Recipe::Method(Box::new(Method::Aggregate(
+			Value::aggregate_array,
+			Recipe::Method(Box::new(Method::Function(
+				Value::function_array,
+				vec![argument],
+			))),
+		))))
+	}
 	pub fn aggregate_average(&mut self, argument: Recipe) -> Recipe {
 		Recipe::Method(Box::new(Method::BinaryOperation(
 			Value::generic_divide,
@@ -137,14 +190,18 @@ pub struct Subquery {
 }*/
 
 impl Recipe {
-	pub fn new_without_meta(expression: Expr) -> Result<Self> {
-		Self::new_with_meta(expression).map(|(new, _)| new)
+	pub fn new_without_meta(expression: Expr, functions: &FunctionRegistry) -> Result<Self> {
+		Self::new_with_meta(expression, functions).map(|(new, _)| new)
 	}
-	fn new_with_meta(expression: Expr) -> Result<(Self, RecipeMeta)> {
+	fn new_with_meta(expression: Expr, functions: &FunctionRegistry) -> Result<(Self, RecipeMeta)> {
 		let mut meta = RecipeMeta::NEW;
-		Ok((Self::with_meta(expression, &mut meta)?, meta))
+		Ok((Self::with_meta(expression, &mut meta, functions)?, meta))
 	}
-	fn with_meta(expression: Expr, meta: &mut RecipeMeta) -> Result<Self> {
+	fn with_meta(
+		expression: Expr,
+		meta: &mut RecipeMeta,
+		functions: &FunctionRegistry,
+	) -> Result<Self> {
 		let error_expression_clone = expression.clone();
 		match expression {
 			Expr::Identifier(identifier) => Ok(Self::from_column(
@@ -160,42 +217,150 @@ impl Recipe {
 			)?))),
 			Expr::IsNull(expression) => Ok(Recipe::Method(Box::new(Method::UnaryOperation(
 				Value::is_null,
-				Self::with_meta(*expression, meta)?,
+				Self::with_meta(*expression, meta, functions)?,
 			)))),
 			Expr::IsNotNull(expression) => Ok(Recipe::Method(Box::new(Method::UnaryOperation(
 				Value::not,
 				Recipe::Method(Box::new(Method::UnaryOperation(
 					Value::is_null,
-					Self::with_meta(*expression, meta)?,
+					Self::with_meta(*expression, meta, functions)?,
 				))),
 			)))),
 			Expr::UnaryOp { op, expr } => Ok(Recipe::Method(Box::new(Method::UnaryOperation(
 				op.into_method()?,
-				Self::with_meta(*expr, meta)?,
+				Self::with_meta(*expr, meta, functions)?,
 			)))),
-			Expr::BinaryOp { op, left, right } => {
-				Ok(Recipe::Method(Box::new(Method::BinaryOperation(
-					op.into_method()?,
-					Self::with_meta(*left, meta)?,
-					Self::with_meta(*right, meta)?,
+			Expr::BinaryOp { op, left, right }
+				if matches!(
+					op,
+					AstBinaryOperator::PGRegexMatch
+						| AstBinaryOperator::PGRegexIMatch
+						| AstBinaryOperator::PGRegexNotMatch
+						| AstBinaryOperator::PGRegexNotIMatch
+						| AstBinaryOperator::Like
+						| AstBinaryOperator::NotLike
+						| AstBinaryOperator::ILike
+						| AstBinaryOperator::NotILike
+				) =>
+			{
+				let syntax = match op {
+					AstBinaryOperator::Like | AstBinaryOperator::NotLike => PatternSyntax::Like {
+						case_insensitive: false,
+					},
+					AstBinaryOperator::ILike | AstBinaryOperator::NotILike => PatternSyntax::Like {
+						case_insensitive: true,
+					},
+					_ => PatternSyntax::Regex,
+				};
+				let value = Self::with_meta(*left, meta, functions)?;
+				let mut pattern = Self::with_meta(*right, meta, functions)?;
+				if matches!(
+					op,
+					AstBinaryOperator::PGRegexIMatch | AstBinaryOperator::PGRegexNotIMatch
+				) {
+					pattern = Recipe::Method(Box::new(Method::BinaryOperation(
+						Value::string_concat,
+						Recipe::Ingredient(Ingredient::Value(Value::Str(String::from("(?i)")))),
+						pattern,
+					)));
+				}
+				Ok(Recipe::Method(Box::new(Method::Pattern(
+					Pattern::with_syntax(op.into_method()?, syntax, &pattern)?,
+					value,
+					pattern,
+					vec![],
 				))))
 			}
+			Expr::BinaryOp { op, left, right } => match *right {
+				Expr::Function(function)
+					if function.args.len() == 1
+						&& matches!(
+							function.name.0[0].value.to_uppercase().as_str(),
+							"ANY" | "SOME" | "ALL"
+						) =>
+				{
+					let all = function.name.0[0].value.to_uppercase() == "ALL";
+					let argument = function.args.into_iter().next().unwrap(); // Checked above
+					Ok(Recipe::Method(Box::new(Method::BinaryOperation(
+						quantified_method(op, all)?,
+						Self::with_meta(*left, meta, functions)?,
+						Recipe::from_argument(argument, meta, functions)?,
+					))))
+				}
+				right => Ok(Recipe::Method(Box::new(Method::BinaryOperation(
+					op.into_method()?,
+					Self::with_meta(*left, meta, functions)?,
+					Self::with_meta(right, meta, functions)?,
+				)))),
+			},
 			Expr::Function(function) => {
 				let name = function.name.0[0].value.clone();
-				if name == "AVG" {
+				// Registered functions take precedence over built-in functions
+				if let Some(custom_function) = functions.get_function(&name) {
+					let arguments = function
+						.args
+						.into_iter()
+						.map(|argument| Recipe::from_argument(argument, meta, functions))
+						.collect::<Result<Vec<Recipe>>>()?;
+					Ok(Recipe::Method(Box::new(Method::CustomFunction(
+						custom_function.clone(),
+						arguments,
+					))))
+				} else if let Some(custom_aggregate) = functions.get_aggregate(&name) {
+					if function.args.len() != 1 {
+						return Err(ValueError::NumberOfFunctionParamsNotMatching {
+							expected: 1,
+							found: function.args.len(),
+						}
+						.into());
+					}
+					let argument = function.args.into_iter().next().unwrap(); // Checked above
+					let argument = Recipe::from_argument(argument, meta, functions)?;
+					Ok(
+						meta.aggregate(Recipe::Method(Box::new(Method::CustomAggregate(
+							custom_aggregate.clone(),
+							argument,
+						)))),
+					)
+				} else if matches!(name.to_uppercase().as_str(), "AVG" | "ARRAY_AGG") {
 					let argument = function
 						.args
 						.get(0)
 						.ok_or(RecipeError::InvalidExpression(error_expression_clone))?
 						.clone();
-					let argument = Recipe::from_argument(argument, meta)?;
+					let argument = Recipe::from_argument(argument, meta, functions)?;
 
-					Ok(meta.aggregate_average(argument))
+					Ok(if name.to_uppercase() == "AVG" {
+						meta.aggregate_average(argument)
+					} else {
+						meta.aggregate_array(argument)
+					})
+				} else if let Ok(pattern_operator) = name.clone().into_method() {
+					let mut arguments = function
+						.args
+						.into_iter()
+						.map(|argument| Recipe::from_argument(argument, meta, functions))
+						.collect::<Result<Vec<Recipe>>>()?;
+					if arguments.len() < 2 {
+						return Err(ValueError::NumberOfFunctionParamsNotMatching {
+							expected: 2,
+							found: arguments.len(),
+						}
+						.into());
+					}
+					let value = arguments.remove(0);
+					let pattern = arguments.remove(0);
+					Ok(Recipe::Method(Box::new(Method::Pattern(
+						Pattern::new(pattern_operator, &pattern)?,
+						value,
+						pattern,
+						arguments,
+					))))
 				} else if let Ok(function_operator) = name.clone().into_method() {
 					let arguments = function
 						.args
 						.into_iter()
-						.map(|argument| Recipe::from_argument(argument, meta))
+						.map(|argument| Recipe::from_argument(argument, meta, functions))
 						.collect::<Result<Vec<Recipe>>>()?;
 					Ok(Recipe::Method(Box::new(Method::Function(
 						function_operator,
@@ -207,7 +372,7 @@ impl Recipe {
 						.get(0)
 						.ok_or(RecipeError::InvalidExpression(error_expression_clone))?
 						.clone();
-					let argument = Recipe::from_argument(argument, meta)?;
+					let argument = Recipe::from_argument(argument, meta, functions)?;
 
 					Ok(meta.aggregate(Recipe::Method(Box::new(Method::Aggregate(
 						name.into_method()?,
@@ -222,26 +387,54 @@ impl Recipe {
 				else_result,
 			} => Ok(Recipe::Method(Box::new(Method::Case {
 				operand: operand
-					.map(|operand| Self::with_meta(*operand, meta))
+					.map(|operand| Self::with_meta(*operand, meta, functions))
 					.transpose()?,
 				cases: conditions
 					.into_iter()
 					.zip(results)
 					.map(|(condition, result)| {
 						Ok((
-							Self::with_meta(condition, meta)?,
-							Self::with_meta(result, meta)?,
+							Self::with_meta(condition, meta, functions)?,
+							Self::with_meta(result, meta, functions)?,
 						))
 					})
 					.collect::<Result<Vec<_>>>()?,
 				else_result: else_result
-					.map(|else_result| Self::with_meta(*else_result, meta))
+					.map(|else_result| Self::with_meta(*else_result, meta, functions))
 					.transpose()?,
 			}))),
 			Expr::Cast { data_type, expr } => Ok(Recipe::Method(Box::new(Method::Cast(
 				data_type,
-				Self::with_meta(*expr, meta)?,
+				Self::with_meta(*expr, meta, functions)?,
 			)))),
+			Expr::InList {
+				expr,
+				list,
+				negated,
+			} => {
+				// As `expr = a OR expr = b ...`, so that a NULL in the list is as with OR
+				let value = Self::with_meta(*expr, meta, functions)?;
+				let body = list
+					.into_iter()
+					.map(|item| {
+						Ok(Recipe::Method(Box::new(Method::BinaryOperation(
+							Value::eq,
+							value.clone(),
+							Self::with_meta(item, meta, functions)?,
+						))))
+					})
+					.collect::<Result<Vec<Recipe>>>()?
+					.into_iter()
+					.reduce(|any, item| {
+						Recipe::Method(Box::new(Method::BinaryOperation(Value::or, any, item)))
+					})
+					.unwrap_or(Recipe::Ingredient(Ingredient::Value(Value::Bool(false))));
+				Ok(if negated {
+					Recipe::Method(Box::new(Method::UnaryOperation(Value::not, body)))
+				} else {
+					body
+				})
+			}
 			Expr::Between {
 				negated,
 				expr,
@@ -252,13 +445,13 @@ impl Recipe {
 					Value::and,
 					Recipe::Method(Box::new(Method::BinaryOperation(
 						Value::gt_eq,
-						Self::with_meta(*expr.clone(), meta)?,
-						Self::with_meta(*low, meta)?,
+						Self::with_meta(*expr.clone(), meta, functions)?,
+						Self::with_meta(*low, meta, functions)?,
 					))),
 					Recipe::Method(Box::new(Method::BinaryOperation(
 						Value::lt_eq,
-						Self::with_meta(*expr, meta)?,
-						Self::with_meta(*high, meta)?,
+						Self::with_meta(*expr, meta, functions)?,
+						Self::with_meta(*high, meta, functions)?,
 					))),
 				);
 				let body = if negated {
@@ -283,7 +476,7 @@ impl Recipe {
 						.get(0)
 						.map(|item| {
 							if let SelectItem::UnnamedExpr(expression) = item {
-								Some(Self::with_meta(expression.clone(), meta))
+								Some(Self::with_meta(expression.clone(), meta, functions))
 							} else {
 								None
 							}
@@ -302,14 +495,83 @@ impl Recipe {
 					Err(RecipeError::UnimplementedQuery(format!("{:?}", query)).into())
 				}
 			}*/
-			Expr::Nested(expression) => Self::with_meta(*expression, meta),
+			Expr::Array(array) => Ok(Recipe::Method(Box::new(Method::Function(
+				Value::function_array,
+				array
+					.elem
+					.into_iter()
+					.map(|element| Self::with_meta(element, meta, functions))
+					.collect::<Result<Vec<Recipe>>>()?,
+			)))),
+			Expr::MapAccess {
+				column: array,
+				keys: indexes,
+			}
+			| Expr::ArrayIndex {
+				obj: array,
+				indexs: indexes,
+			} => indexes.into_iter().try_fold(
+				Self::with_meta(*array, meta, functions)?,
+				|array, index| {
+					Ok(Recipe::Method(Box::new(Method::BinaryOperation(
+						Value::array_index,
+						array,
+						Self::with_meta(index, meta, functions)?,
+					))))
+				},
+			),
+			Expr::Substring {
+				expr,
+				substring_from,
+				substring_for,
+			} => {
+				let mut arguments = vec![
+					Self::with_meta(*expr, meta, functions)?,
+					substring_from
+						.map(|from| Self::with_meta(*from, meta, functions))
+						.transpose()?
+						.unwrap_or(Recipe::Ingredient(Ingredient::Value(Value::I64(1)))),
+				];
+				if let Some(length) = substring_for {
+					arguments.push(Self::with_meta(*length, meta, functions)?);
+				}
+				Ok(Recipe::Method(Box::new(Method::Function(
+					Value::function_substring,
+					arguments,
+				))))
+			}
+			Expr::Trim { expr, trim_where } => {
+				let (operator, characters): (FunctionOperator, _) = match trim_where {
+					None => (Value::function_trim, None),
+					Some((trim_where, characters)) => (
+						match trim_where {
+							TrimWhereField::Both => Value::function_trim,
+							TrimWhereField::Leading => Value::function_ltrim,
+							TrimWhereField::Trailing => Value::function_rtrim,
+						},
+						Some(characters),
+					),
+				};
+				let mut arguments = vec![Self::with_meta(*expr, meta, functions)?];
+				if let Some(characters) = characters {
+					arguments.push(Self::with_meta(*characters, meta, functions)?);
+				}
+				Ok(Recipe::Method(Box::new(Method::Function(
+					operator, arguments,
+				))))
+			}
+			Expr::Nested(expression) => Self::with_meta(*expression, meta, functions),
 			unimplemented => Err(RecipeError::UnimplementedExpression(unimplemented).into()),
 		}
 	}
-	fn from_argument(argument: FunctionArg, meta: &mut RecipeMeta) -> Result<Recipe> {
+	fn from_argument(
+		argument: FunctionArg,
+		meta: &mut RecipeMeta,
+		functions: &FunctionRegistry,
+	) -> Result<Recipe> {
 		match argument {
 			FunctionArg::Named { arg, .. } | FunctionArg::Unnamed(arg) => match arg {
-				FunctionArgExpr::Expr(arg) => Self::with_meta(arg, meta),
+				FunctionArgExpr::Expr(arg) => Self::with_meta(arg, meta, functions),
 				_ => Err(RecipeError::Unimplemented.into()),
 			},
 		}
@@ -0,0 +1,82 @@
+use {
+	super::{Recipe, RecipeUtilities},
+	crate::{Result, Value},
+	regex::Regex,
+	std::fmt::{Debug, Formatter},
+};
+
+pub type PatternOperator = fn(Value, &Regex, Vec<Value>) -> Result<Value>;
+
+/// How a pattern is written
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PatternSyntax {
+	Regex,
+	/// SQL `LIKE`, where `%` matches any characters and `_` any one character
+	Like {
+		case_insensitive: bool,
+	},
+}
+impl PatternSyntax {
+	pub fn compile(&self, pattern: Value) -> Result<Regex> {
+		match self {
+			PatternSyntax::Regex => pattern.to_regex(),
+			PatternSyntax::Like { case_insensitive } => pattern.like_to_regex(*case_insensitive),
+		}
+	}
+}
+
+/// A regular expression method.
+/// Where the pattern is constant it is compiled once, when the recipe is made, rather than for every row.
+#[derive(Clone)]
+pub struct Pattern {
+	pub operator: PatternOperator,
+	pub syntax: PatternSyntax,
+	pub compiled: Option<Regex>,
+}
+
+impl Pattern {
+	pub fn new(operator: PatternOperator, pattern: &Recipe) -> Result<Self> {
+		Self::with_syntax(operator, PatternSyntax::Regex, pattern)
+	}
+	pub fn with_syntax(
+		operator: PatternOperator,
+		syntax: PatternSyntax,
+		pattern: &Recipe,
+	) -> Result<Self> {
+		let compiled = match pattern.clone().simplify_by_basic()?.as_solution() {
+			None | Some(Value::Null) => None,
+			Some(pattern) => Some(syntax.compile(pattern)?),
+		};
+		Ok(Self {
+			operator,
+			syntax,
+			compiled,
+		})
+	}
+	pub fn run(&self, value: Value, pattern: Value, arguments: Vec<Value>) -> Result<Value> {
+		match (&self.compiled, pattern) {
+			(Some(regex), _) => (self.operator)(value, regex, arguments),
+			(None, Value::Null) => Ok(Value::Null),
+			(None, pattern) => (self.operator)(value, &self.syntax.compile(pattern)?, arguments),
+		}
+	}
+}
+
+impl Debug for Pattern {
+	fn fmt(&self, formatter: &mut Formatter) -> std::fmt::Result {
+		formatter
+			.debug_struct("Pattern")
+			.field("syntax", &self.syntax)
+			.field("compiled", &self.compiled.as_ref().map(Regex::as_str))
+			.finish()
+	}
+}
+
+impl PartialEq for Pattern {
+	fn eq(&self, other: &Self) -> bool {
+		self.operator as usize == other.operator as usize
+			&& self.syntax == other.syntax
+			&& self.compiled.as_ref().map(Regex::as_str)
+				== other.compiled.as_ref().map(Regex::as_str)
+	}
+}
@@ -64,35 +64,17 @@ impl PlannedRecipe {
 		columns: &[ColumnInfo],
 	) -> Result<(Self, HashMap<String, IndexFilter>)> {
 		let mut new = Self::new(meta_recipe, columns)?;
-		let indexed_table_columns = columns.iter().cloned().enumerate().fold(
-			HashMap::new(),
-			|mut tables: HashMap<String, Vec<(usize, String)>>, (index, column)| {
-				if let Some(index_name) = new
-					.needed_column_indexes
-					.iter()
-					.find(|need_index| need_index == &&Some(index))
-					.and_then(|_| column.index.clone())
-				{
-					let col_table = &column.table.name;
-					if let Some(table) = tables.get_mut(col_table) {
-						table.push((index, index_name));
-					} else {
-						tables.insert(col_table.clone(), vec![(index, index_name)]);
-					}
-				}
-				tables
-			},
-		);
-
-		let indexed_column_tables = indexed_table_columns.into_iter().fold(
-			HashMap::new(),
-			|mut indexed_columns, (table, columns)| {
-				columns.into_iter().for_each(|(column, index_name)| {
-					indexed_columns.insert(column, (table.clone(), index_name));
-				});
-				indexed_columns
-			},
-		);
+		// Keyed by the recipe's column ingredient, rather than the index in `columns`
+		let indexed_column_tables = new
+			.needed_column_indexes
+			.iter()
+			.enumerate()
+			.filter_map(|(ingredient, index)| {
+				let column = columns.get((*index)?)?;
+				let index_name = column.index.clone()?;
+				Some((ingredient, (column.table.name.clone(), index_name)))
+			})
+			.collect();
 
 		let result = new.recipe.reduce_by_index_filter(indexed_column_tables);
 		new.recipe = result.0;
@@ -189,39 +171,31 @@ impl PlannedRecipe {
 			.into_iter()
 			.zip(other.aggregates)
 			.map(|(self_agg, other_agg)| {
-				let (operator, self_val) = if let Recipe::Method(self_agg) = self_agg {
-					if let Method::Aggregate(operator, recipe) = *self_agg {
-						let value = recipe
-							.confirm_or_err(RecipeError::UnreachableAggregatationFailed.into())?;
-						(operator, value)
-					} else {
-						return Err(RecipeError::UnreachableNotAggregate(format!(
-							"{:?}",
-							self_agg
-						))
-						.into());
-					}
-				} else {
-					return Err(RecipeError::UnreachableNotMethod(format!("{:?}", self_agg)).into());
-				};
-
-				let other_val = if let Recipe::Method(other_agg) = other_agg {
-					if let Method::Aggregate(_, recipe) = *other_agg {
+				let self_agg = into_aggregate(self_agg)?;
+				let other_val = match into_aggregate(other_agg)? {
+					Method::Aggregate(_, recipe) | Method::CustomAggregate(_, recipe) => {
 						recipe.confirm_or_err(RecipeError::UnreachableAggregatationFailed.into())?
-					} else {
-						return Err(RecipeError::UnreachableNotAggregate(format!(
-							"{:?}",
-							other_agg
-						))
-						.into());
 					}
-				} else {
-					return Err(
-						RecipeError::UnreachableNotMethod(format!("{:?}", other_agg)).into(),
-					);
+					_ => return Err(RecipeError::Unreachable.into()),
 				};
-				let value = Recipe::Ingredient(Ingredient::Value(operator(self_val, other_val)?));
-				Ok(Recipe::Method(Box::new(Method::Aggregate(operator, value))))
+				Ok(Recipe::Method(Box::new(match self_agg {
+					Method::Aggregate(operator, recipe) => {
+						let self_val = recipe
+							.confirm_or_err(RecipeError::UnreachableAggregatationFailed.into())?;
+						let value =
+							Recipe::Ingredient(Ingredient::Value(operator(self_val, other_val)?));
+						Method::Aggregate(operator, value)
+					}
+					Method::CustomAggregate(aggregate, recipe) => {
+						let self_val = recipe
+							.confirm_or_err(RecipeError::UnreachableAggregatationFailed.into())?;
+						let value = Recipe::Ingredient(Ingredient::Value(
+							aggregate.accumulator.merge(self_val, other_val)?,
+						));
+						Method::CustomAggregate(aggregate, value)
+					}
+					_ => return Err(RecipeError::Unreachable.into()),
+				})))
 			})
 			.collect::<Result<Vec<Recipe>>>()?;
 		Ok(())
@@ -232,14 +206,19 @@ impl PlannedRecipe {
 			.into_iter()
 			.map(|agg| {
 				if let Recipe::Method(method) = agg {
-					if let Method::Aggregate(_, Recipe::Ingredient(Ingredient::Value(value))) =
-						*method
-					{
-						return Ok(if let Value::Internal(value) = value {
-							Value::I64(value)
-						} else {
-							value
-						});
+					match *method {
+						Method::Aggregate(_, Recipe::Ingredient(Ingredient::Value(value))) => {
+							return Ok(if let Value::Internal(value) = value {
+								Value::I64(value)
+							} else {
+								value
+							});
+						}
+						Method::CustomAggregate(
+							aggregate,
+							Recipe::Ingredient(Ingredient::Value(value)),
+						) => return aggregate.accumulator.finish(value),
+						_ => (),
 					}
 				}
 				Err(RecipeError::UnreachableAggregateFailed.into())
@@ -272,3 +251,15 @@ impl PlannedRecipe {
 		.unwrap_or(format!("unnamed_{}", selection_index))
 	}
 }
+
+fn into_aggregate(recipe: Recipe) -> Result<Method> {
+	if let Recipe::Method(method) = recipe {
+		if matches!(*method, Method::Aggregate(..) | Method::CustomAggregate(..)) {
+			Ok(*method)
+		} else {
+			Err(RecipeError::UnreachableNotAggregate(format!("{:?}", method)).into())
+		}
+	} else {
+		Err(RecipeError::UnreachableNotMethod(format!("{:?}", recipe)).into())
+	}
+}
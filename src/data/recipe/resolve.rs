@@ -115,6 +115,45 @@ impl Resolve for Method {
 					Method::Function(function, arguments)
 				}
 			}
+			Method::CustomFunction(function, arguments) => {
+				let arguments = arguments
+					.into_iter()
+					.map(|argument| argument.simplify(component.clone()))
+					.collect::<Result<Vec<Recipe>>>()?;
+				if let Some(arguments) = arguments
+					.iter()
+					.map(|argument| argument.as_solution())
+					.collect::<Option<Vec<Value>>>()
+				{
+					Method::Value((function.function)(arguments)?)
+				} else {
+					Method::CustomFunction(function, arguments)
+				}
+			}
+			Method::Pattern(pattern, value, regex, arguments) => {
+				let value = value.simplify(component.clone())?;
+				let regex = regex.simplify(component.clone())?;
+				let arguments = arguments
+					.into_iter()
+					.map(|argument| argument.simplify(component.clone()))
+					.collect::<Result<Vec<Recipe>>>()?;
+				if let (Some(value_solution), Some(regex_solution), Some(argument_solutions)) = (
+					value.as_solution(),
+					regex.as_solution(),
+					arguments
+						.iter()
+						.map(|argument| argument.as_solution())
+						.collect::<Option<Vec<Value>>>(),
+				) {
+					Method::Value(pattern.run(
+						value_solution,
+						regex_solution,
+						argument_solutions,
+					)?)
+				} else {
+					Method::Pattern(pattern, value, regex, arguments)
+				}
+			}
 			Method::Cast(data_type, recipe) => {
 				let recipe = recipe.simplify(component)?;
 				if let Some(value) = recipe.as_solution() {
@@ -215,6 +254,16 @@ impl Resolve for Method {
 			Method::Aggregate(operator, recipe) => {
 				Method::Aggregate(operator, recipe.simplify(component)?)
 			}
+			// Each row's value becomes an initial state, which are then merged
+			Method::CustomAggregate(aggregate, recipe) => {
+				let recipe = recipe.simplify(component)?;
+				let recipe = if let Some(value) = recipe.as_solution() {
+					Recipe::Ingredient(Ingredient::Value(aggregate.accumulator.initial(value)?))
+				} else {
+					recipe
+				};
+				Method::CustomAggregate(aggregate, recipe)
+			}
 		})
 	}
 }
@@ -1,7 +1,6 @@
 use {
-	crate::{Column, Index},
+	crate::{Column, DatabaseError, Index, Result, Value, ValueType},
 	serde::{Deserialize, Serialize},
-	std::collections::HashMap,
 	std::fmt::Debug,
 };
 
@@ -12,174 +11,191 @@ pub struct Schema {
 	pub indexes: Vec<Index>,
 }
 
-#[derive(Clone, Default)]
-pub struct SchemaDiff {
-	pub table_name: Option<String>,
-	pub column_defs: Option<HashMap<Option<usize>, Option<Column>>>,
-	pub indexes: Option<HashMap<Option<usize>, Option<Index>>>,
+/// Changes to a table, made in order and all together or not at all.
+///
+/// Changes refer to columns and indexes by name, as they are at the time of the change,
+/// so that no change depends on the positions left by another.
+#[derive(Clone, Default, Debug)]
+pub struct SchemaDiff(Vec<SchemaChange>);
+
+#[derive(Clone, Debug)]
+pub enum SchemaChange {
+	RenameTable(String),
+
+	/// Column name, New column
+	ColumnUpdate(String, Column),
+	ColumnAdd(Column),
+	ColumnRemove(String),
+
+	/// Index name, New index
+	IndexUpdate(String, Index),
+	IndexAdd(Index),
+	IndexRemove(String),
 }
+
 impl SchemaDiff {
+	pub fn new(changes: Vec<SchemaChange>) -> Self {
+		Self(changes)
+	}
 	pub fn new_rename(new_name: String) -> Self {
-		Self {
-			table_name: Some(new_name),
-			column_defs: None,
-			indexes: None,
-		}
+		Self(vec![SchemaChange::RenameTable(new_name)])
 	}
 	pub fn new_add_column(new_column: Column) -> Self {
-		Self {
-			table_name: None,
-			column_defs: Some([(None, Some(new_column))].into()),
-			indexes: None,
-		}
+		Self(vec![SchemaChange::ColumnAdd(new_column)])
 	}
-	pub fn new_remove_column(column_index: usize) -> Self {
-		Self {
-			table_name: None,
-			column_defs: Some([(Some(column_index), None)].into()),
-			indexes: None,
-		}
+	pub fn new_remove_column(column_name: String) -> Self {
+		Self(vec![SchemaChange::ColumnRemove(column_name)])
 	}
-	pub fn new_rename_column(
-		column_index: usize,
-		mut column: Column,
-		new_column_name: String,
-	) -> Self {
-		column.name = new_column_name;
-		Self {
-			table_name: None,
-			column_defs: Some([(Some(column_index), Some(column))].into()),
-			indexes: None,
-		}
+	pub fn new_rename_column(mut column: Column, new_column_name: String) -> Self {
+		let column_name = std::mem::replace(&mut column.name, new_column_name);
+		Self::new_update_column(column_name, column)
+	}
+	pub fn new_update_column(column_name: String, column: Column) -> Self {
+		Self(vec![SchemaChange::ColumnUpdate(column_name, column)])
 	}
 	pub fn new_add_index(new_index: Index) -> Self {
-		Self {
-			table_name: None,
-			column_defs: None,
-			indexes: Some([(None, Some(new_index))].into()),
-		}
+		Self(vec![SchemaChange::IndexAdd(new_index)])
+	}
+	/// Adds a change, to be made after those already given
+	pub fn push(&mut self, change: SchemaChange) {
+		self.0.push(change)
+	}
+	pub fn changes(&self) -> &[SchemaChange] {
+		&self.0
+	}
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+}
+
+impl From<Schema> for SchemaDiff {
+	/// Replaces the name, columns and indexes of a table with those of `from`, which must be as many
+	fn from(from: Schema) -> Self {
+		let mut changes = vec![SchemaChange::RenameTable(from.table_name)];
+		changes.extend(
+			from.column_defs
+				.into_iter()
+				.map(|column| SchemaChange::ColumnUpdate(column.name.clone(), column)),
+		);
+		changes.extend(
+			from.indexes
+				.into_iter()
+				.map(|index| SchemaChange::IndexUpdate(index.name.clone(), index)),
+		);
+		Self(changes)
 	}
 }
 
 impl SchemaDiff {
-	pub fn merge(self, mut schema: Schema) -> Schema {
-		if let Some(table_name) = self.table_name {
-			schema.table_name = table_name
+	/// The schema once changed.
+	/// Fails, and so should the whole diff, where any change concerns a column or index which isn't there.
+	pub fn merge(&self, mut schema: Schema) -> Result<Schema> {
+		for change in self.0.iter() {
+			change.apply(&mut schema)?;
 		}
-		if let Some(column_defs) = self.column_defs {
-			for (index, column_def) in column_defs.into_iter() {
-				match (index, column_def) {
-					(None, None) => (),
-					(Some(index), None) => {
-						schema.column_defs.remove(index);
-					} // TODO: WARN: Will be an issue if multiple change
-					(Some(index), Some(column_def)) => {
-						schema
-							.column_defs
-							.get_mut(index)
-							.map(|old_column_def| *old_column_def = column_def);
-					}
-					(None, Some(column_def)) => {
-						schema.column_defs.push(column_def);
-					}
+		Ok(schema)
+	}
+	/// How rows of the schema, as it is before, are to be rewritten to suit the changes;
+	/// [None] where they needn't be.
+	pub fn row_rewriter(&self, schema: &Schema) -> Result<Option<RowRewriter>> {
+		let mut schema = schema.clone();
+		let mut steps = vec![];
+		for change in self.0.iter() {
+			use SchemaChange::*;
+			match change {
+				ColumnAdd(column_def) => steps.push(RowStep::Add(
+					match (&column_def.default, column_def.is_nullable) {
+						(None, true) => Value::Null,
+						_ => return Err(DatabaseError::Unimplemented.into()), // TODO: #72
+					},
+				)),
+				ColumnRemove(column_name) => {
+					steps.push(RowStep::Remove(column_index(&schema, column_name)?))
 				}
-			}
-		}
-		if let Some(indexes) = self.indexes {
-			for (index, index_def) in indexes.into_iter() {
-				match (index, index_def) {
-					(None, None) => (),
-					(Some(index), None) => {
-						schema.indexes.remove(index);
-					} // TODO: WARN: Will be an issue if multiple change
-					(Some(index), Some(index_def)) => {
-						schema
-							.indexes
-							.get_mut(index)
-							.map(|old_index_def| *old_index_def = index_def);
-					}
-					(None, Some(index_def)) => {
-						schema.indexes.push(index_def);
+				ColumnUpdate(column_name, column_def) => {
+					let index = column_index(&schema, column_name)?;
+					if schema.column_defs[index].data_type != column_def.data_type {
+						steps.push(RowStep::Convert(index, column_def.data_type.clone()))
 					}
 				}
+				RenameTable(_) | IndexUpdate(..) | IndexAdd(_) | IndexRemove(_) => (),
 			}
+			change.apply(&mut schema)?;
 		}
-		schema
+		Ok((!steps.is_empty()).then_some(RowRewriter(steps)))
 	}
 }
 
-impl From<Schema> for SchemaDiff {
-	fn from(from: Schema) -> Self {
-		let column_defs = from
-			.column_defs
-			.into_iter()
-			.enumerate()
-			.map(|(key, col)| (Some(key), Some(col)))
-			.collect::<HashMap<Option<usize>, Option<Column>>>();
-		let indexes = from
-			.indexes
-			.into_iter()
-			.enumerate()
-			.map(|(key, idx)| (Some(key), Some(idx)))
-			.collect::<HashMap<Option<usize>, Option<Index>>>();
-		Self {
-			table_name: Some(from.table_name),
-			column_defs: Some(column_defs),
-			indexes: Some(indexes),
+impl SchemaChange {
+	/// Makes the change to a schema
+	pub fn apply(&self, schema: &mut Schema) -> Result<()> {
+		use SchemaChange::*;
+		match self {
+			RenameTable(table_name) => schema.table_name = table_name.clone(),
+			ColumnUpdate(column_name, column_def) => {
+				let index = column_index(schema, column_name)?;
+				schema.column_defs[index] = column_def.clone();
+			}
+			ColumnAdd(column_def) => schema.column_defs.push(column_def.clone()),
+			ColumnRemove(column_name) => {
+				let index = column_index(schema, column_name)?;
+				schema.column_defs.remove(index);
+			}
+			IndexUpdate(index_name, index_def) => {
+				let index = index_index(schema, index_name)?;
+				schema.indexes[index] = index_def.clone();
+			}
+			IndexAdd(index_def) => schema.indexes.push(index_def.clone()),
+			IndexRemove(index_name) => {
+				let index = index_index(schema, index_name)?;
+				schema.indexes.remove(index);
+			}
 		}
+		Ok(())
 	}
 }
 
-pub enum SchemaChange {
-	RenameTable(String),
+fn column_index(schema: &Schema, column_name: &str) -> Result<usize> {
+	schema
+		.column_defs
+		.iter()
+		.position(|column_def| column_def.name == column_name)
+		.ok_or_else(|| DatabaseError::ColumnNotFound(column_name.to_string()).into())
+}
+fn index_index(schema: &Schema, index_name: &str) -> Result<usize> {
+	schema
+		.indexes
+		.iter()
+		.position(|index| index.name == index_name)
+		.ok_or_else(|| DatabaseError::IndexNotFound(index_name.to_string()).into())
+}
 
-	ColumnUpdate(usize, Column),
-	ColumnAdd(Column),
-	ColumnRemove(usize),
+/// Rewrites rows to suit a [SchemaDiff], for databases which keep whole rows
+pub struct RowRewriter(Vec<RowStep>);
 
-	IndexUpdate(usize, Index),
-	IndexAdd(Index),
-	IndexRemove(usize),
+enum RowStep {
+	Add(Value),
+	Remove(usize),
+	Convert(usize, ValueType),
 }
-impl SchemaDiff {
-	pub fn get_changes(&self) -> Vec<SchemaChange> {
-		use SchemaChange::*;
-		let mut changes = Vec::new();
-		if let Some(table_name) = &self.table_name {
-			changes.push(RenameTable(table_name.clone()))
-		}
-		if let Some(column_defs) = &self.column_defs {
-			for (index, column_def) in column_defs.iter() {
-				match (index, column_def) {
-					(None, None) => (),
-					(Some(index), Some(column_def)) => {
-						changes.push(ColumnUpdate(*index, column_def.clone()));
-					}
-					(None, Some(column_def)) => {
-						changes.push(ColumnAdd(column_def.clone()));
-					}
-					(Some(index), None) => {
-						changes.push(ColumnRemove(*index));
+
+impl RowRewriter {
+	pub fn rewrite(&self, mut row: Vec<Value>) -> Result<Vec<Value>> {
+		for step in self.0.iter() {
+			match step {
+				RowStep::Add(value) => row.push(value.clone()),
+				RowStep::Remove(index) => {
+					if *index < row.len() {
+						row.remove(*index);
 					}
 				}
-			}
-		}
-		if let Some(indexes) = &self.indexes {
-			for (index, index_def) in indexes.iter() {
-				match (index, index_def) {
-					(None, None) => (),
-					(Some(index), Some(index_def)) => {
-						changes.push(IndexUpdate(*index, index_def.clone()));
-					}
-					(None, Some(index_def)) => {
-						changes.push(IndexAdd(index_def.clone()));
-					}
-					(Some(index), None) => {
-						changes.push(IndexRemove(*index));
+				RowStep::Convert(index, data_type) => {
+					if let Some(value) = row.get_mut(*index) {
+						*value = value.cast_valuetype(data_type)?;
 					}
 				}
 			}
 		}
-		changes
+		Ok(row)
 	}
 }
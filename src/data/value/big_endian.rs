@@ -21,6 +21,15 @@ impl BigEndian for Value {
 			.concat(),
 			U64(v) => [SEP.as_slice(), &v.to_be_bytes()].concat(),
 			Str(v) => [SEP.as_slice(), v.as_bytes()].concat(),
+			Bytes(v) => [SEP.as_slice(), v].concat(),
+			Array(v) => [
+				SEP.as_slice(),
+				&v.iter()
+					.flat_map(BigEndian::to_be_bytes)
+					.collect::<Vec<u8>>(),
+				&SEP,
+			]
+			.concat(),
 			_ => unimplemented!(),
 		}
 	}
@@ -128,11 +128,25 @@ impl Cast<String> for Value {
 			Value::Str(value) => value,
 			Value::Timestamp(value) => NaiveDateTime::from_timestamp(value, 0).to_string(),
 			Value::Null => String::from("NULL"),
+			Value::Array(_) => serde_json::value::Value::from(self).to_string(),
+			Value::Bytes(value) => base64::encode(value),
 			_ => return Err(unimplemented_cast(&self, ValueType::Str)),
 		})
 	}
 }
 
+impl Cast<Vec<u8>> for Value {
+	fn cast(self) -> Result<Vec<u8>> {
+		Ok(match self {
+			Value::Bytes(value) => value,
+			Value::Str(value) => base64::decode(&value)
+				.map_err(|_| failed_cast(&Value::Str(value), ValueType::Bytes))?,
+			Value::Null => return Err(failed_cast(&self, ValueType::Bytes)),
+			_ => return Err(unimplemented_cast(&self, ValueType::Bytes)),
+		})
+	}
+}
+
 // Utilities
 impl Cast<usize> for Value {
 	fn cast(self) -> Result<usize> {
@@ -69,6 +69,15 @@ impl Convert<String> for Value {
 	}
 }
 
+impl Convert<Vec<u8>> for Value {
+	fn convert(self) -> Result<Vec<u8>> {
+		Ok(match self {
+			Value::Bytes(inner) => inner,
+			other => return Err(ValueError::CannotConvert(other, "BYTES").into()),
+		})
+	}
+}
+
 impl Convert<NaiveDateTime> for Value {
 	fn convert(self) -> Result<NaiveDateTime> {
 		let secs = self.convert()?;
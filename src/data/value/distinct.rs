@@ -0,0 +1,49 @@
+use crate::Value;
+
+/// A value as compared by `DISTINCT` and set operations (`UNION`, `INTERSECT` & `EXCEPT`).
+/// Unlike with `=`, `NULL` is not distinct from `NULL`; keys may be hashed, rows of them too.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DistinctKey {
+	Null,
+	Bool(bool),
+	U64(u64),
+	I64(i64),
+	/// Bits of the float, `0.0` and `NaN` are made canonical
+	F64(u64),
+	Str(String),
+	Bytes(Vec<u8>),
+	Timestamp(i64),
+	Internal(i64),
+	Array(Vec<DistinctKey>),
+}
+
+impl From<&Value> for DistinctKey {
+	fn from(value: &Value) -> Self {
+		match value {
+			Value::Null => DistinctKey::Null,
+			Value::Bool(value) => DistinctKey::Bool(*value),
+			Value::U64(value) => DistinctKey::U64(*value),
+			Value::I64(value) => DistinctKey::I64(*value),
+			#[cfg(feature = "implicit_float_conversion")]
+			Value::F64(value)
+				if value.fract() == 0.0 && (i64::MIN as f64..i64::MAX as f64).contains(value) =>
+			{
+				DistinctKey::I64(*value as i64)
+			}
+			Value::F64(value) if *value == 0.0 => DistinctKey::F64(0.0_f64.to_bits()),
+			Value::F64(value) if value.is_nan() => DistinctKey::F64(f64::NAN.to_bits()),
+			Value::F64(value) => DistinctKey::F64(value.to_bits()),
+			Value::Str(value) => DistinctKey::Str(value.clone()),
+			Value::Bytes(value) => DistinctKey::Bytes(value.clone()),
+			Value::Timestamp(value) => DistinctKey::Timestamp(*value),
+			Value::Internal(value) => DistinctKey::Internal(*value),
+			Value::Array(values) => DistinctKey::Array(values.iter().map(Self::from).collect()),
+		}
+	}
+}
+
+impl DistinctKey {
+	pub fn of_row(row: &[Value]) -> Vec<Self> {
+		row.iter().map(Self::from).collect()
+	}
+}
@@ -19,6 +19,18 @@ pub enum ValueError {
 	#[error("failed to parse number")]
 	FailedToParseNumber,
 
+	#[error("failed to parse hex: {0}")]
+	FailedToParseHex(String),
+
+	#[error("failed to parse base64: {0}")]
+	FailedToParseBase64(String),
+
+	#[error("unsupported encoding: {0}, expected hex or base64")]
+	UnsupportedEncoding(String),
+
+	#[error("invalid regular expression: {0}")]
+	InvalidPattern(String),
+
 	#[error("unreachable failure on parsing number")]
 	UnreachableNumberParsing,
 
@@ -54,8 +66,14 @@ pub enum ValueError {
 	OnlySupportsNumeric(Value, &'static str),
 	#[error("{1} only supports boolean values, found {0:?}")]
 	OnlySupportsBoolean(Value, &'static str),
+	#[error("{1} only supports array values, found {0:?}")]
+	OnlySupportsArray(Value, &'static str),
+	#[error("{1} of {0:?} is out of range")]
+	OutOfRange(Value, &'static str),
 	#[error("bad input: {0:?}")]
 	BadInput(Value),
+	#[error("division by zero")]
+	DivisionByZero,
 
 	#[error("unimplemented literal type")]
 	UnimplementedLiteralType,
@@ -19,6 +19,9 @@ impl<'a> TryFrom<&'a AstValue> for Value {
 				)
 				.map_err(|_| ValueError::FailedToParseNumber.into()),
 			AstValue::SingleQuotedString(value) => Ok(Value::Str(value.clone())),
+			AstValue::HexStringLiteral(value) => hex::decode(value)
+				.map(Value::Bytes)
+				.map_err(|_| ValueError::FailedToParseHex(value.clone()).into()),
 			AstValue::Null => Ok(Value::Null),
 			_ => Err(ValueError::UnimplementedLiteralType.into()),
 		}
@@ -11,6 +11,8 @@ use {
 
 // Values returned as Value::Internal; need to be popped into Value::I64
 
+// ARRAY_AGG expects each row to already be wrapped as a single item array
+
 impl Value {
 	pub fn aggregate_count(self, other: Value) -> Result<Value> {
 		Ok(Value::Internal(match (self, other) {
@@ -48,4 +50,14 @@ impl Value {
 			.if_null(Value::I64(0)) // TODO: Handle lack of implicit i64 -> f64
 			.generic_add(self.if_null(Value::I64(0)))
 	}
+	pub fn aggregate_array(self, other: Value) -> Result<Value> {
+		Ok(match (self, other) {
+			(Value::Array(mut self_val), Value::Array(other_val)) => {
+				self_val.extend(other_val);
+				Value::Array(self_val)
+			}
+			(Value::Array(val), _) | (_, Value::Array(val)) => Value::Array(val),
+			(_, _) => Value::Null,
+		})
+	}
 }
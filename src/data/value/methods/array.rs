@@ -0,0 +1,74 @@
+use crate::{Cast, Result, Value, ValueError};
+
+macro_rules! quantified_binary_op {
+	($any_name: ident, $all_name: ident, $op: ident) => {
+		pub fn $any_name(self, array: Self) -> Result<Self> {
+			self.quantify(array, Value::$op, false)
+		}
+		pub fn $all_name(self, array: Self) -> Result<Self> {
+			self.quantify(array, Value::$op, true)
+		}
+	};
+}
+macro_rules! quantified_binary_ops {
+	($(($any_name: ident, $all_name: ident, $op: ident)),+) => {
+		impl Value {
+			$(quantified_binary_op!($any_name, $all_name, $op);)+
+		}
+	}
+}
+
+quantified_binary_ops!(
+	(any_eq, all_eq, eq),
+	(any_not_eq, all_not_eq, not_eq),
+	(any_gt, all_gt, gt),
+	(any_gt_eq, all_gt_eq, gt_eq),
+	(any_lt, all_lt, lt),
+	(any_lt_eq, all_lt_eq, lt_eq)
+);
+
+impl Value {
+	pub fn function_array(arguments: Vec<Self>) -> Result<Self> {
+		Ok(Value::Array(arguments))
+	}
+
+	/// Indexes are 1-based, out of range gives NULL
+	pub fn array_index(self, index: Self) -> Result<Self> {
+		let array = match self {
+			Value::Null => return Ok(Value::Null),
+			Value::Array(array) => array,
+			other => return Err(ValueError::OnlySupportsArray(other, "index").into()),
+		};
+		if matches!(index, Value::Null) {
+			return Ok(Value::Null);
+		}
+		let index: i64 = index.cast()?;
+		Ok(usize::try_from(index - 1)
+			.ok()
+			.and_then(|index| array.get(index).cloned())
+			.unwrap_or(Value::Null))
+	}
+
+	fn quantify(
+		self,
+		array: Self,
+		operator: fn(Value, Value) -> Result<Value>,
+		all: bool,
+	) -> Result<Self> {
+		let array = match array {
+			Value::Null => return Ok(Value::Null),
+			Value::Array(array) => array,
+			other => return Err(ValueError::OnlySupportsArray(other, "ANY/ALL").into()),
+		};
+		if matches!(self, Value::Null) {
+			return Ok(Value::Null);
+		}
+		for item in array.into_iter() {
+			let result: bool = operator(self.clone(), item)?.cast()?;
+			if result != all {
+				return Ok(Value::Bool(result));
+			}
+		}
+		Ok(Value::Bool(all))
+	}
+}
@@ -70,6 +70,15 @@ impl Value {
 		arguments.remove(0).length()
 	}
 
+	pub fn function_encode(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 2);
+		arguments.remove(0).encode(arguments.remove(0))
+	}
+	pub fn function_decode(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 2);
+		arguments.remove(0).decode(arguments.remove(0))
+	}
+
 	pub fn function_concat(mut arguments: Vec<Self>) -> Result<Self> {
 		arguments.remove(0).concat(arguments)
 	}
@@ -81,6 +90,73 @@ impl Value {
 			.replace(arguments.remove(0), arguments.remove(0))
 	}
 
+	pub fn function_substring(mut arguments: Vec<Self>) -> Result<Self> {
+		optional_expect_arguments!(arguments, 2, 3);
+		let value = arguments.remove(0);
+		let from = arguments.remove(0);
+		let length = if !arguments.is_empty() {
+			arguments.remove(0)
+		} else {
+			Self::Null
+		};
+		value.substring(from, length)
+	}
+	pub fn function_trim(arguments: Vec<Self>) -> Result<Self> {
+		Self::trim_arguments(arguments).and_then(|(value, characters)| value.trim(characters))
+	}
+	pub fn function_ltrim(arguments: Vec<Self>) -> Result<Self> {
+		Self::trim_arguments(arguments).and_then(|(value, characters)| value.ltrim(characters))
+	}
+	pub fn function_rtrim(arguments: Vec<Self>) -> Result<Self> {
+		Self::trim_arguments(arguments).and_then(|(value, characters)| value.rtrim(characters))
+	}
+	fn trim_arguments(mut arguments: Vec<Self>) -> Result<(Self, Self)> {
+		optional_expect_arguments!(arguments, 1, 2);
+		let value = arguments.remove(0);
+		let characters = if !arguments.is_empty() {
+			arguments.remove(0)
+		} else {
+			Self::Str(String::from(" "))
+		};
+		Ok((value, characters))
+	}
+	pub fn function_position(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 2);
+		arguments.remove(0).position(arguments.remove(0))
+	}
+	pub fn function_instr(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 2);
+		let string = arguments.remove(0);
+		arguments.remove(0).position(string)
+	}
+	pub fn function_lpad(arguments: Vec<Self>) -> Result<Self> {
+		Self::pad_arguments(arguments).and_then(|(value, length, fill)| value.lpad(length, fill))
+	}
+	pub fn function_rpad(arguments: Vec<Self>) -> Result<Self> {
+		Self::pad_arguments(arguments).and_then(|(value, length, fill)| value.rpad(length, fill))
+	}
+	fn pad_arguments(mut arguments: Vec<Self>) -> Result<(Self, Self, Self)> {
+		optional_expect_arguments!(arguments, 2, 3);
+		let value = arguments.remove(0);
+		let length = arguments.remove(0);
+		let fill = if !arguments.is_empty() {
+			arguments.remove(0)
+		} else {
+			Self::Str(String::from(" "))
+		};
+		Ok((value, length, fill))
+	}
+	pub fn function_reverse(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 1);
+		arguments.remove(0).reverse()
+	}
+	pub fn function_split_part(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 3);
+		arguments
+			.remove(0)
+			.split_part(arguments.remove(0), arguments.remove(0))
+	}
+
 	pub fn function_round(mut arguments: Vec<Self>) -> Result<Self> {
 		optional_expect_arguments!(arguments, 1, 2);
 		let value = arguments.remove(0);
@@ -121,6 +197,71 @@ impl Value {
 		arguments.remove(0).pow(arguments.remove(0))
 	}
 
+	pub fn function_abs(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 1);
+		arguments.remove(0).abs()
+	}
+	pub fn function_sign(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 1);
+		arguments.remove(0).sign()
+	}
+	pub fn function_ceil(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 1);
+		arguments.remove(0).ceil()
+	}
+	pub fn function_floor(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 1);
+		arguments.remove(0).floor()
+	}
+	pub fn function_sqrt(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 1);
+		arguments.remove(0).sqrt()
+	}
+	pub fn function_ln(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 1);
+		arguments.remove(0).ln()
+	}
+	pub fn function_exp(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 1);
+		arguments.remove(0).exp()
+	}
+	/// LOG(value) is base 10, LOG(base, value) is given base
+	pub fn function_log(mut arguments: Vec<Self>) -> Result<Self> {
+		optional_expect_arguments!(arguments, 1, 2);
+		if arguments.len() == 2 {
+			let base = arguments.remove(0);
+			arguments.remove(0).log(base)
+		} else {
+			arguments.remove(0).log(Self::F64(10.0))
+		}
+	}
+	pub fn function_mod(mut arguments: Vec<Self>) -> Result<Self> {
+		expect_arguments!(arguments, 2);
+		arguments.remove(0).modulo(arguments.remove(0))
+	}
+
+	/// NULLs are ignored, as with MAX
+	pub fn function_greatest(arguments: Vec<Self>) -> Result<Self> {
+		optional_expect_arguments!(arguments, 1, usize::MAX);
+		arguments
+			.into_iter()
+			.try_fold(Self::Null, |greatest, value| greatest.aggregate_max(value))
+	}
+	/// NULLs are ignored, as with MIN
+	pub fn function_least(arguments: Vec<Self>) -> Result<Self> {
+		optional_expect_arguments!(arguments, 1, usize::MAX);
+		arguments
+			.into_iter()
+			.try_fold(Self::Null, |least, value| least.aggregate_min(value))
+	}
+	pub fn function_coalesce(arguments: Vec<Self>) -> Result<Self> {
+		optional_expect_arguments!(arguments, 1, usize::MAX);
+		Ok(arguments
+			.into_iter()
+			.find(|value| !matches!(value, Self::Null))
+			.unwrap_or(Self::Null))
+	}
+
 	pub fn function_convert(mut arguments: Vec<Self>) -> Result<Self> {
 		optional_expect_arguments!(arguments, 2, 3);
 		let datatype: String = arguments.remove(0).convert()?;
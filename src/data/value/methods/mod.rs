@@ -1,6 +1,8 @@
 mod aggregate;
+mod array;
 mod binary;
 mod function;
+mod regex;
 mod timestamp;
 mod unary;
 mod utility;
@@ -14,3 +16,4 @@ impl ValueCore for bool {}
 impl ValueCore for i64 {}
 impl ValueCore for f64 {}
 impl ValueCore for String {}
+impl ValueCore for Vec<u8> {}
@@ -0,0 +1,114 @@
+use {
+	crate::{Convert, Result, Value, ValueError},
+	regex::Regex,
+};
+
+// Pattern methods are given the value, the compiled pattern and any remaining arguments.
+// Argument counts are reported including the value and pattern.
+
+macro_rules! expect_pattern_arguments {
+	($arguments: expr, $min: expr, $max: expr) => {
+		match $arguments.len() {
+			len if ($min..=$max).contains(&len) => (),
+			found => {
+				return Err(ValueError::NumberOfFunctionParamsNotMatching {
+					expected: $min + 2,
+					found: found + 2,
+				}
+				.into())
+			}
+		}
+	};
+}
+
+impl Value {
+	pub fn to_regex(self) -> Result<Regex> {
+		let pattern: String = self.convert()?;
+		Regex::new(&pattern).map_err(|error| ValueError::InvalidPattern(error.to_string()).into())
+	}
+	/// Compiles a SQL `LIKE` pattern; `\` escapes the character following it
+	pub fn like_to_regex(self, case_insensitive: bool) -> Result<Regex> {
+		let pattern: String = self.convert()?;
+		let mut regex = String::from(if case_insensitive { "(?is)^" } else { "(?s)^" });
+		let mut characters = pattern.chars();
+		while let Some(character) = characters.next() {
+			match character {
+				'%' => regex.push_str(".*"),
+				'_' => regex.push('.'),
+				'\\' => {
+					if let Some(escaped) = characters.next() {
+						regex.push_str(&regex::escape(&escaped.to_string()))
+					}
+				}
+				character => regex.push_str(&regex::escape(&character.to_string())),
+			}
+		}
+		regex.push('$');
+		Regex::new(&regex).map_err(|error| ValueError::InvalidPattern(error.to_string()).into())
+	}
+
+	pub fn regexp_like(self, regex: &Regex, arguments: Vec<Self>) -> Result<Self> {
+		expect_pattern_arguments!(arguments, 0, 0);
+		if matches!(self, Value::Null) {
+			return Ok(self);
+		}
+		let string: String = self.convert()?;
+		Ok(Value::Bool(regex.is_match(&string)))
+	}
+	pub fn regexp_not_like(self, regex: &Regex, arguments: Vec<Self>) -> Result<Self> {
+		self.regexp_like(regex, arguments)?.not_null()
+	}
+	/// Replaces every match, the replacement may reference groups as `$1` or `${name}`
+	pub fn regexp_replace(self, regex: &Regex, mut arguments: Vec<Self>) -> Result<Self> {
+		expect_pattern_arguments!(arguments, 1, 1);
+		let replacement = arguments.remove(0);
+		if matches!(self, Value::Null) || matches!(replacement, Value::Null) {
+			return Ok(Value::Null);
+		}
+		let string: String = self.convert()?;
+		let replacement: String = replacement.convert()?;
+		Ok(Value::Str(
+			regex
+				.replace_all(&string, replacement.as_str())
+				.into_owned(),
+		))
+	}
+	/// Occurrences are 1-based, a missing occurrence gives NULL
+	pub fn regexp_substr(self, regex: &Regex, mut arguments: Vec<Self>) -> Result<Self> {
+		expect_pattern_arguments!(arguments, 0, 1);
+		let occurrence = if !arguments.is_empty() {
+			arguments.remove(0)
+		} else {
+			Value::I64(1)
+		};
+		if matches!(self, Value::Null) || matches!(occurrence, Value::Null) {
+			return Ok(Value::Null);
+		}
+		let string: String = self.convert()?;
+		let occurrence: i64 = occurrence.convert()?;
+		if occurrence < 1 {
+			return Err(ValueError::BadInput(occurrence.into()).into());
+		}
+		Ok(regex
+			.find_iter(&string)
+			.nth(occurrence as usize - 1)
+			.map(|found| Value::Str(found.as_str().to_string()))
+			.unwrap_or(Value::Null))
+	}
+	pub fn regexp_count(self, regex: &Regex, arguments: Vec<Self>) -> Result<Self> {
+		expect_pattern_arguments!(arguments, 0, 0);
+		if matches!(self, Value::Null) {
+			return Ok(self);
+		}
+		let string: String = self.convert()?;
+		Ok(Value::I64(regex.find_iter(&string).count() as i64))
+	}
+
+	fn not_null(self) -> Result<Self> {
+		if matches!(self, Value::Null) {
+			Ok(self)
+		} else {
+			self.not()
+		}
+	}
+}
@@ -73,10 +73,37 @@ impl Value {
 		Ok(Value::Str(truncated))
 	}
 	pub fn length(self) -> Result<Value> {
+		if let Value::Bytes(bytes) = self {
+			return Ok(Value::I64(bytes.len() as i64));
+		}
 		let string: String = self.convert()?;
 		Ok(Value::I64(string.len() as i64))
 	}
 
+	pub fn encode(self, format: Value) -> Result<Value> {
+		protect_null!(self);
+		let bytes: Vec<u8> = self.convert()?;
+		let format: String = format.convert()?;
+		Ok(Value::Str(match format.to_lowercase().as_str() {
+			"hex" => hex::encode(bytes),
+			"base64" => base64::encode(bytes),
+			_ => return Err(ValueError::UnsupportedEncoding(format).into()),
+		}))
+	}
+	pub fn decode(self, format: Value) -> Result<Value> {
+		protect_null!(self);
+		let string: String = self.convert()?;
+		let format: String = format.convert()?;
+		let bytes = match format.to_lowercase().as_str() {
+			"hex" => hex::decode(&string).map_err(|_| ValueError::FailedToParseHex(string))?,
+			"base64" => {
+				base64::decode(&string).map_err(|_| ValueError::FailedToParseBase64(string))?
+			}
+			_ => return Err(ValueError::UnsupportedEncoding(format).into()),
+		};
+		Ok(Value::Bytes(bytes))
+	}
+
 	pub fn concat(self, strings: Vec<Value>) -> Result<Value> {
 		strings
 			.into_iter()
@@ -92,6 +119,137 @@ impl Value {
 		Ok(string.replace(&from, &to).into())
 	}
 
+	/// Positions are 1-based and in characters
+	pub fn substring(self, from: Value, length: Value) -> Result<Value> {
+		protect_null!(self);
+		protect_null!(from);
+		let string: String = self.convert()?;
+		let from: i64 = from.convert()?;
+		let start = from - 1;
+		let end = match length {
+			Value::Null => i64::MAX,
+			length => {
+				let length: i64 = length.convert()?;
+				if length < 0 {
+					return Err(ValueError::BadInput(length.into()).into());
+				}
+				start.saturating_add(length)
+			}
+		};
+		let start = start.max(0) as usize;
+		let end = end.max(0) as usize;
+		Ok(Value::Str(
+			string
+				.chars()
+				.skip(start)
+				.take(end.saturating_sub(start))
+				.collect(),
+		))
+	}
+	pub fn trim(self, characters: Value) -> Result<Value> {
+		protect_null!(self);
+		protect_null!(characters);
+		let string: String = self.convert()?;
+		let characters: String = characters.convert()?;
+		Ok(Value::Str(
+			string
+				.trim_matches(|character| characters.contains(character))
+				.to_string(),
+		))
+	}
+	pub fn ltrim(self, characters: Value) -> Result<Value> {
+		protect_null!(self);
+		protect_null!(characters);
+		let string: String = self.convert()?;
+		let characters: String = characters.convert()?;
+		Ok(Value::Str(
+			string
+				.trim_start_matches(|character| characters.contains(character))
+				.to_string(),
+		))
+	}
+	pub fn rtrim(self, characters: Value) -> Result<Value> {
+		protect_null!(self);
+		protect_null!(characters);
+		let string: String = self.convert()?;
+		let characters: String = characters.convert()?;
+		Ok(Value::Str(
+			string
+				.trim_end_matches(|character| characters.contains(character))
+				.to_string(),
+		))
+	}
+	/// 1-based character position of `self` within `string`, 0 if absent
+	pub fn position(self, string: Value) -> Result<Value> {
+		protect_null!(self);
+		protect_null!(string);
+		let substring: String = self.convert()?;
+		let string: String = string.convert()?;
+		Ok(Value::I64(
+			string
+				.find(&substring)
+				.map(|index| string[..index].chars().count() as i64 + 1)
+				.unwrap_or(0),
+		))
+	}
+	pub fn lpad(self, length: Value, fill: Value) -> Result<Value> {
+		self.pad(length, fill, true)
+	}
+	pub fn rpad(self, length: Value, fill: Value) -> Result<Value> {
+		self.pad(length, fill, false)
+	}
+	fn pad(self, length: Value, fill: Value, left: bool) -> Result<Value> {
+		protect_null!(self);
+		protect_null!(length);
+		protect_null!(fill);
+		let string: String = self.convert()?;
+		let length: i64 = length.convert()?;
+		if length < 0 {
+			return Err(ValueError::BadInput(length.into()).into());
+		}
+		let length = length as usize;
+		let fill: String = fill.convert()?;
+
+		let current = string.chars().count();
+		if current >= length || fill.is_empty() {
+			return Ok(Value::Str(string.chars().take(length).collect()));
+		}
+		let padding: String = fill.chars().cycle().take(length - current).collect();
+		Ok(Value::Str(if left {
+			padding + &string
+		} else {
+			string + &padding
+		}))
+	}
+	pub fn reverse(self) -> Result<Value> {
+		protect_null!(self);
+		let string: String = self.convert()?;
+		Ok(Value::Str(string.chars().rev().collect()))
+	}
+	/// Parts are 1-based, out of range gives an empty string
+	pub fn split_part(self, delimiter: Value, part: Value) -> Result<Value> {
+		protect_null!(self);
+		protect_null!(delimiter);
+		protect_null!(part);
+		let string: String = self.convert()?;
+		let delimiter: String = delimiter.convert()?;
+		let part: i64 = part.convert()?;
+		if part < 1 {
+			return Err(ValueError::BadInput(part.into()).into());
+		}
+		// As of PostgreSQL, an empty delimiter splits nothing, the string being the first and only part
+		if delimiter.is_empty() {
+			return Ok(Value::Str(if part == 1 { string } else { String::new() }));
+		}
+		Ok(Value::Str(
+			string
+				.split(delimiter.as_str())
+				.nth(part as usize - 1)
+				.unwrap_or_default()
+				.to_string(),
+		))
+	}
+
 	pub fn round(self, places: Value) -> Result<Value> {
 		if matches!(self, Value::Null) {
 			return Ok(self);
@@ -106,4 +264,77 @@ impl Value {
 		let power: f64 = power.convert()?;
 		Ok(Value::F64(value.powf(power)))
 	}
+
+	pub fn abs(self) -> Result<Value> {
+		Ok(match self {
+			Value::Null => Value::Null,
+			Value::I64(value) => Value::I64(
+				value
+					.checked_abs()
+					.ok_or(ValueError::OutOfRange(Value::I64(value), "ABS"))?,
+			),
+			Value::F64(value) => Value::F64(value.abs()),
+			other => return Err(ValueError::OnlySupportsNumeric(other, "ABS").into()),
+		})
+	}
+	pub fn sign(self) -> Result<Value> {
+		Ok(match self {
+			Value::Null => Value::Null,
+			Value::I64(value) => Value::I64(value.signum()),
+			Value::F64(0.0) => Value::F64(0.0),
+			Value::F64(value) => Value::F64(value.signum()),
+			other => return Err(ValueError::OnlySupportsNumeric(other, "SIGN").into()),
+		})
+	}
+	pub fn ceil(self) -> Result<Value> {
+		Ok(match self {
+			Value::Null => Value::Null,
+			Value::I64(value) => Value::I64(value),
+			Value::F64(value) => Value::F64(value.ceil()),
+			other => return Err(ValueError::OnlySupportsNumeric(other, "CEIL").into()),
+		})
+	}
+	pub fn floor(self) -> Result<Value> {
+		Ok(match self {
+			Value::Null => Value::Null,
+			Value::I64(value) => Value::I64(value),
+			Value::F64(value) => Value::F64(value.floor()),
+			other => return Err(ValueError::OnlySupportsNumeric(other, "FLOOR").into()),
+		})
+	}
+	pub fn sqrt(self) -> Result<Value> {
+		self.float_function(f64::sqrt)
+	}
+	pub fn ln(self) -> Result<Value> {
+		self.float_function(f64::ln)
+	}
+	pub fn exp(self) -> Result<Value> {
+		self.float_function(f64::exp)
+	}
+	pub fn log(self, base: Value) -> Result<Value> {
+		protect_null!(self);
+		protect_null!(base);
+		let value: f64 = self.convert()?;
+		let base: f64 = base.convert()?;
+		// Dedicated functions are more accurate for common bases
+		Ok(Value::F64(if base == 10.0 {
+			value.log10()
+		} else if base == 2.0 {
+			value.log2()
+		} else {
+			value.log(base)
+		}))
+	}
+	fn float_function(self, function: fn(f64) -> f64) -> Result<Value> {
+		protect_null!(self);
+		let value: f64 = self.convert()?;
+		Ok(Value::F64(function(value)))
+	}
+	pub fn modulo(self, divisor: Value) -> Result<Value> {
+		if matches!(divisor, Value::I64(0)) || matches!(divisor, Value::F64(value) if value == 0.0)
+		{
+			return Err(ValueError::DivisionByZero.into());
+		}
+		self.generic_modulus(divisor)
+	}
 }
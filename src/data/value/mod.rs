@@ -12,16 +12,19 @@ use {
 mod big_endian;
 mod cast;
 mod convert;
+mod distinct;
 mod error;
 mod literal;
 mod methods;
 mod serde_convert;
 mod value_type;
 
+pub(crate) use serde_convert::JSONObject;
 pub use {
 	big_endian::BigEndian,
 	cast::{Cast, CastWithRules},
 	convert::{Convert, ConvertFrom},
+	distinct::DistinctKey,
 	error::ValueError,
 	value_type::ValueType,
 };
@@ -79,6 +82,8 @@ pub enum Value {
 	Timestamp(i64),
 
 	Internal(i64),
+
+	Array(Vec<Value>),
 }
 
 impl Hash for Value {
@@ -129,6 +134,12 @@ impl From<String> for Value {
 	}
 }
 
+impl From<Vec<u8>> for Value {
+	fn from(from: Vec<u8>) -> Value {
+		Value::Bytes(from)
+	}
+}
+
 impl<T: Into<Value>> From<Option<T>> for Value {
 	fn from(from: Option<T>) -> Value {
 		from.map(|v| v.into()).unwrap_or(Value::Null)
@@ -155,6 +166,8 @@ impl PartialEq for Value {
 
 			(Value::Internal(l), Value::Internal(r)) => l == r,
 
+			(Value::Array(l), Value::Array(r)) => l == r,
+
 			#[cfg(feature = "implicit_float_conversion")]
 			(Value::I64(l), Value::F64(r)) => (*l as f64) == *r,
 			#[cfg(feature = "implicit_float_conversion")]
@@ -176,6 +189,8 @@ impl PartialOrd for Value {
 
 			(Value::Internal(l), Value::Internal(r)) => Some(l.cmp(r)),
 
+			(Value::Array(l), Value::Array(r)) => l.partial_cmp(r),
+
 			#[cfg(feature = "implicit_float_conversion")]
 			(Value::I64(l), Value::F64(r)) => (*l as f64).partial_cmp(r),
 
@@ -237,6 +252,8 @@ impl Value {
 			| (ValueType::F64, Value::F64(_))
 			| (ValueType::Str, Value::Str(_))
 			| (ValueType::Timestamp, Value::Timestamp(_))
+			| (ValueType::Array, Value::Array(_))
+			| (ValueType::Bytes, Value::Bytes(_))
 			| (ValueType::Any, _)
 			| (_, Value::Null) => Ok(()),
 			(ValueType::Timestamp, Value::I64(val)) => {
@@ -266,11 +283,14 @@ impl Value {
 				| (DataType::Int(_), Value::I64(_))
 				| (DataType::Float(_), Value::F64(_))
 				| (DataType::Text, Value::Str(_))
+				| (DataType::Array(_), Value::Array(_))
 				| (DataType::Boolean, Value::Null)
 				| (DataType::Int(_), Value::Null)
 				| (DataType::Float(_), Value::Null)
 				| (DataType::Text, Value::Null)
-		)
+				| (DataType::Array(_), Value::Null)
+		) || (matches!(self, Value::Bytes(_) | Value::Null)
+			&& ValueType::from(data_type.clone()) == ValueType::Bytes)
 	}
 
 	pub fn validate_null(&self, nullable: bool) -> Result<()> {
@@ -1,4 +1,99 @@
-use crate::{Cast, Value};
+use {
+	crate::{Cast, Value},
+	serde::{
+		de::{MapAccess, Visitor},
+		ser::SerializeMap,
+		Deserialize, Deserializer, Serialize, Serializer,
+	},
+	serde_json::value::Value as JSONValue,
+	std::fmt,
+};
+
+/// A JSON object whose keys are kept in the order they're written, where [serde_json::Map] sorts them.
+/// Of a key given more than once, the last value is kept, as by [serde_json::Map].
+#[derive(Debug, Default)]
+pub(crate) struct JSONObject(Vec<(String, JSONValue)>);
+
+impl JSONObject {
+	pub fn keys(&self) -> impl Iterator<Item = &String> {
+		self.0.iter().map(|(key, _)| key)
+	}
+	pub fn get(&self, key: &str) -> Option<&JSONValue> {
+		self.0
+			.iter()
+			.find_map(|(other, value)| (other == key).then_some(value))
+	}
+	pub fn remove(&mut self, key: &str) -> Option<JSONValue> {
+		let index = self.0.iter().position(|(other, _)| other == key)?;
+		Some(self.0.remove(index).1)
+	}
+	pub fn insert(&mut self, key: String, value: JSONValue) {
+		match self.0.iter_mut().find(|(other, _)| other == &key) {
+			Some((_, old)) => *old = value,
+			None => self.0.push((key, value)),
+		}
+	}
+}
+
+impl FromIterator<(String, JSONValue)> for JSONObject {
+	fn from_iter<I: IntoIterator<Item = (String, JSONValue)>>(entries: I) -> Self {
+		let mut object = JSONObject::default();
+		entries
+			.into_iter()
+			.for_each(|(key, value)| object.insert(key, value));
+		object
+	}
+}
+
+impl Serialize for JSONObject {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(Some(self.0.len()))?;
+		for (key, value) in self.0.iter() {
+			map.serialize_entry(key, value)?;
+		}
+		map.end()
+	}
+}
+
+impl<'de> Deserialize<'de> for JSONObject {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct ObjectVisitor;
+		impl<'de> Visitor<'de> for ObjectVisitor {
+			type Value = JSONObject;
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				formatter.write_str("a JSON object")
+			}
+			fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<JSONObject, A::Error> {
+				let mut object = JSONObject::default();
+				while let Some((key, value)) = map.next_entry()? {
+					object.insert(key, value);
+				}
+				Ok(object)
+			}
+		}
+		deserializer.deserialize_map(ObjectVisitor)
+	}
+}
+
+impl From<serde_json::value::Value> for Value {
+	fn from(value: serde_json::value::Value) -> Value {
+		use serde_json::value::Value as JSONValue;
+		match value {
+			JSONValue::Null => Value::Null,
+			JSONValue::Bool(value) => Value::Bool(value),
+			JSONValue::Number(number) => number
+				.as_i64()
+				.map(Value::I64)
+				.or_else(|| number.as_u64().map(Value::U64))
+				.or_else(|| number.as_f64().map(Value::F64))
+				.unwrap_or(Value::Null),
+			JSONValue::String(value) => Value::Str(value),
+			JSONValue::Array(values) => Value::Array(values.into_iter().map(Value::from).collect()),
+			// Objects are kept as JSON text
+			object => Value::Str(object.to_string()),
+		}
+	}
+}
 
 impl From<Value> for serde_json::value::Value {
 	fn from(value: Value) -> serde_json::value::Value {
@@ -9,6 +104,11 @@ impl From<Value> for serde_json::value::Value {
 			Value::F64(value) => value.into(),
 			Value::Str(value) => value.into(),
 			Value::Null => serde_json::value::Value::Null,
+			Value::Array(values) => values
+				.into_iter()
+				.map(serde_json::value::Value::from)
+				.collect::<Vec<serde_json::value::Value>>()
+				.into(),
 			other => {
 				let string: String = other.cast().unwrap();
 				string.into()
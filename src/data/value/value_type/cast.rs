@@ -11,6 +11,8 @@ impl Value {
 			| (ValueType::I64, Value::I64(_))
 			| (ValueType::F64, Value::F64(_))
 			| (ValueType::Str, Value::Str(_))
+			| (ValueType::Array, Value::Array(_))
+			| (ValueType::Bytes, Value::Bytes(_))
 			| (ValueType::Any, _) => Ok(self.clone()),
 			(_, Value::Null) => Ok(Value::Null),
 
@@ -18,6 +20,7 @@ impl Value {
 			(ValueType::I64, value) => value.clone().cast().map(Value::I64),
 			(ValueType::F64, value) => value.clone().cast().map(Value::F64),
 			(ValueType::Str, value) => value.clone().cast().map(Value::Str),
+			(ValueType::Bytes, value) => value.clone().cast().map(Value::Bytes),
 			(ValueType::Timestamp, value) => {
 				let datetime: NaiveDateTime = value.clone().cast_with_rule(Value::Null)?;
 				let timestamp = datetime.timestamp();
@@ -40,6 +43,8 @@ impl ToString for ValueType {
 			Str => String::from("Text"),
 			Timestamp => String::from("Timestamp"),
 			Any => String::from("Any"),
+			Array => String::from("Array"),
+			Bytes => String::from("Bytes"),
 		}
 	}
 }
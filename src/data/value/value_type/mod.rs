@@ -16,6 +16,8 @@ pub enum ValueType {
 	Str,
 	Timestamp,
 	Any,
+	Array,
+	Bytes,
 }
 impl Default for ValueType {
 	fn default() -> Self {
@@ -31,6 +33,8 @@ impl From<&Value> for ValueType {
 			Value::F64(_) => ValueType::F64,
 			Value::Str(_) => ValueType::Str,
 			Value::Timestamp(_) => ValueType::Timestamp,
+			Value::Array(_) => ValueType::Array,
+			Value::Bytes(_) => ValueType::Bytes,
 			_ => ValueType::Any,
 		}
 	}
@@ -44,6 +48,11 @@ impl From<DataType> for ValueType {
 			DataType::Float(_) => ValueType::F64,
 			DataType::Text => ValueType::Str,
 			DataType::Timestamp => ValueType::Timestamp,
+			DataType::Array(_) => ValueType::Array,
+			DataType::Bytea | DataType::Blob(_) | DataType::Binary(_) | DataType::Varbinary(_) => {
+				ValueType::Bytes
+			}
+			DataType::Custom(name) if name.to_string().to_uppercase() == "BLOB" => ValueType::Bytes,
 			_ => ValueType::Any,
 		}
 	}
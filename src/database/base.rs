@@ -1,5 +1,5 @@
 use {
-	crate::{DatabaseError, IndexFilter, Plane, Result, Schema, Value},
+	crate::{DatabaseError, IndexFilter, Plane, Predicate, Result, Row, Schema, Value},
 	async_trait::async_trait,
 };
 
@@ -17,6 +17,32 @@ pub trait DBBase {
 		Err(DatabaseError::Unimplemented.into())
 	}
 
+	/// Rows which may meet all of `predicates`, holding only the `projection` columns, in that order, where given.
+	/// Databases needn't filter, see [Predicate]; by default every row is scanned and then projected.
+	async fn scan_data_filtered(
+		&self,
+		table_name: &str,
+		_predicates: &[Predicate],
+		projection: Option<&[String]>,
+	) -> Result<Plane> {
+		let plane = self.scan_data(table_name).await?;
+		match projection {
+			Some(projection) => {
+				let schema = self
+					.fetch_schema(table_name)
+					.await?
+					.ok_or(DatabaseError::TableNotFound)?;
+				let columns: Vec<String> = schema
+					.column_defs
+					.into_iter()
+					.map(|column| column.name)
+					.collect();
+				project(plane, &columns, projection)
+			}
+			None => Ok(plane),
+		}
+	}
+
 	async fn scan_data_indexed(
 		&self,
 		_table_name: &str,
@@ -31,4 +57,33 @@ pub trait DBBase {
 	) -> Result<Vec<Value>> {
 		Err(DatabaseError::Unimplemented.into())
 	}
+
+	/// Forgets anything kept from the underlying source (such as schemas), so that it is read again.
+	/// Databases which keep nothing needn't do anything.
+	async fn refresh(&self) -> Result<()> {
+		Ok(())
+	}
+}
+
+/// Keeps only the `projection` columns of each row, of rows with `columns`
+pub fn project(plane: Plane, columns: &[String], projection: &[String]) -> Result<Plane> {
+	let indexes = projection
+		.iter()
+		.map(|name| {
+			columns
+				.iter()
+				.position(|column| column == name)
+				.ok_or_else(|| DatabaseError::ColumnNotFound(name.clone()).into())
+		})
+		.collect::<Result<Vec<usize>>>()?;
+	Ok(plane
+		.into_iter()
+		.map(|(key, Row(values))| {
+			let values = indexes
+				.iter()
+				.map(|index| values.get(*index).cloned().unwrap_or(Value::Null))
+				.collect();
+			(key, Row(values))
+		})
+		.collect())
 }
@@ -1,6 +1,7 @@
 mod auto_increment;
 mod base;
 mod mutable;
+mod registry;
 
 use std::sync::{Mutex, MutexGuard};
 use {
@@ -10,7 +11,12 @@ use {
 	thiserror::Error,
 };
 
-pub use {auto_increment::AutoIncrement, base::DBBase, mutable::DBMut};
+pub use {
+	auto_increment::AutoIncrement,
+	base::{project, DBBase},
+	mutable::DBMut,
+	registry::{scheme_of, DatabaseFactory, DatabaseRegistry},
+};
 
 #[derive(Error, Serialize, Debug, PartialEq)]
 pub enum DatabaseError {
@@ -20,6 +26,12 @@ pub enum DatabaseError {
 	UnknownConnection,
 	#[error("table not found")]
 	TableNotFound,
+	#[error("column not found: {0}")]
+	ColumnNotFound(String),
+	#[error("index not found: {0}")]
+	IndexNotFound(String),
+	#[error("no database is registered for the scheme of: {0}")]
+	UnknownScheme(String),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -31,10 +43,24 @@ pub enum Connection {
 	Sled(String),
 	#[cfg(feature = "csv-database")]
 	CSV(String, crate::CSVSettings),
+	/// Directory of CSV files, one for each table
+	#[cfg(feature = "csv-database")]
+	CSVDirectory(String, crate::CSVSettings),
+	#[cfg(feature = "ndjson-database")]
+	NDJSON(String),
 	#[cfg(feature = "sheet-database")]
 	Sheet(String),
 	#[cfg(feature = "odbc-database")]
 	ODBC(String),
+	/// Directory of Parquet files, or directories of them, one for each table
+	#[cfg(feature = "parquet-database")]
+	Parquet(String),
+	/// SQLite file
+	#[cfg(feature = "sqlite-database")]
+	SQLite(String),
+	/// Location, as `scheme://...`, of a database of a registered scheme.
+	/// Only opened by the [DatabaseRegistry] holding the scheme, see [DatabaseRegistry::open].
+	Custom(String),
 }
 impl Default for Connection {
 	fn default() -> Self {
@@ -55,10 +81,25 @@ impl TryFrom<Connection> for Database {
 				path,
 				settings.clone(),
 			)?),
+			#[cfg(feature = "csv-database")]
+			CSVDirectory(path, settings) => Box::new(crate::CSVDirectoryDatabase::new_with_settings(
+				path,
+				settings.clone(),
+			)?),
+			#[cfg(feature = "ndjson-database")]
+			NDJSON(path) => Box::new(crate::NDJSONDatabase::new(path)?),
 			#[cfg(feature = "sheet-database")]
 			Sheet(path) => Box::new(crate::SheetDatabase::new(path)?),
 			#[cfg(feature = "odbc-database")]
 			ODBC(connection_string) => Box::new(crate::ODBCDatabase::new(connection_string)?),
+			#[cfg(feature = "parquet-database")]
+			Parquet(path) => Box::new(crate::ParquetDatabase::new(path)?),
+			#[cfg(feature = "sqlite-database")]
+			SQLite(path) => Box::new(crate::SQLiteDatabase::new(path)?),
+			Custom(location) => {
+				let scheme = scheme_of(location).unwrap_or(location);
+				return Err(DatabaseError::UnknownScheme(scheme.to_string()).into());
+			}
 			Unknown => return Err(DatabaseError::UnknownConnection.into()),
 		});
 		Ok(Database {
@@ -93,6 +134,7 @@ impl Database {
 	pub fn into_source(self) -> Connection {
 		self.source_connection
 	}
+	/// [Connection::Custom] is opened by [DatabaseRegistry::open] instead
 	pub fn from_source(connection: Connection) -> Result<Self> {
 		connection.try_into()
 	}
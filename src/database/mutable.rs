@@ -1,5 +1,5 @@
 use {
-	crate::{DatabaseError, Result, Row, Schema, SchemaDiff, Value},
+	crate::{DatabaseError, Payload, Result, Row, Schema, SchemaDiff, Value},
 	async_trait::async_trait,
 };
 
@@ -38,4 +38,9 @@ pub trait DBMut {
 	async fn alter_table(&mut self, _table_name: &str, _schema_diff: SchemaDiff) -> Result<()> {
 		Err(DatabaseError::Unimplemented.into())
 	}
+
+	/// Executes a query in the database's own language, for databases which have one; see [Glue::execute_native](crate::Glue::execute_native)
+	async fn execute_native(&mut self, _query: &str) -> Result<Payload> {
+		Err(DatabaseError::Unimplemented.into())
+	}
 }
@@ -0,0 +1,79 @@
+use {
+	super::{Database, DatabaseError, DatabaseInner},
+	crate::{Connection, Result},
+	std::{
+		collections::HashMap,
+		sync::{Arc, Mutex},
+	},
+};
+
+/// Makes databases from locations of a URI scheme, see [Glue::register_database](crate::Glue::register_database).
+///
+/// Implemented for any `Fn(&str) -> Result<Box<DatabaseInner>>`, which is given the whole location (scheme included).
+pub trait DatabaseFactory: Send + Sync {
+	fn connect(&self, location: &str) -> Result<Box<DatabaseInner>>;
+}
+
+impl<F> DatabaseFactory for F
+where
+	F: Fn(&str) -> Result<Box<DatabaseInner>> + Send + Sync,
+{
+	fn connect(&self, location: &str) -> Result<Box<DatabaseInner>> {
+		self(location)
+	}
+}
+
+/// Factories for databases of URI schemes, each [Glue](crate::Glue) has its own; see [Glue::register_database](crate::Glue::register_database).
+/// Schemes are case insensitive.
+#[derive(Clone, Default)]
+pub struct DatabaseRegistry {
+	factories: HashMap<String, Arc<dyn DatabaseFactory>>,
+}
+
+impl DatabaseRegistry {
+	/// Registering a scheme again replaces its factory.
+	pub fn register_database<F: DatabaseFactory + 'static>(&mut self, scheme: &str, factory: F) {
+		self.factories
+			.insert(scheme.to_lowercase(), Arc::new(factory));
+	}
+	/// Whether a factory is registered for the scheme
+	pub fn is_registered(&self, scheme: &str) -> bool {
+		self.factories.contains_key(&scheme.to_lowercase())
+	}
+	/// Opens a [Connection], as [Database::from_source] does, but with the factories of this registry for [Connection::Custom].
+	pub fn open(&self, connection: Connection) -> Result<Database> {
+		match &connection {
+			Connection::Custom(location) => {
+				let database = Mutex::new(self.connect(location)?);
+				Ok(Database {
+					database,
+					source_connection: connection,
+				})
+			}
+			_ => connection.try_into(),
+		}
+	}
+	pub(crate) fn connect(&self, location: &str) -> Result<Box<DatabaseInner>> {
+		let scheme = scheme_of(location)
+			.ok_or_else(|| DatabaseError::UnknownScheme(location.to_string()))?;
+		let factory = self
+			.factories
+			.get(&scheme.to_lowercase())
+			.ok_or_else(|| DatabaseError::UnknownScheme(scheme.to_string()))?;
+		factory.connect(location)
+	}
+}
+
+/// The scheme of a location such as `scheme://...`, if it has one
+pub fn scheme_of(location: &str) -> Option<&str> {
+	let (scheme, _) = location.split_once("://")?;
+	let mut characters = scheme.chars();
+	(characters
+		.next()
+		.map(|first| first.is_ascii_alphabetic())
+		.unwrap_or(false)
+		&& characters.all(|character| {
+			character.is_ascii_alphanumeric() || matches!(character, '+' | '-' | '.')
+		}))
+	.then_some(scheme)
+}
@@ -22,8 +22,10 @@ impl AutoIncrement for CSVDatabase {
 		)>,
 	> {
 		let lines: i64 = count_lines(
-			std::fs::File::open(self.path.as_str())
-				.map_err(|error| WIPError::Debug(format!("{:?}", error)))?,
+			self.csv_settings.decoded(
+				std::fs::File::open(self.path.as_str())
+					.map_err(|error| WIPError::Debug(format!("{:?}", error)))?,
+			)?,
 		)
 		.map_err(|error| WIPError::Debug(format!("{:?}", error)))? as i64;
 		Ok(columns
@@ -1,6 +1,9 @@
 use {
-	super::{utils::csv_reader, CSVDatabase},
-	crate::{DBBase, Plane, Result, Row, Schema, Value, WIPError},
+	super::{utils::csv_reader, CSVDatabase, FileState},
+	crate::{
+		project, DBBase, Plane, Predicate, Result, Row, RowFilter, Schema, Value, ValueType,
+		WIPError,
+	},
 	async_trait::async_trait,
 };
 
@@ -18,26 +21,82 @@ impl DBBase for CSVDatabase {
 	}
 
 	async fn scan_data(&self, _table_name: &str) -> Result<Plane> {
+		self.read_rows(&RowFilter::new(&[], &[]))
+	}
+
+	/// Rows which don't meet the predicates are dropped as they're read, rather than after the whole file is
+	async fn scan_data_filtered(
+		&self,
+		_table_name: &str,
+		predicates: &[Predicate],
+		projection: Option<&[String]>,
+	) -> Result<Plane> {
+		let columns: Vec<String> = self
+			.schema
+			.as_ref()
+			.map(|schema| {
+				schema
+					.column_defs
+					.iter()
+					.map(|column_def| column_def.name.clone())
+					.collect()
+			})
+			.unwrap_or_default();
+		let plane = self.read_rows(&RowFilter::new(predicates, &columns))?;
+		match projection {
+			Some(projection) => project(plane, &columns, projection),
+			None => Ok(plane),
+		}
+	}
+}
+
+impl CSVDatabase {
+	/// The file is noted as it was before reading, so that keys aren't taken to be of a file written since, see [FileState]
+	fn read_rows(&self, filter: &RowFilter) -> Result<Plane> {
+		let state = FileState::of(&self.path)?;
 		let mut reader = csv_reader(self)?;
+		let data_types: Vec<ValueType> = self
+			.schema
+			.as_ref()
+			.map(|schema| {
+				schema
+					.column_defs
+					.iter()
+					.map(|column_def| column_def.data_type.clone())
+					.collect()
+			})
+			.unwrap_or_default();
 
 		#[allow(clippy::needless_collect)]
 		// Clippy doesn't understand the need. Needed because we have borrowed values within.
-		reader
+		let plane = reader
 			.records()
 			.enumerate()
 			.map(|(index, record)| {
 				record
 					.map_err(|error| WIPError::Debug(format!("{:?}", error)).into())
-					.map(|record| {
-						(
-							Value::I64(index as i64),
+					.and_then(|record| {
+						Ok((
+							Value::I64(self.row_keys.key(index)),
 							Row(record
 								.into_iter()
-								.map(|cell| Value::Str(cell.to_string()))
-								.collect()),
-						)
+								.enumerate()
+								.map(|(column, cell)| {
+									self.csv_settings.parse_cell(
+										cell,
+										data_types.get(column).unwrap_or(&ValueType::Any),
+									)
+								})
+								.collect::<Result<_>>()?),
+						))
 					})
 			})
-			.collect::<Result<_>>()
+			.filter(|row| match row {
+				Ok((_, row)) => filter.confirm(&row.0),
+				Err(_) => true,
+			})
+			.collect::<Result<Plane>>()?;
+		*self.read_state() = Some(state);
+		Ok(plane)
 	}
 }
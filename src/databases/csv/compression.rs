@@ -0,0 +1,100 @@
+use {
+	crate::{Result, WIPError},
+	flate2::{read::MultiGzDecoder, write::GzEncoder},
+	serde::{Deserialize, Serialize},
+	std::{
+		fs::File,
+		io::{self, Read, Write},
+	},
+};
+
+/// Compression of a file, of any number of parts one after another, as appending makes
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CSVCompression {
+	Gzip,
+	Zstd,
+}
+
+impl CSVCompression {
+	/// The compression of a file, by its extension: `.gz` or `.zst`
+	pub fn of_path(path: &str) -> Option<Self> {
+		if path.ends_with(".gz") {
+			Some(Self::Gzip)
+		} else if path.ends_with(".zst") {
+			Some(Self::Zstd)
+		} else {
+			None
+		}
+	}
+	/// Whether a path is of a CSV file, compressed or not
+	pub fn is_csv_path(path: &str) -> bool {
+		path.trim_end_matches(".gz")
+			.trim_end_matches(".zst")
+			.ends_with(".csv")
+	}
+}
+
+/// Reads a file decompressed; empty files, as are made for new tables, are read as they are
+pub(crate) fn decompressor(
+	compression: Option<CSVCompression>,
+	inner: File,
+) -> Result<Box<dyn Read>> {
+	let is_empty = inner
+		.metadata()
+		.map_err(|error| WIPError::Debug(format!("{:?}", error)))?
+		.len() == 0;
+	Ok(match compression {
+		None => Box::new(inner),
+		Some(_) if is_empty => Box::new(inner),
+		Some(CSVCompression::Gzip) => Box::new(MultiGzDecoder::new(inner)),
+		Some(CSVCompression::Zstd) => Box::new(
+			zstd::Decoder::new(inner).map_err(|error| WIPError::Debug(format!("{:?}", error)))?,
+		),
+	})
+}
+
+/// Writes compressed, where there's compression; must be finished for what's written to be whole
+pub(crate) enum Compressor<W: Write> {
+	None(W),
+	Gzip(GzEncoder<W>),
+	Zstd(zstd::Encoder<'static, W>),
+}
+
+impl<W: Write> Compressor<W> {
+	pub fn new(compression: Option<CSVCompression>, inner: W) -> Result<Self> {
+		Ok(match compression {
+			None => Self::None(inner),
+			Some(CSVCompression::Gzip) => {
+				Self::Gzip(GzEncoder::new(inner, flate2::Compression::default()))
+			}
+			Some(CSVCompression::Zstd) => Self::Zstd(
+				zstd::Encoder::new(inner, 0)
+					.map_err(|error| WIPError::Debug(format!("{:?}", error)))?,
+			),
+		})
+	}
+	pub fn finish(self) -> io::Result<W> {
+		match self {
+			Self::None(inner) => Ok(inner),
+			Self::Gzip(encoder) => encoder.finish(),
+			Self::Zstd(encoder) => encoder.finish(),
+		}
+	}
+}
+
+impl<W: Write> Write for Compressor<W> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		match self {
+			Self::None(inner) => inner.write(buf),
+			Self::Gzip(encoder) => encoder.write(buf),
+			Self::Zstd(encoder) => encoder.write(buf),
+		}
+	}
+	fn flush(&mut self) -> io::Result<()> {
+		match self {
+			Self::None(inner) => inner.flush(),
+			Self::Gzip(encoder) => encoder.flush(),
+			Self::Zstd(encoder) => encoder.flush(),
+		}
+	}
+}
@@ -0,0 +1,286 @@
+use {
+	super::{CSVDatabase, CSVDatabaseError, CSVSettings},
+	crate::{
+		AutoIncrement, DBBase, DBFull, DBMut, Database, DatabaseError, Plane, Predicate, Result,
+		Row, Schema, SchemaChange, SchemaDiff, Value, WIPError,
+	},
+	async_trait::async_trait,
+	std::{
+		collections::HashMap,
+		path::{Path, PathBuf},
+		sync::{Arc, Mutex},
+	},
+};
+
+const EXTENSION: &str = "csv";
+
+/// A directory of CSV files, each `<table>.csv` being a table.
+/// Files are only read, and their schemas discerned, once their table is first used.
+pub struct CSVDirectoryDatabase {
+	path: PathBuf,
+	pub csv_settings: CSVSettings,
+	tables: Mutex<HashMap<String, Arc<CSVDatabase>>>,
+}
+
+impl DBFull for CSVDirectoryDatabase {}
+
+impl Database {
+	pub fn new_csv_directory(storage: CSVDirectoryDatabase) -> Self {
+		Self::new(Box::new(storage))
+	}
+}
+impl CSVDirectoryDatabase {
+	pub fn new(path: &str) -> Result<Self> {
+		Self::new_with_settings(path, CSVSettings::default())
+	}
+	/// Creates the directory where there isn't one
+	pub fn new_with_settings(path: &str, csv_settings: CSVSettings) -> Result<Self> {
+		std::fs::create_dir_all(path).map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		Ok(Self {
+			path: PathBuf::from(path),
+			csv_settings,
+			tables: Mutex::new(HashMap::new()),
+		})
+	}
+
+	/// Whether a path is of an existing directory holding any CSV files
+	pub fn is_csv_directory(path: &str) -> bool {
+		std::fs::read_dir(path)
+			.map(|entries| {
+				entries
+					.filter_map(|entry| entry.ok())
+					.any(|entry| table_name_of(&entry.path()).is_some())
+			})
+			.unwrap_or(false)
+	}
+
+	/// Names which would be of a file elsewhere, by path separators or `..`, aren't of tables.
+	/// The table's file, where there is one, is found whatever the case of its extension, as `.CSV`;
+	/// otherwise it would be `<table>.csv`.
+	fn table_path(&self, table_name: &str) -> Result<PathBuf> {
+		if table_name.is_empty() || table_name.contains(['/', '\\']) || table_name.contains("..") {
+			return Err(CSVDatabaseError::InvalidTableName(table_name.to_string()).into());
+		}
+		let path = self.path.join(format!("{}.{}", table_name, EXTENSION));
+		if path.is_file() {
+			return Ok(path);
+		}
+		Ok(self
+			.table_files()?
+			.find(|(name, _)| name == table_name)
+			.map(|(_, path)| path)
+			.unwrap_or(path))
+	}
+
+	fn table_names(&self) -> Result<Vec<String>> {
+		let mut table_names: Vec<String> = self.table_files()?.map(|(name, _)| name).collect();
+		table_names.sort();
+		Ok(table_names)
+	}
+
+	/// Each table's name, with the path of its file
+	fn table_files(&self) -> Result<impl Iterator<Item = (String, PathBuf)>> {
+		Ok(std::fs::read_dir(&self.path)
+			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?
+			.filter_map(|entry| entry.ok())
+			.filter_map(|entry| Some((table_name_of(&entry.path())?, entry.path()))))
+	}
+
+	fn tables(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<CSVDatabase>>> {
+		self.tables
+			.lock()
+			.expect("Unreachable: CSV directory tables poisoned!")
+	}
+
+	fn open(&self, table_name: &str) -> Result<CSVDatabase> {
+		let path = self.table_path(table_name)?;
+		CSVDatabase::new_with_settings(&path.to_string_lossy(), self.csv_settings.clone())
+	}
+
+	/// The table's file, opened if it hasn't been; [None] where there's no such file
+	fn table(&self, table_name: &str) -> Result<Option<Arc<CSVDatabase>>> {
+		let mut tables = self.tables();
+		if let Some(table) = tables.get(table_name) {
+			return Ok(Some(table.clone()));
+		}
+		if !self.table_path(table_name)?.is_file() {
+			return Ok(None);
+		}
+		let table = Arc::new(self.open(table_name)?);
+		tables.insert(table_name.to_string(), table.clone());
+		Ok(Some(table))
+	}
+
+	fn table_mut(&mut self, table_name: &str) -> Result<&mut CSVDatabase> {
+		if self.table(table_name)?.is_none() {
+			return Err(DatabaseError::TableNotFound.into());
+		}
+		let table = self
+			.tables
+			.get_mut()
+			.expect("Unreachable: CSV directory tables poisoned!")
+			.get_mut(table_name)
+			.expect("Unreachable: Table was just opened!");
+		Arc::get_mut(table)
+			.ok_or_else(|| CSVDatabaseError::TableInUse(table_name.to_string()).into())
+	}
+
+	fn existing_table(&self, table_name: &str) -> Result<Arc<CSVDatabase>> {
+		self.table(table_name)?
+			.ok_or_else(|| DatabaseError::TableNotFound.into())
+	}
+}
+
+/// Name of the table of a file, where it is a CSV file
+fn table_name_of(path: &Path) -> Option<String> {
+	if !path.is_file() {
+		return None;
+	}
+	let extension = path.extension()?.to_str()?;
+	if !extension.eq_ignore_ascii_case(EXTENSION) {
+		return None;
+	}
+	path.file_stem()?.to_str().map(String::from)
+}
+
+#[async_trait(?Send)]
+impl DBBase for CSVDirectoryDatabase {
+	async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
+		let table = match self.table(table_name)? {
+			Some(table) => table,
+			None => return Ok(None),
+		};
+		Ok(table.fetch_schema(table_name).await?.map(|mut schema| {
+			schema.table_name = table_name.to_string();
+			schema
+		}))
+	}
+	async fn scan_schemas(&self) -> Result<Vec<Schema>> {
+		let mut schemas = vec![];
+		for table_name in self.table_names()? {
+			if let Some(schema) = self.fetch_schema(&table_name).await? {
+				schemas.push(schema);
+			}
+		}
+		Ok(schemas)
+	}
+
+	async fn scan_data(&self, table_name: &str) -> Result<Plane> {
+		self.existing_table(table_name)?.scan_data(table_name).await
+	}
+
+	async fn scan_data_filtered(
+		&self,
+		table_name: &str,
+		predicates: &[Predicate],
+		projection: Option<&[String]>,
+	) -> Result<Plane> {
+		self.existing_table(table_name)?
+			.scan_data_filtered(table_name, predicates, projection)
+			.await
+	}
+}
+
+#[async_trait(?Send)]
+impl DBMut for CSVDirectoryDatabase {
+	/// Creates the table's file
+	async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
+		let table_name = schema.table_name.clone();
+		let mut table = self.open(&table_name)?;
+		table.insert_schema(schema).await?;
+		self.tables().insert(table_name, Arc::new(table));
+		Ok(())
+	}
+
+	/// Deletes the table's file
+	async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
+		self.tables().remove(table_name);
+		let path = self.table_path(table_name)?;
+		std::fs::remove_file(&path).map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		Ok(())
+	}
+
+	async fn insert_data(&mut self, table_name: &str, rows: Vec<Row>) -> Result<()> {
+		self.table_mut(table_name)?
+			.insert_data(table_name, rows)
+			.await
+	}
+
+	async fn update_data(&mut self, table_name: &str, rows: Vec<(Value, Row)>) -> Result<()> {
+		self.table_mut(table_name)?
+			.update_data(table_name, rows)
+			.await
+	}
+
+	async fn delete_data(&mut self, table_name: &str, keys: Vec<Value>) -> Result<()> {
+		self.table_mut(table_name)?
+			.delete_data(table_name, keys)
+			.await
+	}
+
+	/// Renaming a table renames its file
+	async fn alter_table(&mut self, table_name: &str, schema_diff: SchemaDiff) -> Result<()> {
+		let new_table_name = schema_diff
+			.changes()
+			.iter()
+			.rev()
+			.find_map(|change| match change {
+				SchemaChange::RenameTable(new_table_name) => Some(new_table_name.clone()),
+				_ => None,
+			})
+			.filter(|new_table_name| new_table_name != table_name);
+
+		self.table_mut(table_name)?
+			.alter_table(table_name, schema_diff)
+			.await?;
+
+		if let Some(new_table_name) = new_table_name {
+			let old_path = self.table_path(table_name)?;
+			let path = self.table_path(&new_table_name)?;
+			std::fs::rename(&old_path, &path)
+				.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+			self.table_mut(table_name)?.path = path.to_string_lossy().to_string();
+			let tables = self
+				.tables
+				.get_mut()
+				.expect("Unreachable: CSV directory tables poisoned!");
+			if let Some(table) = tables.remove(table_name) {
+				tables.insert(new_table_name, table);
+			}
+		}
+		Ok(())
+	}
+}
+
+#[async_trait(?Send)]
+impl AutoIncrement for CSVDirectoryDatabase {
+	async fn generate_increment_values(
+		&mut self,
+		table_name: String,
+		columns: Vec<(
+			usize,  /*index*/
+			String, /*name*/
+			i64,    /*row_count*/
+		) /*column*/>,
+	) -> Result<
+		Vec<(
+			/*column*/ (usize /*index*/, String /*name*/),
+			/*start_value*/ i64,
+		)>,
+	> {
+		self.table_mut(&table_name)?
+			.generate_increment_values(table_name, columns)
+			.await
+	}
+
+	async fn set_increment_value(
+		&mut self,
+		table_name: &str,
+		column_name: &str,
+		end: i64,
+	) -> Result<()> {
+		self.table_mut(table_name)?
+			.set_increment_value(table_name, column_name, end)
+			.await
+	}
+}
@@ -1,64 +1,155 @@
 use {
-	super::string_record_to_row,
-	crate::{data::Schema, CSVDatabaseError, CSVSettings, Cast, Column, Result, Value, ValueType},
+	super::compression::decompressor,
+	crate::{
+		data::Schema, types::LabelsAndRows, CSVDatabaseError, CSVSettings, Column, JoinError,
+		Result, Value, ValueType, WIPError,
+	},
 	csv::{Reader, ReaderBuilder, StringRecord},
-	std::{default::Default, fs::File},
+	encoding_rs::{Encoding, UTF_8},
+	std::{
+		collections::HashSet,
+		fs::File,
+		io::{BufRead, BufReader, Cursor, Read},
+	},
 };
 
 impl CSVSettings {
-	pub(crate) fn new_reader(&self, file: File) -> Reader<File> {
-		ReaderBuilder::new()
+	pub(crate) fn reader_builder(&self) -> ReaderBuilder {
+		let mut builder = ReaderBuilder::new();
+		builder
 			.delimiter(self.delimiter)
 			.has_headers(self.has_header.unwrap_or(true))
-			.from_reader(file)
+			.quoting(self.quoting)
+			.comment(self.comment);
+		builder
+	}
+	pub(crate) fn new_reader(&self, file: File) -> Result<Reader<Box<dyn BufRead>>> {
+		let mut source = self.decoded(file)?;
+		self.read_preamble(&mut source)?;
+		Ok(self.reader_builder().from_reader(source))
+	}
+	/// The encoding of files, [None] where UTF-8
+	pub(crate) fn encoding(&self) -> Result<Option<&'static Encoding>> {
+		match &self.encoding {
+			None => Ok(None),
+			Some(label) => Encoding::for_label(label.as_bytes())
+				.map(|encoding| (encoding != UTF_8).then_some(encoding))
+				.ok_or_else(|| CSVDatabaseError::UnknownEncoding(label.clone()).into()),
+		}
+	}
+	/// A file's text, decompressed, as UTF-8
+	pub(crate) fn decoded(&self, file: File) -> Result<Box<dyn BufRead>> {
+		let mut file = BufReader::new(decompressor(self.compression, file)?);
+		Ok(match self.encoding()? {
+			None => Box::new(file),
+			// Decoded all at once, rather than as it's read
+			Some(encoding) => {
+				let mut bytes = vec![];
+				file.read_to_end(&mut bytes)
+					.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+				let (text, _, _) = encoding.decode(&bytes);
+				Box::new(Cursor::new(text.into_owned().into_bytes()))
+			}
+		})
+	}
+	/// Reads the rows to be skipped, returning them as they were
+	pub(crate) fn read_preamble(&self, source: &mut dyn BufRead) -> Result<String> {
+		let mut preamble = String::new();
+		for _ in 0..self.skip_rows {
+			let read = source
+				.read_line(&mut preamble)
+				.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+			if read == 0 {
+				break;
+			}
+		}
+		Ok(preamble)
+	}
+	/// Reads a whole file, without a database, for `READ_CSV`
+	pub(crate) fn read_file(mut self, path: &str) -> Result<LabelsAndRows> {
+		let open = || -> Result<File> {
+			File::open(path).map_err(|error| {
+				JoinError::FailedToReadFile(path.to_string(), error.to_string()).into()
+			})
+		};
+		let labels = self
+			.discern_schema(open()?)?
+			.map(|schema| {
+				schema
+					.column_defs
+					.into_iter()
+					.map(|column_def| column_def.name)
+					.collect()
+			})
+			.unwrap_or_default();
+		let rows = self
+			.new_reader(open()?)?
+			.into_records()
+			.map(|record| {
+				record
+					.map(|record| record.iter().map(|cell| self.infer_value(cell)).collect())
+					.map_err(|error| WIPError::Debug(format!("{:?}", error)).into())
+			})
+			.collect::<Result<Vec<_>>>()?;
+		Ok((labels, rows))
 	}
 	pub(crate) fn discern_header(&mut self, header: &StringRecord) -> Vec<String> {
-		let header = string_record_to_row(header);
-
 		let has_header = if let Some(has_header) = self.has_header {
 			has_header
 		} else {
-			let has_header = !header
-				.iter()
-				.map(ValueType::from)
-				.any(|vt| vt != ValueType::Str);
+			let has_header = self.is_header(header);
 			self.has_header = Some(has_header);
 			has_header
 		};
 
 		if has_header {
-			header
-				.into_iter()
-				.map(Cast::cast)
-				.collect::<Result<Vec<String>>>()
-				.unwrap()
+			header.iter().map(String::from).collect()
 		} else {
-			header
-				.into_iter()
-				.enumerate()
-				.map(|(index, _)| format!("column_{}", index))
+			(0..header.len())
+				.map(|index| format!("column_{}", index))
 				.collect()
 		}
 	}
+	/// Whether a first row is of names: each distinct, and text rather than of any other type
+	fn is_header(&self, header: &StringRecord) -> bool {
+		let mut names = HashSet::new();
+		header
+			.iter()
+			.all(|name| matches!(self.infer_value(name), Value::Str(_)) && names.insert(name))
+	}
 	pub(crate) fn discern_schema(&mut self, file: File) -> Result<Option<Schema>> {
-		let mut reader = self.new_reader(file);
+		let mut reader = self.new_reader(file)?;
 		let header = reader
 			.headers()
-			.map_err(|error| CSVDatabaseError::HeaderError(format!("{:?}", error)))?;
-		let header = self.discern_header(header);
-		if header.is_empty() {
+			.map_err(|error| CSVDatabaseError::HeaderError(format!("{:?}", error)))?
+			.clone();
+		let names = self.discern_header(&header);
+		if names.is_empty() {
 			return Ok(None);
 		}
-		let value_types = self.discern_types(reader);
+		let sample = (self.has_header == Some(false))
+			.then_some(Ok(header))
+			.into_iter()
+			.chain(reader.into_records())
+			.take(self.sample_rows)
+			.collect::<std::result::Result<Vec<StringRecord>, _>>()
+			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
 
-		let column_defs = header
+		let column_defs = names
 			.into_iter()
-			.zip(value_types)
-			.map(|(header, value_type)| {
-				let mut column = Column::default();
-				column.name = header;
-				column.data_type = value_type;
-				column
+			.enumerate()
+			.map(|(index, name)| {
+				let (data_type, is_nullable) = self.discern_column(
+					sample
+						.iter()
+						.map(|record| record.get(index).unwrap_or_default()),
+				);
+				Column {
+					data_type: self.column_types.get(&name).cloned().unwrap_or(data_type),
+					name,
+					is_nullable,
+					..Column::default()
+				}
 			})
 			.collect();
 		Ok(Some(Schema {
@@ -67,30 +158,32 @@ impl CSVSettings {
 			indexes: vec![],
 		}))
 	}
-	pub(crate) fn discern_types(&self, reader: Reader<File>) -> Vec<ValueType> {
-		let sample = reader
-			.into_records()
-			.take(self.sample_rows)
-			.map(|record| string_record_to_row(&record.unwrap()))
-			.collect();
-		discern_types_from_sample(sample)
-	}
-}
-
-pub(crate) fn discern_types_from_sample(sample: Vec<Vec<Value>>) -> Vec<ValueType> {
-	let mut types = sample
-		.into_iter()
-		.map(|row| row.iter().map(ValueType::from).collect());
-	let first_types = types.next().unwrap();
-	types.fold(first_types, |mut out_types, row_types| {
-		out_types
-			.iter_mut()
-			.zip(row_types)
-			.for_each(|(out_type, row_type)| {
-				if out_type != &row_type {
-					*out_type = ValueType::Any
+	/// The type of a column's cells, and whether any are null.
+	/// Integers among floats are taken as floats, otherwise columns of cells of differing types are of [ValueType::Any].
+	fn discern_column<'a>(&self, cells: impl Iterator<Item = &'a str>) -> (ValueType, bool) {
+		let mut data_type = None;
+		let mut is_nullable = false;
+		for cell in cells {
+			let value = self.infer_value(cell);
+			if matches!(value, Value::Null) {
+				is_nullable = true;
+				continue;
+			}
+			let cell_type = ValueType::from(&value);
+			data_type = Some(match data_type {
+				None => cell_type,
+				Some(data_type) if data_type == cell_type => data_type,
+				Some(ValueType::I64 | ValueType::F64)
+					if matches!(cell_type, ValueType::I64 | ValueType::F64) =>
+				{
+					ValueType::F64
 				}
+				Some(_) => ValueType::Any,
 			});
-		out_types
-	})
+		}
+		match data_type {
+			Some(data_type) => (data_type, is_nullable),
+			None => (ValueType::Any, true),
+		}
+	}
 }
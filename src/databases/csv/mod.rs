@@ -1,16 +1,26 @@
 mod auto_increment;
 mod base;
+mod compression;
+mod directory;
 mod discern;
 mod mutable;
+mod options;
 mod record;
 mod utils;
 
-pub use record::*;
+pub use {compression::CSVCompression, directory::CSVDirectoryDatabase};
 
 use {
-	crate::{data::Schema, DBFull, Database, Result, WIPError},
+	crate::{data::Schema, DBFull, Database, Result, ValueType, WIPError},
 	serde::{Deserialize, Serialize},
-	std::{default::Default, fmt::Debug, fs::OpenOptions},
+	std::{
+		collections::HashMap,
+		default::Default,
+		fmt::Debug,
+		fs::OpenOptions,
+		sync::{Mutex, MutexGuard},
+		time::SystemTime,
+	},
 	thiserror::Error,
 };
 
@@ -21,12 +31,32 @@ pub enum CSVDatabaseError {
 
 	#[error("Failed to open CSV because of a error with header: {0}")]
 	HeaderError(String),
+
+	#[error("unknown CSV option: {0}")]
+	UnknownOption(String),
+	#[error("invalid value for CSV option {0}: {1}")]
+	InvalidOption(String, String),
+	#[error("unknown encoding: {0}")]
+	UnknownEncoding(String),
+	#[error("text can't be written as {0}")]
+	UnwritableEncoding(String),
+	#[error("CSV file of comments can't be rewritten, as by UPDATE, DELETE or ALTER TABLE, without losing them")]
+	UnwritableComments,
+	#[error("CSV file was written by another since its rows were read")]
+	ChangedSinceRead,
+	#[error("table name can't be of a CSV file in the directory: {0}")]
+	InvalidTableName(String),
+	#[error("table is still in use: {0}")]
+	TableInUse(String),
 }
 
 pub struct CSVDatabase {
 	schema: Option<Schema>,
 	path: String,
 	pub csv_settings: CSVSettings,
+	row_keys: RowKeys,
+	/// The file as it was when rows, and so their keys, were last read
+	read_state: Mutex<Option<FileState>>,
 }
 #[derive(Clone, Serialize, Deserialize)]
 pub struct CSVSettings {
@@ -34,6 +64,20 @@ pub struct CSVSettings {
 	pub quoting: bool,
 	pub has_header: Option<bool>,
 	pub sample_rows: usize,
+	/// Types of columns, by name, taken in place of those discerned
+	pub column_types: HashMap<String, ValueType>,
+	/// Cells which are null, besides empty cells of columns which aren't text.
+	/// Where not given, empty cells of text columns are null too, so that NULL may be written as an empty cell.
+	pub null_token: Option<String>,
+	/// Label of the encoding of the file, such as `latin1`; UTF-8 where not given
+	pub encoding: Option<String>,
+	/// Lines starting with this are skipped.
+	/// Rows may only be appended to files of comments, as rewriting them, to update or delete rows or alter the table, would lose the comments.
+	pub comment: Option<u8>,
+	/// Lines skipped, before the header, at the start of the file
+	pub skip_rows: usize,
+	/// Compression of the file; taken from the file's extension, where not given, by [CSVDatabase::new_with_settings]
+	pub compression: Option<CSVCompression>,
 }
 impl Default for CSVSettings {
 	fn default() -> Self {
@@ -42,6 +86,12 @@ impl Default for CSVSettings {
 			quoting: true,
 			has_header: None,
 			sample_rows: 100,
+			column_types: HashMap::new(),
+			null_token: None,
+			encoding: None,
+			comment: None,
+			skip_rows: 0,
+			compression: None,
 		}
 	}
 }
@@ -58,6 +108,9 @@ impl CSVDatabase {
 		Self::new_with_settings(path, CSVSettings::default())
 	}
 	pub fn new_with_settings(path: &str, mut csv_settings: CSVSettings) -> Result<Self> {
+		csv_settings.compression = csv_settings
+			.compression
+			.or_else(|| CSVCompression::of_path(path));
 		let file = OpenOptions::new()
 			.read(true)
 			.write(true)
@@ -70,6 +123,57 @@ impl CSVDatabase {
 			schema,
 			path: path.to_string(),
 			csv_settings,
+			row_keys: RowKeys::default(),
+			read_state: Mutex::new(None),
 		})
 	}
 }
+
+/// Keys of rows, by their position in the file, which stay with rows as others are deleted.
+/// Until any are deleted, rows are keyed by their position.
+#[derive(Default)]
+pub(crate) struct RowKeys(Option<(Vec<i64>, i64 /*next*/)>);
+
+impl RowKeys {
+	pub fn key(&self, position: usize) -> i64 {
+		match &self.0 {
+			None => position as i64,
+			Some((keys, next)) => keys
+				.get(position)
+				.copied()
+				.unwrap_or_else(|| next + (position - keys.len()) as i64),
+		}
+	}
+	/// Keeps keys of only the rows kept, of as many as there were
+	pub fn retain(&mut self, count: usize, keep: impl Fn(usize) -> bool) {
+		let next = self.key(count);
+		let keys = (0..count)
+			.filter(|position| keep(*position))
+			.map(|position| self.key(position))
+			.collect();
+		self.0 = Some((keys, next));
+	}
+	pub fn reset(&mut self) {
+		self.0 = None;
+	}
+}
+
+/// Length and time of last change of a file, by which to tell whether it has been written since
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct FileState(u64, Option<SystemTime>);
+
+impl FileState {
+	pub fn of(path: &str) -> Result<Self> {
+		let metadata =
+			std::fs::metadata(path).map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		Ok(Self(metadata.len(), metadata.modified().ok()))
+	}
+}
+
+impl CSVDatabase {
+	pub(crate) fn read_state(&self) -> MutexGuard<'_, Option<FileState>> {
+		self.read_state
+			.lock()
+			.expect("Unreachable: CSV read state poisoned!")
+	}
+}
@@ -1,9 +1,17 @@
 use {
-	super::{CSVDatabase, CSVDatabaseError},
-	crate::{Cast, DBMut, Result, Row, Schema, WIPError},
+	super::{
+		utils::{csv_reader_with_preamble, csv_writer, encoded_writer, finish_writer, FileWriter},
+		CSVDatabase, CSVDatabaseError, FileState,
+	},
+	crate::{Cast, DBMut, DatabaseError, Result, Row, Schema, SchemaDiff, Value, WIPError},
 	async_trait::async_trait,
-	csv::WriterBuilder,
-	std::{fs::OpenOptions, io::Write},
+	fs2::FileExt,
+	std::{
+		collections::{HashMap, HashSet},
+		fs::{File, OpenOptions},
+		io::Write,
+		path::{Path, PathBuf},
+	},
 };
 
 #[async_trait(?Send)]
@@ -13,81 +21,273 @@ impl DBMut for CSVDatabase {
 			return Err(CSVDatabaseError::OnlyOneTableAllowed.into());
 		}
 
-		let mut writer = WriterBuilder::new()
-			.delimiter(self.csv_settings.delimiter)
-			.from_writer(vec![]); // Not good but was having Size issues with moving this elsewhere
-
 		let header: Vec<String> = schema
 			.column_defs
 			.iter()
 			.map(|column_def| column_def.name.clone())
 			.collect();
-
-		writer
-			.write_record(header)
-			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
-
-		writer
-			.flush()
-			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
-
-		let csv_bytes = writer
-			.into_inner()
-			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
-
-		let mut file = OpenOptions::new()
-			.truncate(true)
-			.write(true)
-			.open(self.path.as_str())
-			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
-
-		file.write_all(&csv_bytes)
-			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
-		file.flush()
-			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		self.write_locked(false, || self.write_records(vec![header], false))?;
 
 		self.schema = Some(schema.clone());
+		self.row_keys.reset();
 		Ok(())
 	}
 
 	async fn delete_schema(&mut self, _table_name: &str) -> Result<()> {
 		self.schema = None;
+		self.row_keys.reset();
 		Ok(())
 	}
 
 	async fn insert_data(&mut self, _table_name: &str, rows: Vec<Row>) -> Result<()> {
-		let mut writer = WriterBuilder::new()
-			.delimiter(self.csv_settings.delimiter)
-			.from_writer(vec![]); // Not good but was having Size issues with moving this elsewhere
+		let records = rows
+			.into_iter()
+			.map(|row| self.row_to_record(row))
+			.collect::<Result<Vec<Vec<String>>>>()?;
+		self.write_locked(false, || self.write_records(records, true))
+	}
+
+	/// Rows keep their place, and so their keys
+	async fn update_data(&mut self, _table_name: &str, rows: Vec<(Value, Row)>) -> Result<()> {
+		let mut rows = rows
+			.into_iter()
+			.map(|(key, row)| Ok((key.cast()?, row)))
+			.collect::<Result<HashMap<i64, Row>>>()?;
+		self.write_locked(true, || {
+			self.rewrite_records(None, |position, record| {
+				match rows.remove(&self.row_keys.key(position)) {
+					Some(row) => self.row_to_record(row).map(Some),
+					None => Ok(Some(record)),
+				}
+			})
+		})?;
+		Ok(())
+	}
 
-		for row in rows.into_iter() {
-			let string_row = row
-				.0
-				.into_iter()
-				.map(|cell| cell.cast())
-				.collect::<Result<Vec<String>>>()?;
-			writer
-				.write_record(string_row)
+	async fn delete_data(&mut self, _table_name: &str, keys: Vec<Value>) -> Result<()> {
+		let keys = keys
+			.into_iter()
+			.map(Cast::cast)
+			.collect::<Result<HashSet<i64>>>()?;
+		let mut deleted = HashSet::new();
+		let count = self.write_locked(true, || {
+			self.rewrite_records(None, |position, record| {
+				Ok(if keys.contains(&self.row_keys.key(position)) {
+					deleted.insert(position);
+					None
+				} else {
+					Some(record)
+				})
+			})
+		})?;
+		self.row_keys
+			.retain(count, |position| !deleted.contains(&position));
+		Ok(())
+	}
+
+	/// Rewrites the whole file where columns change
+	async fn alter_table(&mut self, _table_name: &str, schema_diff: SchemaDiff) -> Result<()> {
+		let schema = self.schema.clone().ok_or(DatabaseError::TableNotFound)?;
+		let new_schema = schema_diff.merge(schema.clone())?;
+		let rewriter = schema_diff.row_rewriter(&schema)?;
+		let renamed_columns = schema
+			.column_defs
+			.iter()
+			.map(|column_def| &column_def.name)
+			.ne(new_schema
+				.column_defs
+				.iter()
+				.map(|column_def| &column_def.name));
+		if rewriter.is_some() || renamed_columns {
+			let header = new_schema
+				.column_defs
+				.iter()
+				.map(|column_def| column_def.name.clone())
+				.collect();
+			// Cells are kept as they're written, besides those which are null
+			self.write_locked(false, || {
+				self.rewrite_records(Some(header), |_, record| {
+					let row = record
+						.into_iter()
+						.zip(&schema.column_defs)
+						.map(|(cell, column_def)| {
+							match self.csv_settings.parse_cell(&cell, &column_def.data_type)? {
+								Value::Null => Ok(Value::Null),
+								_ => Ok(Value::Str(cell)),
+							}
+						})
+						.collect::<Result<Vec<Value>>>()?;
+					let row = match &rewriter {
+						Some(rewriter) => rewriter.rewrite(row)?,
+						None => row,
+					};
+					self.row_to_record(Row(row)).map(Some)
+				})
+			})?;
+		}
+		self.schema = Some(new_schema);
+		Ok(())
+	}
+}
+
+impl CSVDatabase {
+	/// Writes while holding the lock, see [CSVDatabase::lock].
+	/// Where rows are found by their keys, the file must be as it was when they were read, as keys are only of this process.
+	/// Keys which were of the file, as it was before, are of the file as written.
+	fn write_locked<T>(&self, keyed: bool, write: impl FnOnce() -> Result<T>) -> Result<T> {
+		let _lock = self.lock()?;
+		let current = *self.read_state() == Some(FileState::of(&self.path)?);
+		if keyed && !current {
+			return Err(CSVDatabaseError::ChangedSinceRead.into());
+		}
+		let written = write()?;
+		if current {
+			*self.read_state() = Some(FileState::of(&self.path)?);
+		}
+		Ok(written)
+	}
+
+	/// Writes records to the end of the file, or in place of it.
+	/// The lock is to be held, see [CSVDatabase::write_locked].
+	fn write_records(
+		&self,
+		records: impl IntoIterator<Item = Vec<String>>,
+		append: bool,
+	) -> Result<()> {
+		let write = |writer: &mut FileWriter| {
+			records.into_iter().try_for_each(|record| {
+				writer
+					.write_record(record)
+					.map_err(|error| WIPError::Debug(format!("{:?}", error)).into())
+			})
+		};
+		if append {
+			let file = OpenOptions::new()
+				.append(true)
+				.open(self.path.as_str())
 				.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+			let mut writer = csv_writer(self, encoded_writer(self, file)?);
+			write(&mut writer)?;
+			// Compressed files are appended to by another compressed part
+			finish_writer(writer)?;
+			Ok(())
+		} else {
+			self.replace_file("", write)
 		}
+	}
 
-		writer
-			.flush()
-			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+	/// Rewrites every record, which `edit` may change or drop (by [None]), given its position.
+	/// The header, and any rows skipped before it, are kept, unless another header is given.
+	/// Files of comments aren't rewritten, as the comments, being skipped in reading, would be lost.
+	/// Returns how many records there were.
+	/// The lock is to be held, see [CSVDatabase::write_locked].
+	fn rewrite_records(
+		&self,
+		header: Option<Vec<String>>,
+		mut edit: impl FnMut(usize, Vec<String>) -> Result<Option<Vec<String>>>,
+	) -> Result<usize> {
+		if self.csv_settings.comment.is_some() {
+			return Err(CSVDatabaseError::UnwritableComments.into());
+		}
+		let (preamble, mut reader) = csv_reader_with_preamble(self)?;
+		let header = match (self.csv_settings.has_header, header) {
+			(Some(false), _) => None,
+			(_, Some(header)) => Some(header),
+			(_, None) => Some(
+				reader
+					.headers()
+					.map_err(|error| CSVDatabaseError::HeaderError(format!("{:?}", error)))?
+					.iter()
+					.map(String::from)
+					.collect(),
+			),
+		};
+		let mut count = 0;
+		self.replace_file(&preamble, |writer| {
+			if let Some(header) = header {
+				writer
+					.write_record(header)
+					.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+			}
+			for (position, record) in reader.records().enumerate() {
+				count += 1;
+				let record = record.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+				if let Some(record) = edit(position, record.iter().map(String::from).collect())? {
+					writer
+						.write_record(record)
+						.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+				}
+			}
+			Ok(())
+		})?;
+		Ok(count)
+	}
 
-		let csv_bytes = writer
-			.into_inner()
+	/// Writes a new file beside the old, which it then takes the place of all at once.
+	/// The file starts with the preamble, rows not of the CSV which are kept as they are.
+	fn replace_file(
+		&self,
+		preamble: &str,
+		write: impl FnOnce(&mut FileWriter) -> Result<()>,
+	) -> Result<()> {
+		let temporary_path = format!("{}.tmp", self.path);
+		let file = File::create(&temporary_path)
 			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
-
-		let mut file = OpenOptions::new()
-			.append(true)
-			.open(self.path.as_str())
+		let mut file = encoded_writer(self, file)?;
+		file.write_all(preamble.as_bytes())
+			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		let mut writer = csv_writer(self, file);
+		write(&mut writer)?;
+		finish_writer(writer)?
+			.sync_all()
 			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		std::fs::rename(&temporary_path, &self.path)
+			.map_err(|error| WIPError::Debug(format!("{:?}", error)).into())
+	}
 
-		file.write_all(&csv_bytes)
+	/// Holds others, such as other [Glue](crate::Glue)s of the same file, from writing until dropped.
+	/// The lock is of a file in the temporary directory, named by the file's path,
+	/// as the file itself is replaced in writing and a file beside would be left there.
+	fn lock(&self) -> Result<File> {
+		let lock = OpenOptions::new()
+			.create(true)
+			.truncate(false)
+			.write(true)
+			.open(self.lock_path()?)
 			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
-		file.flush()
+		lock.lock_exclusive()
 			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
-		Ok(())
+		Ok(lock)
+	}
+
+	/// Of the absolute path, hashed by FNV-1a so that every process finds the same
+	fn lock_path(&self) -> Result<PathBuf> {
+		let path = Path::new(&self.path);
+		let directory = match path.parent() {
+			Some(directory) if !directory.as_os_str().is_empty() => directory,
+			_ => Path::new("."),
+		}
+		.canonicalize()
+		.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		let hash = directory
+			.join(path.file_name().unwrap_or_default())
+			.to_string_lossy()
+			.bytes()
+			.fold(0xcbf29ce484222325_u64, |hash, byte| {
+				(hash ^ byte as u64).wrapping_mul(0x100000001b3)
+			});
+		Ok(std::env::temp_dir().join(format!("multisql-{:016x}.lock", hash)))
+	}
+
+	/// Nulls are written as the null token, where there is one, otherwise as empty cells
+	fn row_to_record(&self, row: Row) -> Result<Vec<String>> {
+		row.0
+			.into_iter()
+			.map(|cell| match (cell, &self.csv_settings.null_token) {
+				(Value::Null, Some(null_token)) => Ok(null_token.clone()),
+				(Value::Null, None) => Ok(String::new()),
+				(cell, _) => cell.cast(),
+			})
+			.collect()
 	}
 }
@@ -0,0 +1,80 @@
+use {
+	crate::{CSVCompression, CSVDatabaseError, CSVSettings, Result, ValueType},
+	sqlparser::{dialect::GenericDialect, parser::Parser, tokenizer::Tokenizer},
+};
+
+impl CSVSettings {
+	/// Settings of options as `key=value&key=value`, such as follow the `?` of a location:
+	/// `CREATE DATABASE people LOCATION 'data/people.csv?delimiter=;&null=NA&type.born=TIMESTAMP'`.
+	/// See [CSVSettings::set_option] for the options.
+	pub fn from_options(options: &str) -> Result<Self> {
+		let mut settings = Self::default();
+		options
+			.split('&')
+			.filter(|option| !option.is_empty())
+			.try_for_each(|option| {
+				let (key, value) = option.split_once('=').unwrap_or((option, ""));
+				settings.set_option(key, value)
+			})?;
+		Ok(settings)
+	}
+
+	/// Sets an option, of:
+	/// - `delimiter`, `comment`: a single character, or `tab`
+	/// - `quoting`, `header`: `true` or `false`
+	/// - `sample_rows`, `skip_rows`: a number of rows
+	/// - `null`: the null token
+	/// - `encoding`: the label of an encoding, such as `latin1`
+	/// - `compression`: `gzip` or `zstd`
+	/// - `type.<column>`: the type of a column, as of SQL, such as `INTEGER`
+	pub fn set_option(&mut self, key: &str, value: &str) -> Result<()> {
+		let invalid = || CSVDatabaseError::InvalidOption(key.to_string(), value.to_string());
+		match key {
+			"delimiter" => self.delimiter = character(value).ok_or_else(invalid)?,
+			"comment" => self.comment = Some(character(value).ok_or_else(invalid)?),
+			"quoting" => self.quoting = value.parse().map_err(|_| invalid())?,
+			"header" => self.has_header = Some(value.parse().map_err(|_| invalid())?),
+			"sample_rows" => self.sample_rows = value.parse().map_err(|_| invalid())?,
+			"skip_rows" => self.skip_rows = value.parse().map_err(|_| invalid())?,
+			"null" => self.null_token = Some(value.to_string()),
+			"compression" => {
+				self.compression = Some(match value {
+					"gzip" | "gz" => CSVCompression::Gzip,
+					"zstd" | "zst" => CSVCompression::Zstd,
+					_ => return Err(invalid().into()),
+				})
+			}
+			"encoding" => {
+				self.encoding = Some(value.to_string());
+				self.encoding()?;
+			}
+			_ => match key.strip_prefix("type.") {
+				Some(column) => {
+					self.column_types
+						.insert(column.to_string(), data_type(value).ok_or_else(invalid)?);
+				}
+				None => return Err(CSVDatabaseError::UnknownOption(key.to_string()).into()),
+			},
+		}
+		Ok(())
+	}
+}
+
+fn character(value: &str) -> Option<u8> {
+	match value {
+		"tab" | "\\t" => Some(b'\t'),
+		_ => match value.as_bytes() {
+			[character] => Some(*character),
+			_ => None,
+		},
+	}
+}
+
+fn data_type(value: &str) -> Option<ValueType> {
+	let dialect = GenericDialect {};
+	let tokens = Tokenizer::new(&dialect, value).tokenize().ok()?;
+	Parser::new(tokens, &dialect)
+		.parse_data_type()
+		.ok()
+		.map(ValueType::from)
+}
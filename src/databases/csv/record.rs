@@ -1,15 +1,129 @@
-use {crate::Value, csv::StringRecord};
+use {
+	crate::{CSVSettings, Result, Value, ValueType},
+	chrono::{DateTime, NaiveDate, NaiveDateTime},
+	lazy_static::lazy_static,
+	regex::Regex,
+};
 
-pub fn string_record_to_row(record: &StringRecord) -> Vec<Value> {
-	record.iter().map(csv_cell_to_value).collect()
+lazy_static! {
+	/// Numbers with thousands separators, such as `1,234,567.89`
+	static ref SEPARATED_NUMBER: Regex = Regex::new(r"^[+-]?\d{1,3}(,\d{3})+(\.\d+)?$").unwrap();
 }
 
-fn csv_cell_to_value(cell: &str) -> Value {
-	let cell = cell.to_string();
-	cell.parse::<bool>()
-		.map(|v| Value::Bool(v))
-		.or_else(|_| cell.parse::<u64>().map(|v| Value::U64(v)))
-		.or_else(|_| cell.parse::<i64>().map(|v| Value::I64(v)))
-		.or_else(|_| cell.parse::<f64>().map(|v| Value::F64(v)))
-		.unwrap_or_else(|_| Value::Str(cell))
+const TIMESTAMP_FORMATS: [&str; 6] = [
+	"%Y-%m-%d %H:%M:%S%.f",
+	"%Y-%m-%dT%H:%M:%S%.f",
+	"%Y-%m-%d %H:%M",
+	"%Y-%m-%dT%H:%M",
+	"%Y/%m/%d %H:%M:%S",
+	"%d/%m/%Y %H:%M:%S",
+];
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%Y/%m/%d", "%d/%m/%Y"];
+
+impl CSVSettings {
+	pub(crate) fn is_null_token(&self, cell: &str) -> bool {
+		self.null_token.as_deref() == Some(cell)
+	}
+	/// Whether a cell is null, in discerning types
+	pub(crate) fn is_null(&self, cell: &str) -> bool {
+		cell.is_empty() || self.is_null_token(cell)
+	}
+
+	/// A cell's value, of whichever type it looks to be
+	pub(crate) fn infer_value(&self, cell: &str) -> Value {
+		if self.is_null(cell) {
+			return Value::Null;
+		}
+		parse_bool(cell)
+			.map(Value::Bool)
+			.or_else(|| parse_integer(cell))
+			.or_else(|| parse_float(cell).map(Value::F64))
+			.or_else(|| parse_timestamp(cell).map(Value::Timestamp))
+			.unwrap_or_else(|| Value::Str(cell.to_string()))
+	}
+
+	/// A cell's value, as of a column of the type.
+	/// Cells which aren't of the type are left as text, for the engine to deal with.
+	pub(crate) fn parse_cell(&self, cell: &str, data_type: &ValueType) -> Result<Value> {
+		if self.is_null_token(cell) {
+			return Ok(Value::Null);
+		}
+		let value = match data_type {
+			// Bytes are stored as base64
+			ValueType::Bytes => return Value::Str(cell.to_string()).cast_valuetype(data_type),
+			// Without a null token, NULL is written as an empty cell
+			ValueType::Str | ValueType::Any | ValueType::Array if self.null_token.is_some() => None,
+			_ if cell.is_empty() => Some(Value::Null),
+			ValueType::Str | ValueType::Any | ValueType::Array => None,
+			ValueType::Bool => parse_bool(cell).map(Value::Bool),
+			ValueType::I64 => parse_number(cell)
+				.and_then(|number| number.parse().ok())
+				.map(Value::I64),
+			ValueType::U64 => parse_number(cell)
+				.and_then(|number| number.parse().ok())
+				.map(Value::U64),
+			ValueType::F64 => parse_float(cell).map(Value::F64),
+			ValueType::Timestamp => parse_timestamp(cell).map(Value::Timestamp),
+		};
+		Ok(value.unwrap_or_else(|| Value::Str(cell.to_string())))
+	}
+}
+
+fn parse_bool(cell: &str) -> Option<bool> {
+	match cell.to_lowercase().as_str() {
+		"true" | "yes" => Some(true),
+		"false" | "no" => Some(false),
+		_ => None,
+	}
+}
+
+/// A number without thousands separators, where it has them
+fn parse_number(cell: &str) -> Option<String> {
+	if SEPARATED_NUMBER.is_match(cell) {
+		Some(cell.replace(',', ""))
+	} else if cell.starts_with(|first: char| first.is_ascii_digit() || "+-.".contains(first)) {
+		Some(cell.to_string())
+	} else {
+		None
+	}
+}
+
+/// Integers are signed, to be comparable with those of queries, unless too large to be
+fn parse_integer(cell: &str) -> Option<Value> {
+	let number = parse_number(cell)?;
+	number
+		.parse()
+		.map(Value::I64)
+		.or_else(|_| number.parse().map(Value::U64))
+		.ok()
+}
+
+fn parse_float(cell: &str) -> Option<f64> {
+	parse_number(cell)?.parse().ok()
+}
+
+/// Seconds since the epoch, of a timestamp or date of a common format
+fn parse_timestamp(cell: &str) -> Option<i64> {
+	DateTime::parse_from_rfc3339(cell)
+		.map(|datetime| datetime.timestamp())
+		.ok()
+		.or_else(|| {
+			TIMESTAMP_FORMATS.iter().find_map(|format| {
+				NaiveDateTime::parse_from_str(cell, format)
+					.map(|datetime| datetime.and_utc().timestamp())
+					.ok()
+			})
+		})
+		.or_else(|| {
+			DATE_FORMATS.iter().find_map(|format| {
+				NaiveDate::parse_from_str(cell, format)
+					.map(|date| {
+						date.and_hms_opt(0, 0, 0)
+							.unwrap_or_default()
+							.and_utc()
+							.timestamp()
+					})
+					.ok()
+			})
+		})
 }
@@ -1,22 +1,117 @@
 use {
-	super::CSVDatabase,
+	super::{compression::Compressor, CSVDatabase, CSVDatabaseError},
 	crate::{Result, WIPError},
-	csv::{Reader, ReaderBuilder},
-	std::fs::File,
+	csv::{QuoteStyle, Reader, Writer, WriterBuilder},
+	encoding_rs::Encoding,
+	std::{
+		fs::File,
+		io::{self, BufRead, Write},
+	},
 };
 
-pub(crate) fn csv_reader(store: &CSVDatabase) -> Result<Reader<File>> {
-	let reader = ReaderBuilder::new()
-		.delimiter(store.csv_settings.delimiter)
-		.quoting(store.csv_settings.quoting)
+pub(crate) fn csv_reader(store: &CSVDatabase) -> Result<Reader<Box<dyn BufRead>>> {
+	csv_reader_with_preamble(store).map(|(_, reader)| reader)
+}
+
+/// A reader of the file's records, and the rows skipped before them
+pub(crate) fn csv_reader_with_preamble(
+	store: &CSVDatabase,
+) -> Result<(String, Reader<Box<dyn BufRead>>)> {
+	let file =
+		File::open(store.path.as_str()).map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+	let mut source = store.csv_settings.decoded(file)?;
+	let preamble = store.csv_settings.read_preamble(&mut source)?;
+	let reader = store
+		.csv_settings
+		.reader_builder()
 		.buffer_capacity(8 * 500 * 1_000_000) // 500MB
-		.from_path(store.path.as_str())
-		.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
-	Ok(reader)
+		.from_reader(source);
+	Ok((preamble, reader))
+}
+
+pub(crate) fn csv_writer<T: Write>(
+	store: &CSVDatabase,
+	init: EncodedWriter<T>,
+) -> Writer<EncodedWriter<T>> {
+	WriterBuilder::new()
+		.delimiter(store.csv_settings.delimiter)
+		.quote_style(if store.csv_settings.quoting {
+			QuoteStyle::Necessary
+		} else {
+			QuoteStyle::Never
+		})
+		.from_writer(init)
 }
 
-/*pub(crate) fn csv_writer<T: Write>(store: &CSVDatabase, init: T) -> Result<Writer<T>> {
-	let writer = WriterBuilder::new().delimiter(store.csv_settings.delimiter).from_writer(init);
+/// Writer of records to a file, see [finish_writer]
+pub(crate) type FileWriter = Writer<EncodedWriter<Compressor<File>>>;
+
+/// A writer of text in the file's encoding and compression
+pub(crate) fn encoded_writer(
+	store: &CSVDatabase,
+	file: File,
+) -> Result<EncodedWriter<Compressor<File>>> {
+	let encoding = store.csv_settings.encoding()?;
+	if let Some(encoding) = encoding {
+		if encoding.output_encoding() != encoding {
+			return Err(CSVDatabaseError::UnwritableEncoding(encoding.name().to_string()).into());
+		}
+	}
+	Ok(EncodedWriter {
+		inner: Compressor::new(store.csv_settings.compression, file)?,
+		encoding,
+		pending: vec![],
+	})
+}
 
-	Ok(writer)
-}*/
+/// Writes what's left to be written, ending any compression, returning the file
+pub(crate) fn finish_writer(writer: FileWriter) -> Result<File> {
+	writer
+		.into_inner()
+		.map_err(|error| WIPError::Debug(format!("{:?}", error)))?
+		.into_inner()
+		.finish()
+		.map_err(|error| WIPError::Debug(format!("{:?}", error)).into())
+}
+
+/// Writes text, given as UTF-8, in an encoding; as it is where [None]
+pub(crate) struct EncodedWriter<W: Write> {
+	inner: W,
+	encoding: Option<&'static Encoding>,
+	/// Start of a character, the rest of which is yet to be written
+	pending: Vec<u8>,
+}
+
+impl<W: Write> EncodedWriter<W> {
+	pub fn into_inner(self) -> W {
+		self.inner
+	}
+}
+
+impl<W: Write> Write for EncodedWriter<W> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		let encoding = match self.encoding {
+			Some(encoding) => encoding,
+			None => return self.inner.write(buf),
+		};
+		self.pending.extend_from_slice(buf);
+		let complete = match std::str::from_utf8(&self.pending) {
+			Ok(text) => text.len(),
+			Err(error) => error.valid_up_to(),
+		};
+		let text = String::from_utf8_lossy(&self.pending[..complete]);
+		let (bytes, _, unmappable) = encoding.encode(&text);
+		if unmappable {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidData,
+				format!("text can't be written as {}", encoding.name()),
+			));
+		}
+		self.inner.write_all(&bytes)?;
+		self.pending.drain(..complete);
+		Ok(buf.len())
+	}
+	fn flush(&mut self) -> io::Result<()> {
+		self.inner.flush()
+	}
+}
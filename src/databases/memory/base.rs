@@ -4,7 +4,8 @@ use crate::{join_iters, JoinType, Row};
 
 use {
 	crate::{
-		DBBase, IndexFilter, MemoryDatabase, MemoryDatabaseError, Plane, Result, Schema, Value,
+		project, DBBase, IndexFilter, MemoryDatabase, MemoryDatabaseError, Plane, Predicate,
+		Result, RowFilter, Schema, Value,
 	},
 	async_trait::async_trait,
 };
@@ -26,6 +27,35 @@ impl DBBase for MemoryDatabase {
 			.map(|rows| rows.into_iter().collect())
 	}
 
+	async fn scan_data_filtered(
+		&self,
+		table_name: &str,
+		predicates: &[Predicate],
+		projection: Option<&[String]>,
+	) -> Result<Plane> {
+		let columns: Vec<String> = self
+			.tables
+			.get(table_name)
+			.ok_or(MemoryDatabaseError::TableNotFound)?
+			.column_defs
+			.iter()
+			.map(|column| column.name.clone())
+			.collect();
+		let filter = RowFilter::new(predicates, &columns);
+		let plane = self
+			.data
+			.get(table_name)
+			.ok_or(MemoryDatabaseError::TableNotFound)?
+			.iter()
+			.filter(|(_, row)| filter.confirm(&row.0))
+			.map(|(key, row)| (key.clone(), row.clone()))
+			.collect();
+		match projection {
+			Some(projection) => project(plane, &columns, projection),
+			None => Ok(plane),
+		}
+	}
+
 	async fn scan_data_indexed(
 		&self,
 		table_name: &str,
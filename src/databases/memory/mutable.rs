@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use {
-	crate::{DBMut, MemoryDatabase, Result, Row, Schema, Value},
+	crate::{
+		DBMut, DatabaseError, MemoryDatabase, Result, Row, Schema, SchemaChange, SchemaDiff, Value,
+	},
 	async_trait::async_trait,
 };
 
@@ -16,14 +18,23 @@ impl DBMut for MemoryDatabase {
 
 	async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
 		self.tables.remove(table_name);
-		self.tables.remove(table_name);
+		self.data.remove(table_name);
+		self.indexes.remove(table_name);
 		Ok(())
 	}
 
 	async fn insert_data(&mut self, table_name: &str, rows: Vec<Row>) -> Result<()> {
 		let table_name = table_name.to_string();
 		let old_rows = self.data.remove(&table_name).unwrap_or_default();
-		let init = old_rows.len();
+		// Rows may have been deleted, so keys follow the greatest rather than the count
+		let init = old_rows
+			.keys()
+			.filter_map(|key| match key {
+				Value::U64(key) => Some(*key as usize + 1),
+				_ => None,
+			})
+			.max()
+			.unwrap_or(0);
 		let rows = rows
 			.into_iter()
 			.enumerate()
@@ -34,6 +45,23 @@ impl DBMut for MemoryDatabase {
 		Ok(())
 	}
 
+	async fn update_data(&mut self, table_name: &str, rows: Vec<(Value, Row)>) -> Result<()> {
+		self.data
+			.entry(table_name.to_string())
+			.or_default()
+			.extend(rows);
+		Ok(())
+	}
+
+	async fn delete_data(&mut self, table_name: &str, keys: Vec<Value>) -> Result<()> {
+		if let Some(rows) = self.data.get_mut(table_name) {
+			keys.iter().for_each(|key| {
+				rows.remove(key);
+			});
+		}
+		Ok(())
+	}
+
 	async fn update_index(
 		&mut self,
 		table_name: &str,
@@ -48,4 +76,34 @@ impl DBMut for MemoryDatabase {
 		self.indexes.insert(table_name, indexes);
 		Ok(())
 	}
+
+	async fn alter_table(&mut self, table_name: &str, schema_diff: SchemaDiff) -> Result<()> {
+		let schema = self
+			.tables
+			.get(table_name)
+			.ok_or(DatabaseError::TableNotFound)?;
+		// Everything is worked out before anything is changed, so that the table is left as it was should any change fail
+		let new_schema = schema_diff.merge(schema.clone())?;
+		let rows = self.data.get(table_name).cloned().unwrap_or_default();
+		let rows = match schema_diff.row_rewriter(schema)? {
+			Some(rewriter) => rows
+				.into_iter()
+				.map(|(key, Row(row))| rewriter.rewrite(row).map(|row| (key, Row(row))))
+				.collect::<Result<_>>()?,
+			None => rows,
+		};
+		let mut indexes = self.indexes.get(table_name).cloned().unwrap_or_default();
+		schema_diff.changes().iter().for_each(|change| {
+			if let SchemaChange::IndexRemove(name) | SchemaChange::IndexUpdate(name, _) = change {
+				indexes.remove(name);
+			}
+		});
+
+		self.delete_schema(table_name).await?;
+		let table_name = new_schema.table_name.clone();
+		self.data.insert(table_name.clone(), rows);
+		self.indexes.insert(table_name.clone(), indexes);
+		self.tables.insert(table_name, new_schema);
+		Ok(())
+	}
 }
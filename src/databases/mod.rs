@@ -6,7 +6,14 @@ pub use self::sled::SledDatabase;
 #[cfg(feature = "csv-database")]
 mod csv;
 #[cfg(feature = "csv-database")]
-pub use self::csv::{CSVDatabase, CSVDatabaseError, CSVSettings};
+pub use self::csv::{
+	CSVCompression, CSVDatabase, CSVDatabaseError, CSVDirectoryDatabase, CSVSettings,
+};
+
+#[cfg(feature = "ndjson-database")]
+mod ndjson;
+#[cfg(feature = "ndjson-database")]
+pub use self::ndjson::{NDJSONDatabase, NDJSONDatabaseError};
 
 #[cfg(feature = "sheet-database")]
 mod sheet;
@@ -21,4 +28,14 @@ pub use self::memory::{MemoryDatabase, MemoryDatabaseError};
 #[cfg(feature = "odbc-database")]
 mod odbc;
 #[cfg(feature = "odbc-database")]
-pub use self::odbc::ODBCDatabase;
+pub use self::odbc::{ODBCDatabase, ODBCDatabaseError};
+
+#[cfg(feature = "parquet-database")]
+mod parquet;
+#[cfg(feature = "parquet-database")]
+pub use self::parquet::{ParquetDatabase, ParquetDatabaseError};
+
+#[cfg(feature = "sqlite-database")]
+mod sqlite;
+#[cfg(feature = "sqlite-database")]
+pub use self::sqlite::{SQLiteDatabase, SQLiteDatabaseError};
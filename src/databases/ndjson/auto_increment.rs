@@ -0,0 +1,38 @@
+use {
+	super::NDJSONDatabase,
+	crate::{AutoIncrement, Result},
+	async_trait::async_trait,
+};
+
+#[async_trait(?Send)]
+impl AutoIncrement for NDJSONDatabase {
+	async fn generate_increment_values(
+		&mut self,
+		_table_name: String,
+		columns: Vec<(
+			usize,  /*index*/
+			String, /*name*/
+			i64,    /*row_count*/
+		) /*column*/>,
+	) -> Result<
+		Vec<(
+			/*column*/ (usize /*index*/, String /*name*/),
+			/*start_value*/ i64,
+		)>,
+	> {
+		let objects = self.objects()?.count() as i64;
+		Ok(columns
+			.into_iter()
+			.map(|(index, name, _)| ((index, name), objects + 1))
+			.collect())
+	}
+
+	async fn set_increment_value(
+		&mut self,
+		_table_name: &str,
+		_column_name: &str,
+		_end: i64,
+	) -> Result<()> {
+		Ok(())
+	}
+}
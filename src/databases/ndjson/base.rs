@@ -0,0 +1,62 @@
+use {
+	super::NDJSONDatabase,
+	crate::{DBBase, Plane, Result, Row, Schema, Value, ValueType},
+	async_trait::async_trait,
+};
+
+#[async_trait(?Send)]
+impl DBBase for NDJSONDatabase {
+	async fn fetch_schema(&self, _table_name: &str) -> Result<Option<Schema>> {
+		Ok(self.schema.clone())
+	}
+	async fn scan_schemas(&self) -> Result<Vec<Schema>> {
+		Ok(self
+			.schema
+			.clone()
+			.map(|schema| vec![schema])
+			.unwrap_or_default())
+	}
+
+	/// Rows are keyed by their position; keys which aren't columns are left out
+	async fn scan_data(&self, _table_name: &str) -> Result<Plane> {
+		let columns: Vec<(String, ValueType)> = self
+			.schema
+			.as_ref()
+			.map(|schema| {
+				schema
+					.column_defs
+					.iter()
+					.map(|column_def| (column_def.name.clone(), column_def.data_type.clone()))
+					.collect()
+			})
+			.unwrap_or_default();
+		self.objects()?
+			.enumerate()
+			.map(|(index, object)| {
+				let mut object = object?;
+				let row = columns
+					.iter()
+					.map(|(column, data_type)| {
+						object
+							.remove(column)
+							.map(|value| typed(Value::from(value), data_type))
+							.unwrap_or(Value::Null)
+					})
+					.collect();
+				Ok((Value::I64(index as i64), Row(row)))
+			})
+			.collect()
+	}
+}
+
+/// A value as of its column's type, as where numbers are written as text.
+/// Values which can't be, or would lose some of themselves as floats would as integers, are left for the engine to deal with.
+fn typed(value: Value, data_type: &ValueType) -> Value {
+	match (&value, data_type) {
+		(Value::Null, _) | (_, ValueType::Any) => value,
+		(Value::Str(_), _) | (Value::I64(_), ValueType::F64) | (_, ValueType::Str) => {
+			value.cast_valuetype(data_type).unwrap_or(value)
+		}
+		_ => value,
+	}
+}
@@ -0,0 +1,76 @@
+use {
+	super::NDJSONDatabase,
+	crate::{data::Schema, Column, JSONObject, Result, Value, ValueType},
+	serde_json::Value as JSONValue,
+};
+
+impl NDJSONDatabase {
+	/// Columns are of every key of the sampled objects, in the order they're first found.
+	/// Nested objects are kept as JSON text, and so their columns are of [ValueType::Str].
+	pub(crate) fn discern_schema(&self) -> Result<Option<Schema>> {
+		let sample = self
+			.objects()?
+			.take(self.sample_rows)
+			.collect::<Result<Vec<JSONObject>>>()?;
+		if sample.is_empty() {
+			return Ok(None);
+		}
+
+		let mut names: Vec<&String> = vec![];
+		sample
+			.iter()
+			.flat_map(|object| object.keys())
+			.for_each(|key| {
+				if !names.contains(&key) {
+					names.push(key)
+				}
+			});
+		let column_defs = names
+			.into_iter()
+			.map(|name| {
+				let (data_type, is_nullable) =
+					discern_column(sample.iter().map(|object| object.get(name)));
+				Column {
+					name: name.clone(),
+					data_type,
+					is_nullable,
+					..Column::default()
+				}
+			})
+			.collect();
+		Ok(Some(Schema {
+			table_name: String::new(),
+			column_defs,
+			indexes: vec![],
+		}))
+	}
+}
+
+/// The type of a key's values, and whether any are null or missing.
+/// Integers among floats are taken as floats, otherwise columns of values of differing types are of [ValueType::Any].
+fn discern_column<'a>(values: impl Iterator<Item = Option<&'a JSONValue>>) -> (ValueType, bool) {
+	let mut data_type = None;
+	let mut is_nullable = false;
+	for value in values {
+		let value = value.cloned().map(Value::from).unwrap_or(Value::Null);
+		if matches!(value, Value::Null) {
+			is_nullable = true;
+			continue;
+		}
+		let value_type = ValueType::from(&value);
+		data_type = Some(match data_type {
+			None => value_type,
+			Some(data_type) if data_type == value_type => data_type,
+			Some(ValueType::I64 | ValueType::F64)
+				if matches!(value_type, ValueType::I64 | ValueType::F64) =>
+			{
+				ValueType::F64
+			}
+			Some(_) => ValueType::Any,
+		});
+	}
+	match data_type {
+		Some(data_type) => (data_type, is_nullable),
+		None => (ValueType::Any, true),
+	}
+}
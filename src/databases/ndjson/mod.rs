@@ -0,0 +1,76 @@
+mod auto_increment;
+mod base;
+mod discern;
+mod mutable;
+
+use {
+	crate::{data::Schema, DBFull, Database, JSONObject, Result, WIPError},
+	serde::Serialize,
+	std::{
+		fmt::Debug,
+		fs::{File, OpenOptions},
+		io::{BufRead, BufReader},
+	},
+	thiserror::Error,
+};
+
+#[derive(Error, Serialize, Debug, PartialEq)]
+pub enum NDJSONDatabaseError {
+	#[error("NDJSON storages only support one table at a time")]
+	OnlyOneTableAllowed,
+	#[error("line {0} of NDJSON isn't an object: {1}")]
+	NotAnObject(usize, String),
+}
+
+/// A file of JSON objects, one to a line, as one table.
+/// Columns are the keys of the objects, as discerned from the first of them, see [NDJSONDatabase::new_with_sample_rows];
+/// values are read as of their column's type where they can be, as a number written as text would be of a column of integers.
+pub struct NDJSONDatabase {
+	schema: Option<Schema>,
+	path: String,
+	sample_rows: usize,
+}
+
+impl DBFull for NDJSONDatabase {}
+
+impl Database {
+	pub fn new_ndjson(storage: NDJSONDatabase) -> Self {
+		Self::new(Box::new(storage))
+	}
+}
+impl NDJSONDatabase {
+	pub fn new(path: &str) -> Result<Self> {
+		Self::new_with_sample_rows(path, 100)
+	}
+	/// Columns are discerned from the first `sample_rows` objects
+	pub fn new_with_sample_rows(path: &str, sample_rows: usize) -> Result<Self> {
+		OpenOptions::new()
+			.write(true)
+			.create(true)
+			.truncate(false)
+			.open(path)
+			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		let mut database = Self {
+			schema: None,
+			path: path.to_string(),
+			sample_rows,
+		};
+		database.schema = database.discern_schema()?;
+		Ok(database)
+	}
+
+	/// Objects of the file, in order; blank lines are skipped
+	pub(crate) fn objects(&self) -> Result<impl Iterator<Item = Result<JSONObject>>> {
+		let file =
+			File::open(&self.path).map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		Ok(BufReader::new(file)
+			.lines()
+			.enumerate()
+			.filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+			.map(|(index, line)| {
+				let line = line.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+				serde_json::from_str(&line)
+					.map_err(|_| NDJSONDatabaseError::NotAnObject(index + 1, line).into())
+			}))
+	}
+}
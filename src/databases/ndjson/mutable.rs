@@ -0,0 +1,54 @@
+use {
+	super::{NDJSONDatabase, NDJSONDatabaseError},
+	crate::{DBMut, DatabaseError, JSONObject, Result, Row, Schema, WIPError},
+	async_trait::async_trait,
+	serde_json::Value as JSONValue,
+	std::{
+		fs::{File, OpenOptions},
+		io::{BufWriter, Write},
+	},
+};
+
+#[async_trait(?Send)]
+impl DBMut for NDJSONDatabase {
+	/// Objects have no header, so the file is left empty until rows are inserted
+	async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
+		if self.schema.is_some() {
+			return Err(NDJSONDatabaseError::OnlyOneTableAllowed.into());
+		}
+		File::create(&self.path).map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		self.schema = Some(schema.clone());
+		Ok(())
+	}
+
+	async fn delete_schema(&mut self, _table_name: &str) -> Result<()> {
+		self.schema = None;
+		Ok(())
+	}
+
+	/// Rows are appended as objects; nulls are written as they are, rather than left out
+	async fn insert_data(&mut self, _table_name: &str, rows: Vec<Row>) -> Result<()> {
+		let schema = self.schema.as_ref().ok_or(DatabaseError::TableNotFound)?;
+		let file = OpenOptions::new()
+			.append(true)
+			.open(&self.path)
+			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		let mut writer = BufWriter::new(file);
+		for Row(values) in rows {
+			let object: JSONObject = schema
+				.column_defs
+				.iter()
+				.map(|column_def| column_def.name.clone())
+				.zip(values.into_iter().map(JSONValue::from))
+				.collect();
+			serde_json::to_writer(&mut writer, &object)
+				.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+			writer
+				.write_all(b"\n")
+				.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		}
+		writer
+			.flush()
+			.map_err(|error| WIPError::Debug(format!("{:?}", error)).into())
+	}
+}
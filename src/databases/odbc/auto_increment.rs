@@ -1,20 +1,38 @@
 use {
-	crate::{AutoIncrement, ODBCDatabase, Result},
+	super::{base::read_text_rows, Dialect},
+	crate::{AutoIncrement, Cast, ODBCDatabase, Result, Value},
 	async_trait::async_trait,
 };
 
+/// Values follow on from the greatest of the column, as it is on the remote database
 #[async_trait(?Send)]
 impl AutoIncrement for ODBCDatabase {
 	async fn generate_increment_values(
 		&mut self,
-		_table_name: String,
+		table_name: String,
 		columns: Vec<(usize, String, i64)>,
 	) -> Result<Vec<((usize, String), i64)>> {
-		let row_init = 1;
-		Ok(columns
+		let connection = self.connection()?;
+		let dialect = Dialect::of(&connection)?;
+		let table_name = dialect.table(&table_name);
+		columns
 			.into_iter()
-			.map(|(index, name, _)| ((index, name), row_init as i64))
-			.collect())
+			.map(|(index, name, _)| {
+				let query = format!("SELECT MAX({}) FROM {}", dialect.quoted(&name), table_name);
+				let greatest = match connection.execute(&query, ())? {
+					Some(cursor) => read_text_rows(cursor)?
+						.into_iter()
+						.next()
+						.and_then(|row| row.into_iter().next().flatten()),
+					None => None,
+				};
+				let start = match greatest {
+					Some(greatest) => Cast::<i64>::cast(Value::Str(greatest))? + 1,
+					None => 1,
+				};
+				Ok(((index, name), start))
+			})
+			.collect()
 	}
 
 	async fn set_increment_value(
@@ -1,170 +1,418 @@
 use {
-	crate::{Cast, Column, DBBase, ODBCDatabase, Plane, Result, Row, Schema, Value, ValueType},
+	super::{Dialect, ODBCDatabaseError},
+	crate::{
+		Cast, Column, Comparison, DBBase, DatabaseError, ODBCDatabase, Plane, Predicate, Result,
+		Row, Schema, Value, ValueError, ValueType,
+	},
 	async_trait::async_trait,
-	odbc_api::{Cursor, ResultSetMetadata},
+	odbc_api::{Connection, Cursor, DataType},
 };
 
 #[async_trait(?Send)]
 impl DBBase for ODBCDatabase {
+	/// Every table is described at once, by one connection, and kept until invalidated
 	async fn scan_schemas(&self) -> Result<Vec<Schema>> {
-		let connection = self
-			.environment
-			.connect_with_connection_string(&self.connection_string)?;
-		let mut tables = connection.tables(&connection.current_catalog()?, "", "", "TABLE")?;
-		let col_range = 1..(tables.num_result_cols()? + 1);
-		let mut schemas = Vec::new();
-		while let Some(mut row) = tables.next_row()? {
-			let row = col_range
-				.clone()
-				.map(|col| {
-					let mut output = Vec::new();
-					let output = row
-						.get_text(col as u16, &mut output)
-						.map(|_| std::str::from_utf8(&output).unwrap_or_default())
-						.unwrap_or("NULL")
-						.to_string();
-					Ok(output)
-				})
-				.collect::<Result<Vec<String>>>()?;
-			if let Some(schema) = self.fetch_schema(&row[4]).await? {
-				schemas.push(schema);
-			}
+		if let Some(schemas) = self.schema_cache().all() {
+			return Ok(schemas);
 		}
+		let connection = self.connection()?;
+		let catalog = connection.current_catalog()?;
+		let tables: Vec<String> = read_text_rows(connection.tables(&catalog, "", "", "TABLE")?)?
+			.into_iter()
+			.map(|row| {
+				let text = |index: usize| row.get(index).cloned().flatten().unwrap_or_default();
+				join_table_name(&text(1), &text(2))
+			})
+			.collect();
+		let schemas: Vec<Schema> = describe_tables(&connection, "", "")?
+			.into_iter()
+			.filter(|schema| tables.contains(&schema.table_name))
+			.collect();
+		self.schema_cache().fill(schemas.clone());
 		Ok(schemas)
 	}
 	async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
-		let connection = self
-			.environment
-			.connect_with_connection_string(&self.connection_string)?;
-		let (schema_name, table_name) = split_table_name(table_name);
-		let mut columns =
-			connection.columns(&connection.current_catalog()?, schema_name, table_name, "")?;
-		let col_range = 1..(columns.num_result_cols()? + 1);
-		let mut column_defs = Vec::new();
-		while let Some(mut row) = columns.next_row()? {
-			let row = col_range
-				.clone()
-				.map(|col| {
-					let mut output = Vec::new();
-					let output = row
-						.get_text(col as u16, &mut output)
-						.map(|_| std::str::from_utf8(&output).unwrap_or_default())
-						.unwrap_or("NULL")
-						.to_string();
-					Ok(output)
-				})
-				.collect::<Result<Vec<String>>>()?;
-			// TODO: Be safer with referencing elements -- doesn't matter that much though
-			column_defs.push(Column {
-				name: row[3].clone(),
-				data_type: odbc_type_to_multisql(&row[5]),
-				default: None,     // doesn't really matter
-				is_nullable: true, //(row[17] != "NO"),
-				is_unique: false,  // doesn't realllllyyyy matter
-			});
+		if let Some(schema) = self.schema_cache().get(table_name) {
+			return Ok(schema);
 		}
-		Ok(if !column_defs.is_empty() {
-			Some(Schema {
+		let connection = self.connection()?;
+		let (schema_name, plain_table_name) = split_table_name(table_name);
+		let schema = describe_tables(&connection, schema_name, plain_table_name)?
+			.into_iter()
+			.next()
+			.map(|schema| Schema {
 				table_name: table_name.to_string(),
-				column_defs,
-				indexes: Vec::new(), // TODO
-			})
-		} else {
-			None
-		})
+				..schema
+			});
+		self.schema_cache()
+			.insert(table_name.to_string(), schema.clone());
+		Ok(schema)
+	}
+
+	/// Forgets cached schemas, so that they're described again by the remote database
+	async fn refresh(&self) -> Result<()> {
+		self.schema_cache().clear();
+		Ok(())
 	}
 
 	async fn scan_data(&self, table_name: &str) -> Result<Plane> {
-		// TODO: Non-string conversion (if possible?)
-		let connection = self
-			.environment
-			.connect_with_connection_string(&self.connection_string)?;
-
-		let schema = self.fetch_schema(table_name).await?.unwrap(); // TODO: Handle
-		let table_name = convert_table_name(table_name);
-		let response = connection.execute(
-			&format!("SELECT TOP 1000 * FROM {table}", table = table_name),
-			(),
-		)?;
-		Ok(if let Some(mut rows) = response {
-			let col_range = 1..(rows.num_result_cols()? + 1);
-
-			let mut out_rows = Vec::new();
-			while let Some(mut row) = rows.next_row()? {
-				let row = col_range
-					.clone()
-					.map(|col| {
-						let mut output = Vec::new();
-						let output = row
-							.get_text(col as u16, &mut output)
-							.map(|_| std::str::from_utf8(&output).unwrap_or_default())
-							.unwrap_or("NULL")
-							.to_string();
-						let column = &schema.column_defs[(col - 1) as usize]; // TODO: Protect
-						odbc_value_to_multisql(output, &column.data_type)
-					})
-					.collect::<Result<Vec<Value>>>()?;
-				out_rows.push((Value::Null, Row(row))); // TODO: PK
-			}
-			out_rows
+		self.select_rows(table_name, &[], None).await
+	}
+
+	/// Predicates are given to the remote database as a `WHERE` clause, where it would agree with the engine, and projections as the columns selected
+	async fn scan_data_filtered(
+		&self,
+		table_name: &str,
+		predicates: &[Predicate],
+		projection: Option<&[String]>,
+	) -> Result<Plane> {
+		self.select_rows(table_name, predicates, projection).await
+	}
+}
+
+impl ODBCDatabase {
+	async fn select_rows(
+		&self,
+		table_name: &str,
+		predicates: &[Predicate],
+		projection: Option<&[String]>,
+	) -> Result<Plane> {
+		let connection = self.connection()?;
+		let dialect = Dialect::of(&connection)?;
+
+		let schema = self
+			.fetch_schema(table_name)
+			.await?
+			.ok_or_else(|| ODBCDatabaseError::TableNotFound(table_name.to_string()))?;
+		let find_column = |name: &String| {
+			schema
+				.column_defs
+				.iter()
+				.find(|column_def| &column_def.name == name)
+				.cloned()
+				.ok_or_else(|| DatabaseError::ColumnNotFound(name.clone()).into())
+		};
+		// Rows are keyed by their primary key, which is selected ahead of the other columns
+		let key_defs = primary_key(&connection, table_name)
+			.iter()
+			.map(find_column)
+			.collect::<Result<Vec<Column>>>()?;
+		let column_defs = match projection {
+			Some(projection) => projection
+				.iter()
+				.map(find_column)
+				.collect::<Result<Vec<Column>>>()?,
+			None => schema.column_defs.clone(),
+		};
+		let selected: Vec<&Column> = key_defs.iter().chain(column_defs.iter()).collect();
+
+		// Where no column is needed, as of `SELECT 1 FROM ...` of a table without a primary key, rows are still counted
+		let columns = if selected.is_empty() {
+			String::from("1")
 		} else {
-			Vec::new()
-		})
+			selected
+				.iter()
+				.map(|column_def| dialect.quoted(&column_def.name))
+				.collect::<Vec<String>>()
+				.join(", ")
+		};
+		let conditions: Vec<String> = predicates
+			.iter()
+			.filter_map(|predicate| predicate_sql(predicate, &schema, &dialect))
+			.collect();
+		let where_clause = if conditions.is_empty() {
+			String::new()
+		} else {
+			format!(" WHERE {}", conditions.join(" AND "))
+		};
+		let query = format!(
+			"SELECT {columns} FROM {table}{where_clause}",
+			columns = columns,
+			table = dialect.table(table_name),
+			where_clause = where_clause
+		);
+		let rows = match connection.execute(&query, ())? {
+			Some(cursor) => read_text_rows(cursor)?,
+			None => Vec::new(),
+		};
+		rows.into_iter()
+			.map(|row| {
+				let mut row = row
+					.into_iter()
+					.zip(selected.iter())
+					.map(|(text, column_def)| odbc_value_to_multisql(text, &column_def.data_type))
+					.collect::<Result<Vec<Value>>>()?;
+				let values = row.split_off(key_defs.len());
+				Ok((row_key(row), Row(values)))
+			})
+			.collect()
 	}
 }
 
-pub(crate) fn convert_table_name(table_name: &str) -> String {
+/// Schemas of the tables matching patterns of their schema and name, in order, as the remote database describes them
+fn describe_tables(
+	connection: &Connection,
+	schema_pattern: &str,
+	table_pattern: &str,
+) -> Result<Vec<Schema>> {
+	let catalog = connection.current_catalog()?;
+	let columns = connection.columns(&catalog, schema_pattern, table_pattern, "")?;
+	// Columns come ordered by table, then by position
+	let mut schemas: Vec<Schema> = Vec::new();
+	for row in read_text_rows(columns)? {
+		let text = |index: usize| row.get(index).cloned().flatten().unwrap_or_default();
+		let table_name = join_table_name(&text(1), &text(2));
+		let column = Column {
+			name: text(3),
+			data_type: odbc_type_to_multisql(&text(5)),
+			default: None,     // doesn't really matter
+			is_nullable: true, //(row[17] != "NO"),
+			is_unique: false,  // doesn't realllllyyyy matter
+		};
+		match schemas.last_mut() {
+			Some(schema) if schema.table_name == table_name => schema.column_defs.push(column),
+			_ => schemas.push(Schema {
+				table_name,
+				column_defs: vec![column],
+				indexes: Vec::new(), // TODO
+			}),
+		}
+	}
+	Ok(schemas)
+}
+
+/// Every row of a cursor, as text; [None] being `NULL`
+pub(crate) fn read_text_rows(mut cursor: impl Cursor) -> Result<Vec<Vec<Option<String>>>> {
+	let col_range = 1..(cursor.num_result_cols()? as u16 + 1);
+	let mut rows = Vec::new();
+	while let Some(mut row) = cursor.next_row()? {
+		rows.push(
+			col_range
+				.clone()
+				.map(|col| {
+					let mut output = Vec::new();
+					Ok(row
+						.get_text(col, &mut output)?
+						.then(|| String::from_utf8_lossy(&output).into_owned()))
+				})
+				.collect::<Result<Vec<Option<String>>>>()?,
+		);
+	}
+	Ok(rows)
+}
+
+/// Columns of a table's primary key, in order.
+/// Found by the `INFORMATION_SCHEMA` (as of SQL Server, PostgreSQL and MySQL) or, failing that, SQLite's `PRAGMA`;
+/// empty where the table has none or it can't be found.
+pub(crate) fn primary_key(connection: &Connection, table_name: &str) -> Vec<String> {
 	let (schema_name, table_name) = split_table_name(table_name);
+	let schema_condition = if schema_name.is_empty() {
+		String::new()
+	} else {
+		format!(" AND tc.TABLE_SCHEMA = {}", sql_string(schema_name))
+	};
+	let queries = [
+		format!(
+			"SELECT kcu.COLUMN_NAME \
+			FROM INFORMATION_SCHEMA.TABLE_CONSTRAINTS tc \
+			JOIN INFORMATION_SCHEMA.KEY_COLUMN_USAGE kcu \
+				ON kcu.CONSTRAINT_NAME = tc.CONSTRAINT_NAME \
+				AND kcu.TABLE_SCHEMA = tc.TABLE_SCHEMA \
+				AND kcu.TABLE_NAME = tc.TABLE_NAME \
+			WHERE tc.CONSTRAINT_TYPE = 'PRIMARY KEY' AND tc.TABLE_NAME = {table}{schema_condition} \
+			ORDER BY kcu.ORDINAL_POSITION",
+			table = sql_string(table_name),
+			schema_condition = schema_condition
+		),
+		format!(
+			"SELECT name FROM pragma_table_info({}) WHERE pk > 0 ORDER BY pk",
+			sql_string(table_name)
+		),
+	];
+	queries
+		.iter()
+		.find_map(|query| {
+			let cursor = connection.execute(query, ()).ok()??;
+			read_text_rows(cursor).ok()
+		})
+		.unwrap_or_default()
+		.into_iter()
+		.filter_map(|row| row.into_iter().next().flatten())
+		.collect()
+}
+
+/// The key of a row, of the values of its primary key
+pub(crate) fn row_key(mut key: Vec<Value>) -> Value {
+	match key.len() {
+		0 => Value::Null,
+		1 => key.remove(0),
+		_ => Value::Array(key),
+	}
+}
+
+/// A predicate as a SQL condition, where the remote database would keep every row the engine would; see [comparable].
+/// Case insensitive `LIKE`s are left to the engine, as databases fold case differently.
+fn predicate_sql(predicate: &Predicate, schema: &Schema, dialect: &Dialect) -> Option<String> {
+	let data_type = schema
+		.column_defs
+		.iter()
+		.find(|column_def| column_def.name == predicate.column())
+		.map(|column_def| &column_def.data_type)?;
+	let column = dialect.quoted(predicate.column());
+	Some(match predicate {
+		// NULL <> value is true to the engine, where to the remote database it's NULL
+		Predicate::Compare(_, Comparison::NotEq, value)
+			if comparable(value, data_type, &Comparison::NotEq) =>
+		{
+			format!(
+				"({column} <> {value} OR {column} IS NULL)",
+				column = column,
+				value = sql_literal(value, dialect)?
+			)
+		}
+		Predicate::Compare(_, comparison, value) if comparable(value, data_type, comparison) => {
+			format!(
+				"{} {} {}",
+				column,
+				comparison.as_sql(),
+				sql_literal(value, dialect)?
+			)
+		}
+		Predicate::In(_, values)
+			if !values.is_empty()
+				&& values
+					.iter()
+					.all(|value| comparable(value, data_type, &Comparison::Eq)) =>
+		{
+			format!(
+				"{} IN ({})",
+				column,
+				values
+					.iter()
+					.map(|value| sql_literal(value, dialect))
+					.collect::<Option<Vec<String>>>()?
+					.join(", ")
+			)
+		}
+		// Patterns are of escapes (`\`) and sets (`[...]`) in some databases, but not to the engine
+		Predicate::Like(_, pattern, false)
+			if matches!(data_type, ValueType::Str) && !pattern.contains(['\\', '[']) =>
+		{
+			format!("{} LIKE {}", column, sql_string(pattern))
+		}
+		Predicate::IsNull(_) => format!("{} IS NULL", column),
+		Predicate::IsNotNull(_) => format!("{} IS NOT NULL", column),
+		_ => return None,
+	})
+}
+
+/// Whether the remote database compares the value with those of a column of the type as the engine would,
+/// or finds more rows of the comparison. Only then may the comparison be given to it.
+/// As collations may ignore case, or trailing spaces, strings are only compared for equality;
+/// nor are those of `\\`, which some databases take as an escape.
+fn comparable(value: &Value, data_type: &ValueType, comparison: &Comparison) -> bool {
+	match (value, data_type) {
+		(Value::I64(_) | Value::U64(_), ValueType::I64 | ValueType::F64) => true,
+		(Value::F64(value), ValueType::I64 | ValueType::F64) => value.is_finite(),
+		(Value::Bool(_), ValueType::Bool) => true,
+		(Value::Str(value), ValueType::Str) => {
+			comparison == &Comparison::Eq && !value.contains('\\')
+		}
+		_ => false,
+	}
+}
+
+fn sql_literal(value: &Value, dialect: &Dialect) -> Option<String> {
+	match value {
+		Value::Bool(value) => Some(dialect.bool_literal(*value).to_string()),
+		Value::I64(value) => Some(value.to_string()),
+		Value::U64(value) => Some(value.to_string()),
+		Value::F64(value) if value.is_finite() => Some(value.to_string()),
+		Value::Str(value) => Some(sql_string(value)),
+		_ => None,
+	}
+}
+
+pub(crate) fn sql_string(string: &str) -> String {
+	format!("'{}'", string.replace('\'', "''"))
+}
+
+/// Opposite of [split_table_name]
+fn join_table_name(schema_name: &str, table_name: &str) -> String {
 	if !schema_name.is_empty() {
 		format!("{}.{}", schema_name, table_name)
 	} else {
 		table_name.to_string()
 	}
 }
+/// A table's name as of its schema and its own name, where it's of a `.`; names such as `order_items` being of no schema
 pub(crate) fn split_table_name(table_name: &str) -> (&str, &str) {
-	let mut table_name: Vec<&str> = table_name.split('_').collect();
-	if table_name.len() == 1 {
-		("", table_name.remove(0))
-	} else if table_name.len() == 2 {
-		(table_name.remove(0), table_name.remove(0))
-	} else {
-		("", "")
-	}
+	table_name.split_once('.').unwrap_or(("", table_name))
 }
 
 fn odbc_type_to_multisql(data_type: &str) -> ValueType {
-	match data_type {
-		"bigint" /*lossy*/ | "tinyint" | "smallint" | "int" => ValueType::I64,
-		"decimal" /*lossy*/ | "money" /*lossy*/ | "float" => ValueType::F64,
-		"smalldatetime" | "datetime" => ValueType::Timestamp,
-		"bit" => ValueType::Bool,
-		"varchar" => ValueType::Str,
-		_ => {
-			ValueType::Any
+	match data_type.to_lowercase().as_str() {
+		"bigint" /*lossy*/ | "int8" | "integer" | "int" | "int4" | "smallint" | "int2"
+		| "tinyint" => ValueType::I64,
+		"decimal" /*lossy*/ | "numeric" /*lossy*/ | "money" /*lossy*/ | "float" | "float4"
+		| "float8" | "real" | "double" | "double precision" => ValueType::F64,
+		"smalldatetime" | "datetime" | "datetime2" | "date" | "timestamp" | "timestamptz" => {
+			ValueType::Timestamp
 		}
+		"bit" | "bool" | "boolean" => ValueType::Bool,
+		"char" | "nchar" | "varchar" | "nvarchar" | "text" | "ntext" | "bpchar" => ValueType::Str,
+		"binary" | "varbinary" | "image" | "bytea" | "blob" => ValueType::Bytes,
+		_ => ValueType::Any,
 	}
 }
 
-fn odbc_value_to_multisql(data_value: String, data_type: &ValueType) -> Result<Value> {
-	if data_value == "NULL" || (data_value.is_empty() && !matches!(data_type, ValueType::Str)) {
-		return Ok(Value::Null);
+/// The type of a column of a result set
+pub(crate) fn data_type_to_multisql(data_type: DataType) -> ValueType {
+	match data_type {
+		DataType::BigInt | DataType::Integer | DataType::SmallInt | DataType::TinyInt => {
+			ValueType::I64
+		}
+		DataType::Numeric { .. } /*lossy*/ | DataType::Decimal { .. } /*lossy*/
+		| DataType::Float { .. } | DataType::Real | DataType::Double => ValueType::F64,
+		DataType::Date | DataType::Timestamp { .. } => ValueType::Timestamp,
+		DataType::Bit => ValueType::Bool,
+		DataType::Binary { .. } | DataType::Varbinary { .. } | DataType::LongVarbinary { .. } => {
+			ValueType::Bytes
+		}
+		_ => ValueType::Str,
 	}
-	let from = Value::Str(data_value.clone());
+}
+
+pub(crate) fn odbc_value_to_multisql(
+	data_value: Option<String>,
+	data_type: &ValueType,
+) -> Result<Value> {
+	let data_value = match data_value {
+		Some(data_value) if !data_value.is_empty() || matches!(data_type, ValueType::Str) => {
+			data_value
+		}
+		_ => return Ok(Value::Null),
+	};
 	match data_type {
-		ValueType::I64 => Ok(from
-			.cast()
-			.map(Value::I64)
-			.expect(&format!("{}", data_value))),
-		ValueType::F64 => Ok(from
-			.cast()
-			.map(Value::F64)
-			.expect(&format!("{}", data_value))),
-		ValueType::Timestamp => Ok(Value::Null), // TODO
-		ValueType::Bool => from
-			.cast()
-			.map(Value::I64)
-			.and_then(|int| int.cast().map(Value::Bool)),
-		ValueType::Str => Ok(from),
-		_ => Ok(from),
+		ValueType::I64 => Value::Str(data_value).cast().map(Value::I64),
+		ValueType::F64 => Value::Str(data_value).cast().map(Value::F64),
+		ValueType::Timestamp => {
+			// Fractions of seconds are dropped, as timestamps are only of seconds
+			let data_value = data_value
+				.split_once('.')
+				.map(|(seconds, _)| seconds.to_string())
+				.unwrap_or(data_value);
+			Value::Str(data_value).cast_valuetype(&ValueType::Timestamp)
+		}
+		ValueType::Bool => match data_value.to_lowercase().as_str() {
+			"1" | "t" | "true" => Ok(Value::Bool(true)),
+			"0" | "f" | "false" => Ok(Value::Bool(false)),
+			_ => Err(ValueError::ImpossibleCast.into()),
+		},
+		// Binary columns are read as text, which ODBC gives as hex
+		ValueType::Bytes => hex::decode(&data_value)
+			.map(Value::Bytes)
+			.map_err(|_| ValueError::FailedToParseHex(data_value).into()),
+		_ => Ok(Value::Str(data_value)),
 	}
 }
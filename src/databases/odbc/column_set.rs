@@ -1,116 +1,145 @@
 use {
-	crate::{Cast, Error, Result, Value, ValueType},
-	odbc_api::buffers::{
-		AnyColumnBuffer, BufferDescription, BufferKind, ColumnarBuffer, TextRowSet,
+	crate::{Cast, Result, Value, ValueType},
+	chrono::{Datelike, NaiveDateTime, Timelike},
+	odbc_api::{
+		buffers::{AnyColumnBuffer, AnyColumnSliceMut, BufferDescription, BufferKind},
+		parameter::InputParameter,
+		sys::Timestamp,
+		Bit, ColumnarBulkInserter, IntoParameter,
 	},
 };
 
-impl Into<BufferKind> for ValueType {
-	fn into(self) -> BufferKind {
-		match self {
-			ValueType::Str => BufferKind::Text {
-				max_str_len: 255, // Arbitrary!!! Could be a problem..?
-			},
-			ValueType::I64 => BufferKind::I64,
+impl From<ValueType> for BufferKind {
+	fn from(value_type: ValueType) -> Self {
+		match value_type {
+			ValueType::I64 | ValueType::U64 /*lossy*/ => BufferKind::I64,
 			ValueType::F64 => BufferKind::F64,
 			ValueType::Bool => BufferKind::Bit,
 			ValueType::Timestamp => BufferKind::Timestamp,
-			ValueType::U64 => BufferKind::I64, // Unsafe
-			_ => unimplemented!(),
+			ValueType::Bytes => BufferKind::Binary {
+				length: 255, // Arbitrary, see `ColumnValues::description`
+			},
+			_ => BufferKind::Text {
+				max_str_len: 255, // Arbitrary, see `ColumnValues::description`
+			},
 		}
 	}
 }
 
 struct ColumnValues {
-	index: usize,
 	value_type: ValueType,
-	pub values: Vec<Value>,
-	size: usize,
+	values: Vec<Value>,
 }
-impl TryInto<AnyColumnBuffer> for ColumnValues {
-	type Error = Error;
-	fn try_into(self) -> Result<AnyColumnBuffer> {
-		let description = BufferDescription {
-			nullable: true,
-			kind: self.value_type.into(),
+impl ColumnValues {
+	fn description(&self) -> BufferDescription {
+		let kind = match BufferKind::from(self.value_type.clone()) {
+			// Buffers of variable length values are as long as the longest value
+			BufferKind::Text { .. } => BufferKind::Text {
+				max_str_len: self.max_length(|value| value.cast().map(|text: String| text.len())),
+			},
+			BufferKind::Binary { .. } => BufferKind::Binary {
+				length: self.max_length(|value| value.cast().map(|bytes: Vec<u8>| bytes.len())),
+			},
+			kind => kind,
 		};
-		let mut buffer = AnyColumnBuffer::from_description(self.size, description);
-		match &mut buffer {
-			AnyColumnBuffer::NullableI64(column) => {
-				let values = self
-					.values
-					.into_iter()
-					.map(|cell| {
-						Ok(if matches!(cell, Value::Null) {
-							None
-						} else {
-							Some(cell.cast()?)
-						})
-					})
-					.collect::<Result<Vec<Option<i64>>>>()?;
-				column.writer_n(self.size).write(values.into_iter());
+		BufferDescription {
+			nullable: true,
+			kind,
+		}
+	}
+	fn max_length(&self, length: impl Fn(Value) -> Result<usize>) -> usize {
+		self.values
+			.iter()
+			.filter_map(|value| length(value.clone()).ok())
+			.max()
+			.unwrap_or_default()
+			.max(1)
+	}
+	fn cells<T>(self, convert: impl Fn(Value) -> Result<T>) -> Result<Vec<Option<T>>> {
+		let value_type = self.value_type;
+		self.values
+			.into_iter()
+			.map(|value| match value {
+				Value::Null => Ok(None),
+				value => convert(value.cast_valuetype(&value_type)?).map(Some),
+			})
+			.collect()
+	}
+	fn write(self, column: AnyColumnSliceMut) -> Result<()> {
+		match column {
+			AnyColumnSliceMut::NullableI64(mut column) => {
+				column.write(self.cells(Value::cast)?.into_iter())
 			}
-			AnyColumnBuffer::Text(column) => {
-				let values = self
-					.values
-					.into_iter()
-					.map(|cell| {
-						Ok(if matches!(cell, Value::Null) {
-							None
-						} else {
-							Some(cell.cast()?)
-						})
-					})
-					.collect::<Result<Vec<Option<String>>>>()?;
-				column.writer_n(self.size).write(
-					values
-						.iter()
-						.map(|text| text.as_ref().map(|text| text.as_bytes())),
-				);
+			AnyColumnSliceMut::NullableF64(mut column) => {
+				column.write(self.cells(Value::cast)?.into_iter())
 			}
-			_ => unimplemented!(),
+			AnyColumnSliceMut::NullableBit(mut column) => column.write(
+				self.cells(|value| value.cast().map(Bit::from_bool))?
+					.into_iter(),
+			),
+			AnyColumnSliceMut::NullableTimestamp(mut column) => {
+				column.write(self.cells(timestamp)?.into_iter())
+			}
+			AnyColumnSliceMut::Text(mut column) => {
+				let cells: Vec<Option<String>> = self.cells(Value::cast)?;
+				cells.iter().enumerate().for_each(|(row, cell)| {
+					column.set_cell(row, cell.as_ref().map(|text| text.as_bytes()))
+				});
+			}
+			AnyColumnSliceMut::Binary(mut column) => {
+				let cells: Vec<Option<Vec<u8>>> = self.cells(Value::cast)?;
+				cells
+					.iter()
+					.enumerate()
+					.for_each(|(row, cell)| column.set_cell(row, cell.as_deref()));
+			}
+			_ => unreachable!(), // Only the above are described, see `From<ValueType> for BufferKind`
 		}
-		Ok(buffer)
+		Ok(())
 	}
 }
+
+/// Rows to be sent all together, column by column, in buffers of the columns' types
 pub(crate) struct ColumnSet {
 	columns: Vec<ColumnValues>,
+	rows: usize,
 }
 
 impl ColumnSet {
-	pub fn new(rows: Vec<Vec<Value>>, size: usize) -> Self {
-		let columns: Vec<(Vec<Value>, Option<ValueType>)> =
-			rows[0].iter().map(|_| (Vec::new(), None)).collect();
-		let columns = rows.into_iter().fold(columns, |mut columns, row| {
-			row.into_iter().enumerate().for_each(|(index, cell)| {
-				if columns[index].1.is_none() && !matches!(cell, Value::Null) {
-					columns[index].1 = Some((&cell).into());
-				}
-				columns[index].0.push(cell);
-			});
-			columns
-		});
-		let columns = columns
-			.into_iter()
-			.enumerate()
-			.filter_map(|(index, (values, value_type))| {
-				value_type.map(|value_type| ColumnValues {
-					index,
-					value_type,
-					values,
-					size,
-				})
+	/// Values are cast to the types given, [ValueType::Any] columns taking the type of their first value
+	pub fn new(rows: Vec<Vec<Value>>, value_types: &[ValueType]) -> Self {
+		let columns: Vec<ColumnValues> = value_types
+			.iter()
+			.map(|value_type| ColumnValues {
+				value_type: value_type.clone(),
+				values: Vec::with_capacity(rows.len()),
 			})
 			.collect();
-		Self { columns }
+		let row_count = rows.len();
+		let mut columns = rows.into_iter().fold(columns, |mut columns, row| {
+			row.into_iter()
+				.zip(columns.iter_mut())
+				.for_each(|(cell, column)| column.values.push(cell));
+			columns
+		});
+		columns
+			.iter_mut()
+			.filter(|column| matches!(column.value_type, ValueType::Any))
+			.for_each(|column| {
+				column.value_type = column
+					.values
+					.iter()
+					.find(|value| !matches!(value, Value::Null))
+					.map(ValueType::from)
+					.unwrap_or(ValueType::Str)
+			});
+		Self {
+			columns,
+			rows: row_count,
+		}
 	}
-	pub fn query(&self, table: &str, columns: &[&str]) -> String {
-		let columns: Vec<&str> = columns
-			.into_iter()
-			.enumerate()
-			.filter(|(index, _)| self.columns.iter().any(|col| index == &col.index))
-			.map(|(_, column)| *column)
-			.collect();
+	/// The table and columns are to be quoted already, see [Dialect](super::Dialect)
+	pub fn query(&self, table: &str, columns: &[String]) -> String {
 		let placeholders: Vec<&str> = columns.iter().map(|_| "?").collect();
 		format!(
 			"INSERT INTO {table} ({columns}) VALUES ({placeholders})",
@@ -119,34 +148,53 @@ impl ColumnSet {
 			placeholders = placeholders.join(", ")
 		)
 	}
-}
-impl TryInto<ColumnarBuffer<AnyColumnBuffer>> for ColumnSet {
-	type Error = Error;
-	fn try_into(self) -> Result<ColumnarBuffer<AnyColumnBuffer>> {
-		let buffers = self
-			.columns
+	pub fn descriptions(&self) -> Vec<BufferDescription> {
+		self.columns.iter().map(ColumnValues::description).collect()
+	}
+	pub fn len(&self) -> usize {
+		self.rows
+	}
+	/// Fills the buffers of an inserter, which must be of [ColumnSet::descriptions]
+	pub fn write<S>(self, inserter: &mut ColumnarBulkInserter<S, AnyColumnBuffer>) -> Result<()>
+	where
+		S: odbc_api::handles::AsStatementRef,
+	{
+		inserter.set_num_rows(self.rows);
+		self.columns
 			.into_iter()
 			.enumerate()
-			.map(|(index, column)| column.try_into().map(|buffer| (index as u16, buffer)))
-			.collect::<Result<Vec<_>>>()?;
-		Ok(ColumnarBuffer::new(buffers))
+			.try_for_each(|(index, column)| column.write(inserter.column_mut(index)))
 	}
 }
-impl TryInto<TextRowSet> for ColumnSet {
-	type Error = Error;
-	fn try_into(self) -> Result<TextRowSet> {
-		let mut buffer = TextRowSet::from_max_str_lens(
-			self.columns[0].values.len(),
-			self.columns.iter().map(|_| 0),
-		)?;
-		for index in 0..self.columns[0].values.len() {
-			let record = self
-				.columns
-				.iter()
-				.map(|column| column.values[index].clone().cast())
-				.collect::<Result<Vec<String>>>()?;
-			buffer.append(record.iter().map(|field| Some(field.as_bytes())))
-		}
-		Ok(buffer)
-	}
+
+fn timestamp(value: Value) -> Result<Timestamp> {
+	let datetime: NaiveDateTime = value.cast()?;
+	Ok(Timestamp {
+		year: datetime.year() as i16,
+		month: datetime.month() as u16,
+		day: datetime.day() as u16,
+		hour: datetime.hour() as u16,
+		minute: datetime.minute() as u16,
+		second: datetime.second() as u16,
+		fraction: 0,
+	})
+}
+
+/// A value as a parameter of a statement, timestamps being given as text
+pub(crate) fn into_parameter(value: Value) -> Result<Box<dyn InputParameter>> {
+	Ok(match value {
+		Value::Null => Box::new(Option::<String>::None.into_parameter()),
+		Value::Bool(value) => Box::new(Bit::from_bool(value)),
+		Value::I64(value) => Box::new(value),
+		Value::U64(_) => Box::new(Cast::<i64>::cast(value)?),
+		Value::F64(value) => Box::new(value),
+		Value::Bytes(value) => Box::new(value.into_parameter()),
+		Value::Timestamp(_) => Box::new(
+			Cast::<NaiveDateTime>::cast(value)?
+				.format("%F %T")
+				.to_string()
+				.into_parameter(),
+		),
+		value => Box::new(Cast::<String>::cast(value)?.into_parameter()),
+	})
 }
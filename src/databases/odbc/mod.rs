@@ -2,28 +2,153 @@ mod auto_increment;
 mod base;
 mod column_set;
 mod mutable;
+mod pool;
 
 pub(crate) use column_set::ColumnSet;
 use {
-	crate::{database::*, Result},
-	odbc_api::Environment,
+	crate::{database::*, Result, Schema, ValueType},
+	odbc_api::Connection,
+	pool::{ConnectionPool, PooledConnection},
+	serde::Serialize,
+	std::{
+		collections::HashMap,
+		fmt::Debug,
+		sync::{Mutex, MutexGuard},
+	},
+	thiserror::Error,
 };
 
+#[derive(Error, Serialize, Debug, PartialEq)]
+pub enum ODBCDatabaseError {
+	#[error("table not found: {0}")]
+	TableNotFound(String),
+	#[error("table has no primary key by which to find its rows: {0}")]
+	NoPrimaryKey(String),
+	#[error("failed to set up an ODBC environment")]
+	EnvironmentUnavailable,
+}
+
 pub struct ODBCDatabase {
-	environment: Environment,
-	connection_string: String,
+	pool: ConnectionPool,
+	schemas: Mutex<SchemaCache>,
 }
 
 impl DBFull for ODBCDatabase {}
 
 impl ODBCDatabase {
 	pub fn new(connection_string: &str) -> Result<Self> {
-		let environment = Environment::new()?;
-		environment.connect_with_connection_string(connection_string)?; // Fail Fast
-		let connection_string = connection_string.to_string();
 		Ok(Self {
-			environment,
-			connection_string,
+			pool: ConnectionPool::new(connection_string)?,
+			schemas: Mutex::new(SchemaCache::default()),
 		})
 	}
+	pub(crate) fn connection(&self) -> Result<PooledConnection<'_>> {
+		self.pool.get()
+	}
+	pub(crate) fn schema_cache(&self) -> MutexGuard<'_, SchemaCache> {
+		self.schemas
+			.lock()
+			.expect("Unreachable: ODBC schema cache poisoned!")
+	}
+}
+
+/// Schemas as last described by the remote database, kept until invalidated
+#[derive(Default)]
+pub(crate) struct SchemaCache {
+	schemas: HashMap<String, Option<Schema>>,
+	/// Whether every table is held
+	complete: bool,
+}
+
+impl SchemaCache {
+	pub fn get(&self, table_name: &str) -> Option<Option<Schema>> {
+		self.schemas.get(table_name).cloned()
+	}
+	pub fn all(&self) -> Option<Vec<Schema>> {
+		self.complete
+			.then(|| self.schemas.values().flatten().cloned().collect())
+	}
+	pub fn insert(&mut self, table_name: String, schema: Option<Schema>) {
+		self.schemas.insert(table_name, schema);
+	}
+	pub fn fill(&mut self, schemas: Vec<Schema>) {
+		self.schemas = schemas
+			.into_iter()
+			.map(|schema| (schema.table_name.clone(), Some(schema)))
+			.collect();
+		self.complete = true;
+	}
+	pub fn invalidate(&mut self, table_name: &str) {
+		self.schemas.remove(table_name);
+		self.complete = false;
+	}
+	pub fn clear(&mut self) {
+		self.schemas.clear();
+		self.complete = false;
+	}
+}
+
+/// Differences between databases in types and in how identifiers are quoted
+pub(crate) enum Dialect {
+	SQLServer,
+	PostgreSQL,
+	MySQL,
+	Other,
+}
+impl Dialect {
+	pub fn of(connection: &Connection) -> Result<Self> {
+		let name = connection.database_management_system_name()?;
+		Ok(match name.as_str() {
+			"Microsoft SQL Server" => Dialect::SQLServer,
+			"PostgreSQL" => Dialect::PostgreSQL,
+			"MySQL" | "MariaDB" => Dialect::MySQL,
+			_ => Dialect::Other,
+		})
+	}
+	/// An identifier, quoted, so that it may be of any name; by backticks for MySQL, otherwise as standard SQL
+	pub fn quoted(&self, identifier: &str) -> String {
+		let quote = match self {
+			Dialect::MySQL => '`',
+			_ => '"',
+		};
+		format!(
+			"{quote}{identifier}{quote}",
+			quote = quote,
+			identifier = identifier.replace(quote, &quote.to_string().repeat(2))
+		)
+	}
+	/// A table's name, of its schema where it has one, quoted; see [split_table_name](base::split_table_name)
+	pub fn table(&self, table_name: &str) -> String {
+		match base::split_table_name(table_name) {
+			("", table_name) => self.quoted(table_name),
+			(schema_name, table_name) => {
+				format!("{}.{}", self.quoted(schema_name), self.quoted(table_name))
+			}
+		}
+	}
+	/// As SQL Server has no `TRUE` nor `FALSE`, of `BIT`s, `1` and `0`
+	pub fn bool_literal(&self, value: bool) -> &'static str {
+		match (self, value) {
+			(Dialect::SQLServer, true) => "1",
+			(Dialect::SQLServer, false) => "0",
+			(_, true) => "TRUE",
+			(_, false) => "FALSE",
+		}
+	}
+	pub fn data_type(&self, data_type: &ValueType) -> &'static str {
+		use Dialect::*;
+		match (data_type, self) {
+			(ValueType::I64 | ValueType::U64, _) => "BIGINT",
+			(ValueType::F64, _) => "DOUBLE PRECISION",
+			(ValueType::Bool, SQLServer) => "BIT",
+			(ValueType::Bool, _) => "BOOLEAN",
+			(ValueType::Timestamp, SQLServer) => "DATETIME2",
+			(ValueType::Timestamp, _) => "TIMESTAMP",
+			(ValueType::Bytes, SQLServer) => "VARBINARY(MAX)",
+			(ValueType::Bytes, PostgreSQL) => "BYTEA",
+			(ValueType::Bytes, MySQL | Other) => "BLOB",
+			(_, SQLServer) => "NVARCHAR(MAX)",
+			(_, _) => "TEXT",
+		}
+	}
 }
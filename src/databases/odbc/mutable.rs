@@ -1,47 +1,230 @@
 use {
-	super::{base::convert_table_name, ColumnSet},
-	crate::{DBBase, DBMut, ODBCDatabase, Result, Row, Value},
+	super::{
+		base::{data_type_to_multisql, odbc_value_to_multisql, primary_key, read_text_rows},
+		column_set::into_parameter,
+		pool::PooledConnection,
+		ColumnSet, Dialect, ODBCDatabaseError,
+	},
+	crate::{DBBase, DBMut, ODBCDatabase, Payload, Result, Row, Schema, Value, ValueType},
 	async_trait::async_trait,
-	odbc_api::buffers::TextRowSet,
+	odbc_api::{parameter::InputParameter, Connection, ResultSetMetadata},
 };
 
 const BATCH_SIZE: usize = 4096;
 
 #[async_trait(?Send)]
 impl DBMut for ODBCDatabase {
+	async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
+		let connection = self.connection()?;
+		let dialect = Dialect::of(&connection)?;
+		let columns = schema
+			.column_defs
+			.iter()
+			.map(|column_def| {
+				format!(
+					"{name} {data_type}{nullable}{unique}",
+					name = dialect.quoted(&column_def.name),
+					data_type = dialect.data_type(&column_def.data_type),
+					nullable = if column_def.is_nullable {
+						""
+					} else {
+						" NOT NULL"
+					},
+					unique = if column_def.is_unique { " UNIQUE" } else { "" }
+				)
+			})
+			.collect::<Vec<String>>()
+			.join(", ");
+		connection.execute(
+			&format!(
+				"CREATE TABLE {table} ({columns})",
+				table = dialect.table(&schema.table_name),
+				columns = columns
+			),
+			(),
+		)?;
+		self.schema_cache().invalidate(&schema.table_name);
+		Ok(())
+	}
+
+	async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
+		let connection = self.connection()?;
+		let dialect = Dialect::of(&connection)?;
+		connection.execute(&format!("DROP TABLE {}", dialect.table(table_name)), ())?;
+		self.schema_cache().invalidate(table_name);
+		Ok(())
+	}
+
 	async fn insert_data(&mut self, table_name: &str, rows: Vec<Row>) -> Result<()> {
 		self.insert(table_name, rows.to_vec()).await?;
 		Ok(())
 	}
+
+	/// Rows are found by their keys, of their primary key, see [DBBase::scan_data]
+	async fn update_data(&mut self, table_name: &str, rows: Vec<(Value, Row)>) -> Result<()> {
+		let schema = self
+			.fetch_schema(table_name)
+			.await?
+			.ok_or_else(|| ODBCDatabaseError::TableNotFound(table_name.to_string()))?;
+		let connection = self.connection()?;
+		let dialect = Dialect::of(&connection)?;
+		let key = key_of(&connection, table_name)?;
+		let assignments = schema
+			.column_defs
+			.iter()
+			.map(|column_def| format!("{} = ?", dialect.quoted(&column_def.name)))
+			.collect::<Vec<String>>()
+			.join(", ");
+		let query = format!(
+			"UPDATE {table} SET {assignments} WHERE {conditions}",
+			table = dialect.table(table_name),
+			assignments = assignments,
+			conditions = key_conditions(&key, &dialect)
+		);
+
+		transaction(&connection, || {
+			let mut statement = connection.prepare(&query)?;
+			for (row_key, Row(values)) in rows {
+				let parameters = values
+					.into_iter()
+					.chain(key_values(row_key, key.len()))
+					.map(into_parameter)
+					.collect::<Result<Vec<Box<dyn InputParameter>>>>()?;
+				statement.execute(parameters.as_slice())?;
+			}
+			Ok(())
+		})
+	}
+
+	/// Rows are found by their keys, of their primary key, see [DBBase::scan_data]
+	async fn delete_data(&mut self, table_name: &str, keys: Vec<Value>) -> Result<()> {
+		let connection = self.connection()?;
+		let dialect = Dialect::of(&connection)?;
+		let key = key_of(&connection, table_name)?;
+		let query = format!(
+			"DELETE FROM {table} WHERE {conditions}",
+			table = dialect.table(table_name),
+			conditions = key_conditions(&key, &dialect)
+		);
+
+		transaction(&connection, || {
+			let mut statement = connection.prepare(&query)?;
+			for row_key in keys {
+				let parameters = key_values(row_key, key.len())
+					.map(into_parameter)
+					.collect::<Result<Vec<Box<dyn InputParameter>>>>()?;
+				statement.execute(parameters.as_slice())?;
+			}
+			Ok(())
+		})
+	}
+
+	/// Sends the query, as it is, to the remote database.
+	/// As it may change any table, cached schemas are forgotten.
+	async fn execute_native(&mut self, query: &str) -> Result<Payload> {
+		self.schema_cache().clear();
+		let connection = self.connection()?;
+		let mut cursor = match connection.execute(query, ())? {
+			Some(cursor) => cursor,
+			None => return Ok(Payload::Success),
+		};
+		let labels = cursor
+			.column_names()?
+			.collect::<std::result::Result<Vec<String>, _>>()?;
+		let data_types = (1..=labels.len() as u16)
+			.map(|col| cursor.col_data_type(col).map(data_type_to_multisql))
+			.collect::<std::result::Result<Vec<ValueType>, _>>()?;
+		let rows = read_text_rows(cursor)?
+			.into_iter()
+			.map(|row| {
+				row.into_iter()
+					.zip(data_types.iter())
+					.map(|(text, data_type)| odbc_value_to_multisql(text, data_type))
+					.collect::<Result<Vec<Value>>>()
+					.map(Row)
+			})
+			.collect::<Result<Vec<Row>>>()?;
+		Ok(Payload::Select { labels, rows })
+	}
 }
 
 impl ODBCDatabase {
 	async fn insert(&mut self, table_name: &str, rows: Vec<Row>) -> Result<()> {
-		let connection = self
-			.environment
-			.connect_with_connection_string(&self.connection_string)?;
-		let schema = self.fetch_schema(&table_name).await?.unwrap();
-		let table_name = convert_table_name(table_name);
+		let schema = self
+			.fetch_schema(table_name)
+			.await?
+			.ok_or_else(|| ODBCDatabaseError::TableNotFound(table_name.to_string()))?;
+		let connection = self.connection()?;
+		let dialect = Dialect::of(&connection)?;
+		let table_name = dialect.table(table_name);
 		let columns = schema
 			.column_defs
 			.iter()
-			.map(|col_def| col_def.name.as_str())
-			.collect::<Vec<&str>>();
+			.map(|col_def| dialect.quoted(&col_def.name))
+			.collect::<Vec<String>>();
+		let value_types = schema
+			.column_defs
+			.iter()
+			.map(|col_def| col_def.data_type.clone())
+			.collect::<Vec<ValueType>>();
 
 		let rows: Vec<Vec<Value>> = rows.into_iter().map(|Row(row)| row).collect();
 
-		connection.set_autocommit(false)?;
-		for rows in rows.chunks(BATCH_SIZE) {
-			let column_set = ColumnSet::new(rows.to_vec(), BATCH_SIZE);
-			let query = column_set.query(&table_name, &columns);
+		transaction(&connection, || {
+			for rows in rows.chunks(BATCH_SIZE) {
+				let column_set = ColumnSet::new(rows.to_vec(), &value_types);
+				let query = column_set.query(&table_name, &columns);
 
-			let mut statement = connection.prepare(&query)?;
-			//let buffer: ColumnarBuffer<AnyColumnBuffer> = column_set.try_into()?;
-			let buffer: TextRowSet = column_set.try_into()?;
+				let mut inserter = connection
+					.prepare(&query)?
+					.into_any_column_inserter(column_set.len(), column_set.descriptions())?;
+				column_set.write(&mut inserter)?;
+				inserter.execute()?;
+			}
+			Ok(())
+		})
+	}
+}
 
-			statement.execute(&buffer)?;
-		}
-		connection.commit()?;
-		Ok(())
+/// Runs `action` as one transaction, rolling it back where it, or its commit, fails.
+/// Autocommit is restored however the transaction ends; where it can't be, the connection isn't returned to its pool.
+fn transaction<T>(connection: &PooledConnection, action: impl FnOnce() -> Result<T>) -> Result<T> {
+	connection.set_autocommit(false)?;
+	let result = action();
+	let ended = match result {
+		Ok(_) => connection.commit(),
+		Err(_) => connection.rollback(),
+	};
+	// Restoring autocommit would commit a transaction left open, so one which can't be rolled back is left to be dropped
+	let left_open = ended.is_err() && connection.rollback().is_err();
+	if left_open || connection.set_autocommit(true).is_err() {
+		connection.discard();
+	}
+	ended?;
+	result
+}
+
+fn key_of(connection: &Connection, table_name: &str) -> Result<Vec<String>> {
+	let key = primary_key(connection, table_name);
+	if key.is_empty() {
+		Err(ODBCDatabaseError::NoPrimaryKey(table_name.to_string()).into())
+	} else {
+		Ok(key)
+	}
+}
+
+fn key_conditions(key: &[String], dialect: &Dialect) -> String {
+	key.iter()
+		.map(|column| format!("{} = ?", dialect.quoted(column)))
+		.collect::<Vec<String>>()
+		.join(" AND ")
+}
+
+/// Values of a row's key, of as many columns as the primary key has, see `row_key`
+fn key_values(row_key: Value, columns: usize) -> impl Iterator<Item = Value> {
+	match row_key {
+		Value::Array(values) if columns > 1 => values,
+		row_key => vec![row_key],
 	}
+	.into_iter()
 }
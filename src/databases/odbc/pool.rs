@@ -0,0 +1,111 @@
+use {
+	super::ODBCDatabaseError,
+	crate::Result,
+	lazy_static::lazy_static,
+	odbc_api::{Connection, Environment},
+	std::{
+		cell::Cell,
+		ops::Deref,
+		sync::{Mutex, MutexGuard},
+	},
+};
+
+lazy_static! {
+	/// One environment serves every connection, as ODBC would have it
+	static ref ENVIRONMENT: Option<Environment> = Environment::new().ok();
+}
+
+/// Most connections kept open, when not in use, by one pool
+const MAX_IDLE: usize = 4;
+
+/// Connections to one data source, kept open once made to be used again
+pub(crate) struct ConnectionPool {
+	connection_string: String,
+	idle: Mutex<Vec<Connection<'static>>>,
+}
+
+impl ConnectionPool {
+	/// Fails where the data source can't be connected to
+	pub fn new(connection_string: &str) -> Result<Self> {
+		let pool = Self {
+			connection_string: connection_string.to_string(),
+			idle: Mutex::new(Vec::new()),
+		};
+		drop(pool.get()?); // Fail Fast
+		Ok(pool)
+	}
+
+	/// An idle connection which is still alive, or otherwise a new one
+	pub fn get(&self) -> Result<PooledConnection<'_>> {
+		let connection = match self.take_idle().find(is_alive) {
+			Some(connection) => connection,
+			None => environment()?.connect_with_connection_string(&self.connection_string)?,
+		};
+		Ok(PooledConnection {
+			connection: Some(connection),
+			pool: self,
+			discarded: Cell::new(false),
+		})
+	}
+
+	/// Idle connections, as they're taken; dead connections are taken and dropped in finding one alive
+	fn take_idle(&self) -> impl Iterator<Item = Connection<'static>> + '_ {
+		std::iter::from_fn(move || self.idle().pop())
+	}
+
+	fn idle(&self) -> MutexGuard<'_, Vec<Connection<'static>>> {
+		self.idle
+			.lock()
+			.expect("Unreachable: ODBC connection pool poisoned!")
+	}
+
+	fn release(&self, connection: Connection<'static>) {
+		let mut idle = self.idle();
+		if idle.len() < MAX_IDLE && is_alive(&connection) {
+			idle.push(connection);
+		}
+	}
+}
+
+fn environment() -> Result<&'static Environment> {
+	ENVIRONMENT
+		.as_ref()
+		.ok_or_else(|| ODBCDatabaseError::EnvironmentUnavailable.into())
+}
+
+/// Whether the driver believes the connection to still be open
+fn is_alive(connection: &Connection) -> bool {
+	matches!(connection.is_dead(), Ok(false))
+}
+
+/// A connection of a pool, returned to it when dropped
+pub(crate) struct PooledConnection<'pool> {
+	connection: Option<Connection<'static>>,
+	pool: &'pool ConnectionPool,
+	discarded: Cell<bool>,
+}
+
+impl PooledConnection<'_> {
+	/// Closes the connection once dropped, rather than returning it, where it's been left unfit for use again
+	pub fn discard(&self) {
+		self.discarded.set(true);
+	}
+}
+
+impl Deref for PooledConnection<'_> {
+	type Target = Connection<'static>;
+	fn deref(&self) -> &Self::Target {
+		self.connection
+			.as_ref()
+			.expect("Unreachable: Pooled connection already released!")
+	}
+}
+
+impl Drop for PooledConnection<'_> {
+	fn drop(&mut self) {
+		match self.connection.take() {
+			Some(connection) if !self.discarded.get() => self.pool.release(connection),
+			_ => (),
+		}
+	}
+}
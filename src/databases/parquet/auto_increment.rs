@@ -0,0 +1,41 @@
+use {
+	super::{row_count, ParquetDatabase},
+	crate::{AutoIncrement, DatabaseError, Result},
+	async_trait::async_trait,
+};
+
+#[async_trait(?Send)]
+impl AutoIncrement for ParquetDatabase {
+	async fn generate_increment_values(
+		&mut self,
+		table_name: String,
+		columns: Vec<(
+			usize,  /*index*/
+			String, /*name*/
+			i64,    /*row_count*/
+		) /*column*/>,
+	) -> Result<
+		Vec<(
+			/*column*/ (usize /*index*/, String /*name*/),
+			/*start_value*/ i64,
+		)>,
+	> {
+		let parts = self
+			.parts(&table_name)?
+			.ok_or(DatabaseError::TableNotFound)?;
+		let rows = row_count(&parts)?;
+		Ok(columns
+			.into_iter()
+			.map(|(index, name, _)| ((index, name), rows + 1))
+			.collect())
+	}
+
+	async fn set_increment_value(
+		&mut self,
+		_table_name: &str,
+		_column_name: &str,
+		_end: i64,
+	) -> Result<()> {
+		Ok(())
+	}
+}
@@ -0,0 +1,179 @@
+use {
+	super::{
+		open,
+		schema::{readable_fields, value_of},
+		statistics::may_match,
+		ParquetDatabase,
+	},
+	crate::{
+		project, DBBase, DatabaseError, Plane, Predicate, Result, Row, RowFilter, Schema, Value,
+		ValueType, WIPError,
+	},
+	async_trait::async_trait,
+	parquet::{
+		file::reader::{FileReader, RowGroupReader},
+		schema::types::Type,
+	},
+	std::sync::Arc,
+};
+
+#[async_trait(?Send)]
+impl DBBase for ParquetDatabase {
+	async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
+		Ok(self.table(table_name)?.map(|(_, schema)| schema))
+	}
+	async fn scan_schemas(&self) -> Result<Vec<Schema>> {
+		let mut schemas = vec![];
+		for table_name in self.table_names()? {
+			if let Some(schema) = self.fetch_schema(&table_name).await? {
+				schemas.push(schema);
+			}
+		}
+		Ok(schemas)
+	}
+
+	async fn scan_data(&self, table_name: &str) -> Result<Plane> {
+		self.scan_data_filtered(table_name, &[], None).await
+	}
+
+	/// Only the columns projected, and those of the predicates, are read;
+	/// row groups whose statistics show no row can meet the predicates are skipped.
+	/// Rows are keyed by their position in the table.
+	async fn scan_data_filtered(
+		&self,
+		table_name: &str,
+		predicates: &[Predicate],
+		projection: Option<&[String]>,
+	) -> Result<Plane> {
+		let (parts, schema) = self
+			.table(table_name)?
+			.ok_or(DatabaseError::TableNotFound)?;
+		let columns: Vec<String> = schema
+			.column_defs
+			.into_iter()
+			.map(|column_def| column_def.name)
+			.filter(|column| match projection {
+				Some(projection) => {
+					projection.contains(column)
+						|| predicates
+							.iter()
+							.any(|predicate| predicate.column() == column)
+				}
+				None => true,
+			})
+			.collect();
+		let filter = RowFilter::new(predicates, &columns);
+
+		let mut plane = vec![];
+		let mut key = 0;
+		for part in parts {
+			let reader = open(&part)?;
+			let root = reader
+				.metadata()
+				.file_metadata()
+				.schema_descr()
+				.root_schema();
+			let fields = readable_fields(root);
+			let fields: Vec<_> = columns
+				.iter()
+				.map(|column| {
+					fields
+						.iter()
+						.find(|(field, _)| field.name() == column)
+						.cloned()
+				})
+				.collect();
+			let read_fields: Vec<_> = fields.iter().flatten().cloned().collect();
+			let read_type = Type::group_type_builder(root.name())
+				.with_fields(read_fields.iter().map(|(field, _)| field.clone()).collect())
+				.build()
+				.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+			let leaves = reader.metadata().file_metadata().schema_descr_ptr();
+
+			for index in 0..reader.num_row_groups() {
+				let metadata = reader.metadata().row_group(index);
+				let row_count = metadata.num_rows();
+				let may_match_all = predicates.iter().all(|predicate| {
+					let (field, data_type) = match fields
+						.iter()
+						.zip(&columns)
+						.find(|(_, column)| column.as_str() == predicate.column())
+					{
+						Some((Some((field, data_type)), _)) if field.is_primitive() => {
+							(field, data_type)
+						}
+						_ => return true,
+					};
+					(0..leaves.num_columns())
+						.find(|leaf| {
+							leaves.column(*leaf).path().parts() == [field.name().to_string()]
+						})
+						.map(|leaf| {
+							may_match(
+								predicate,
+								field,
+								data_type,
+								metadata.column(leaf).statistics(),
+								row_count,
+							)
+						})
+						.unwrap_or(true)
+				});
+				if !may_match_all {
+					key += row_count;
+					continue;
+				}
+
+				let row_group = reader
+					.get_row_group(index)
+					.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+				let rows = read_row_group(row_group.as_ref(), &read_type, &fields, row_count)?;
+				for row in rows {
+					if filter.confirm(&row) {
+						plane.push((Value::I64(key), Row(row)));
+					}
+					key += 1;
+				}
+			}
+		}
+
+		match projection {
+			Some(projection) => project(plane, &columns, projection),
+			None => Ok(plane),
+		}
+	}
+}
+
+type Fields = [Option<(Arc<Type>, ValueType)>];
+
+/// Rows of a row group, of the fields given, [None] being of columns the part doesn't have
+fn read_row_group(
+	row_group: &dyn RowGroupReader,
+	read_type: &Type,
+	fields: &Fields,
+	row_count: i64,
+) -> Result<Vec<Vec<Value>>> {
+	if fields.iter().all(Option::is_none) {
+		return Ok(vec![vec![Value::Null; fields.len()]; row_count as usize]);
+	}
+	row_group
+		.get_row_iter(Some(read_type.clone()))
+		.map_err(|error| WIPError::Debug(format!("{:?}", error)))?
+		.map(|row| {
+			let mut cells = row
+				.map_err(|error| WIPError::Debug(format!("{:?}", error)))?
+				.into_columns()
+				.into_iter();
+			Ok(fields
+				.iter()
+				.map(|field| match field {
+					Some((_, data_type)) => cells
+						.next()
+						.map(|(_, cell)| value_of(cell, data_type))
+						.unwrap_or(Value::Null),
+					None => Value::Null,
+				})
+				.collect())
+		})
+		.collect()
+}
@@ -0,0 +1,185 @@
+mod auto_increment;
+mod base;
+mod mutable;
+mod schema;
+mod statistics;
+
+use {
+	crate::{DBFull, Database, Result, Schema, WIPError},
+	parquet::file::{reader::FileReader, serialized_reader::SerializedFileReader},
+	schema::schema_of,
+	serde::Serialize,
+	std::{
+		fmt::Debug,
+		fs::File,
+		path::{Path, PathBuf},
+	},
+	thiserror::Error,
+};
+
+const EXTENSION: &str = "parquet";
+
+#[derive(Error, Serialize, Debug, PartialEq)]
+pub enum ParquetDatabaseError {
+	#[error("table already exists: {0}")]
+	TableExists(String),
+	#[error("column can't be written to: {0}")]
+	UnwritableColumn(String),
+}
+
+/// A directory of Parquet files, each table being either a `<table>.parquet` file
+/// or a `<table>` directory of `.parquet` files, the parts of the table, of the same schema.
+/// Rows are appended as new parts; tables made here are always directories.
+pub struct ParquetDatabase {
+	path: PathBuf,
+}
+
+impl DBFull for ParquetDatabase {}
+
+impl Database {
+	pub fn new_parquet(storage: ParquetDatabase) -> Self {
+		Self::new(Box::new(storage))
+	}
+}
+impl ParquetDatabase {
+	/// Creates the directory where there isn't one
+	pub fn new(path: &str) -> Result<Self> {
+		std::fs::create_dir_all(path).map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		Ok(Self {
+			path: PathBuf::from(path),
+		})
+	}
+
+	/// Whether a path is of an existing directory holding any tables of Parquet files
+	pub fn is_parquet_directory(path: &str) -> bool {
+		Self {
+			path: PathBuf::from(path),
+		}
+		.table_names()
+		.map(|table_names| !table_names.is_empty())
+		.unwrap_or(false)
+	}
+
+	fn file_path(&self, table_name: &str) -> PathBuf {
+		self.path.join(format!("{}.{}", table_name, EXTENSION))
+	}
+	fn directory_path(&self, table_name: &str) -> PathBuf {
+		self.path.join(table_name)
+	}
+
+	pub(crate) fn table_names(&self) -> Result<Vec<String>> {
+		let mut table_names: Vec<String> = std::fs::read_dir(&self.path)
+			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?
+			.filter_map(|entry| entry.ok())
+			.map(|entry| entry.path())
+			.filter_map(|path| {
+				if is_parquet_file(&path) {
+					path.file_stem()?.to_str().map(String::from)
+				} else if path.is_dir() && !parts_of(&path).ok()?.is_empty() {
+					path.file_name()?.to_str().map(String::from)
+				} else {
+					None
+				}
+			})
+			.collect();
+		table_names.sort();
+		table_names.dedup();
+		Ok(table_names)
+	}
+
+	/// Files of the table, in order; [None] where there's no such table
+	pub(crate) fn parts(&self, table_name: &str) -> Result<Option<Vec<PathBuf>>> {
+		let file_path = self.file_path(table_name);
+		if file_path.is_file() {
+			return Ok(Some(vec![file_path]));
+		}
+		let directory_path = self.directory_path(table_name);
+		if !directory_path.is_dir() {
+			return Ok(None);
+		}
+		let parts = parts_of(&directory_path)?;
+		Ok((!parts.is_empty()).then_some(parts))
+	}
+
+	/// The table's parts, and its schema as of its first part
+	pub(crate) fn table(&self, table_name: &str) -> Result<Option<(Vec<PathBuf>, Schema)>> {
+		let parts = match self.parts(table_name)? {
+			Some(parts) => parts,
+			None => return Ok(None),
+		};
+		let reader = open(&parts[0])?;
+		let schema = schema_of(
+			table_name,
+			reader
+				.metadata()
+				.file_metadata()
+				.schema_descr()
+				.root_schema(),
+		);
+		Ok(Some((parts, schema)))
+	}
+
+	/// Path of a new part of the table, a file of the table being moved into a directory of its own
+	pub(crate) fn new_part(&self, table_name: &str) -> Result<PathBuf> {
+		let directory_path = self.directory_path(table_name);
+		std::fs::create_dir_all(&directory_path)
+			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		let file_path = self.file_path(table_name);
+		if file_path.is_file() {
+			std::fs::rename(&file_path, part_path(&directory_path, 0))
+				.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		}
+		let parts = parts_of(&directory_path)?;
+		Ok((parts.len()..)
+			.map(|index| part_path(&directory_path, index))
+			.find(|path| !path.exists())
+			.expect("Unreachable: Parts are finite!"))
+	}
+
+	/// Removes the table's file, or directory
+	pub(crate) fn remove(&self, table_name: &str) -> Result<()> {
+		let file_path = self.file_path(table_name);
+		if file_path.is_file() {
+			std::fs::remove_file(&file_path)
+		} else {
+			std::fs::remove_dir_all(self.directory_path(table_name))
+		}
+		.map_err(|error| WIPError::Debug(format!("{:?}", error)).into())
+	}
+}
+
+fn is_parquet_file(path: &Path) -> bool {
+	path.is_file()
+		&& path
+			.extension()
+			.and_then(|extension| extension.to_str())
+			.map(|extension| extension.eq_ignore_ascii_case(EXTENSION))
+			.unwrap_or(false)
+}
+
+fn parts_of(directory_path: &Path) -> Result<Vec<PathBuf>> {
+	let mut parts: Vec<PathBuf> = std::fs::read_dir(directory_path)
+		.map_err(|error| WIPError::Debug(format!("{:?}", error)))?
+		.filter_map(|entry| entry.ok())
+		.map(|entry| entry.path())
+		.filter(|path| is_parquet_file(path))
+		.collect();
+	parts.sort();
+	Ok(parts)
+}
+
+fn part_path(directory_path: &Path, index: usize) -> PathBuf {
+	directory_path.join(format!("part-{:05}.{}", index, EXTENSION))
+}
+
+pub(crate) fn open(path: &Path) -> Result<SerializedFileReader<File>> {
+	let file = File::open(path).map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+	SerializedFileReader::new(file).map_err(|error| WIPError::Debug(format!("{:?}", error)).into())
+}
+
+/// Number of rows of every part of a table
+pub(crate) fn row_count(parts: &[PathBuf]) -> Result<i64> {
+	parts.iter().try_fold(0, |count, part| {
+		Ok(count + open(part)?.metadata().file_metadata().num_rows())
+	})
+}
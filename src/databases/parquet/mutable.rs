@@ -0,0 +1,227 @@
+use {
+	super::{
+		open,
+		schema::{parquet_schema, units_per_second, value_type, SECONDS_PER_DAY},
+		ParquetDatabase, ParquetDatabaseError,
+	},
+	crate::{Cast, DBMut, DatabaseError, Result, Row, Schema, Value, ValueType, WIPError},
+	async_trait::async_trait,
+	parquet::{
+		basic::{Compression, ConvertedType, Repetition},
+		column::writer::ColumnWriter,
+		data_type::ByteArray,
+		file::{properties::WriterProperties, reader::FileReader, writer::SerializedFileWriter},
+		schema::types::{Type, TypePtr},
+	},
+	std::{fs::File, path::Path, sync::Arc},
+};
+
+#[async_trait(?Send)]
+impl DBMut for ParquetDatabase {
+	/// Creates the table as a directory, its first part being of no rows
+	async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
+		if self.parts(&schema.table_name)?.is_some() {
+			return Err(ParquetDatabaseError::TableExists(schema.table_name.clone()).into());
+		}
+		let path = self.new_part(&schema.table_name)?;
+		write_part(&path, parquet_schema(schema)?, vec![])
+	}
+
+	/// Deletes the table's file, or directory of parts
+	async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
+		self.remove(table_name)
+	}
+
+	/// Rows are written as a new part, of the schema of the table's first part.
+	/// Tables with columns which can't be read, or are nested, can't be written to.
+	async fn insert_data(&mut self, table_name: &str, rows: Vec<Row>) -> Result<()> {
+		if rows.is_empty() {
+			return Ok(());
+		}
+		let parts = self
+			.parts(table_name)?
+			.ok_or(DatabaseError::TableNotFound)?;
+		let root = open(&parts[0])?
+			.metadata()
+			.file_metadata()
+			.schema_descr()
+			.root_schema_ptr();
+		root.get_fields()
+			.iter()
+			.try_for_each(|field| writable_type(field).map(|_| ()))?;
+		let path = self.new_part(table_name)?;
+		write_part(&path, root, rows)
+	}
+}
+
+/// Writes rows to a new file, as one row group; the file is removed where it can't be written
+fn write_part(path: &Path, root: TypePtr, rows: Vec<Row>) -> Result<()> {
+	let result = File::create(path)
+		.map_err(|error| WIPError::Debug(format!("{:?}", error)).into())
+		.and_then(|file| write_file(file, root, rows));
+	if result.is_err() {
+		std::fs::remove_file(path).ok();
+	}
+	result
+}
+
+fn write_file(file: File, root: TypePtr, rows: Vec<Row>) -> Result<()> {
+	let properties = WriterProperties::builder()
+		.set_compression(Compression::SNAPPY)
+		.build();
+	let mut writer = SerializedFileWriter::new(file, root.clone(), Arc::new(properties))
+		.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+	if !rows.is_empty() {
+		let fields = root.get_fields();
+		let mut columns: Vec<Vec<Value>> = vec![Vec::with_capacity(rows.len()); fields.len()];
+		rows.into_iter().for_each(|Row(values)| {
+			values
+				.into_iter()
+				.zip(columns.iter_mut())
+				.for_each(|(value, column)| column.push(value))
+		});
+
+		let mut row_group = writer
+			.next_row_group()
+			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		for (field, values) in fields.iter().zip(columns) {
+			let mut column = row_group
+				.next_column()
+				.map_err(|error| WIPError::Debug(format!("{:?}", error)))?
+				.ok_or_else(|| ParquetDatabaseError::UnwritableColumn(field.name().to_string()))?;
+			write_column(column.untyped(), field, values)?;
+			column
+				.close()
+				.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+		}
+		row_group
+			.close()
+			.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+	}
+	writer
+		.close()
+		.map(|_| ())
+		.map_err(|error| WIPError::Debug(format!("{:?}", error)).into())
+}
+
+/// Writes the values of a field, cast to the field's type; nulls are left to definition levels
+fn write_column(writer: &mut ColumnWriter, field: &Type, values: Vec<Value>) -> Result<()> {
+	let unwritable = || ParquetDatabaseError::UnwritableColumn(field.name().to_string());
+	let data_type = writable_type(field)?;
+	if !field.is_optional() && values.iter().any(|value| matches!(value, Value::Null)) {
+		return Err(unwritable().into());
+	}
+	let levels: Option<Vec<i16>> = field.is_optional().then(|| {
+		values
+			.iter()
+			.map(|value| !matches!(value, Value::Null) as i16)
+			.collect()
+	});
+	let levels = levels.as_deref();
+	let values = values
+		.into_iter()
+		.filter(|value| !matches!(value, Value::Null))
+		.collect();
+	let converted_type = field.get_basic_info().converted_type();
+
+	match (writer, data_type) {
+		(ColumnWriter::BoolColumnWriter(writer), _) => {
+			writer.write_batch(&cells(values, Value::cast)?, levels, None)
+		}
+		(ColumnWriter::Int32ColumnWriter(writer), ValueType::Timestamp) => writer.write_batch(
+			&cells(values, |value| {
+				i32::try_from(seconds(value)?.div_euclid(SECONDS_PER_DAY))
+					.map_err(|_| unwritable().into())
+			})?,
+			levels,
+			None,
+		),
+		(ColumnWriter::Int32ColumnWriter(writer), ValueType::I64) => writer.write_batch(
+			&cells(values, |value| {
+				let integer: i64 = value.cast()?;
+				match converted_type {
+					ConvertedType::UINT_8 | ConvertedType::UINT_16 | ConvertedType::UINT_32 => {
+						u32::try_from(integer).map(|integer| integer as i32).ok()
+					}
+					_ => i32::try_from(integer).ok(),
+				}
+				.ok_or_else(|| unwritable().into())
+			})?,
+			levels,
+			None,
+		),
+		(ColumnWriter::Int64ColumnWriter(writer), ValueType::Timestamp) => {
+			let units = units_per_second(field).ok_or_else(unwritable)?;
+			writer.write_batch(
+				&cells(values, |value| Ok(seconds(value)? * units))?,
+				levels,
+				None,
+			)
+		}
+		(ColumnWriter::Int64ColumnWriter(writer), ValueType::U64) => writer.write_batch(
+			&cells(values, |value| {
+				value.cast().map(|integer: u64| integer as i64)
+			})?,
+			levels,
+			None,
+		),
+		(ColumnWriter::Int64ColumnWriter(writer), ValueType::I64) => {
+			writer.write_batch(&cells(values, Value::cast)?, levels, None)
+		}
+		(ColumnWriter::FloatColumnWriter(writer), ValueType::F64) => writer.write_batch(
+			&cells(values, |value| value.cast().map(|float: f64| float as f32))?,
+			levels,
+			None,
+		),
+		(ColumnWriter::DoubleColumnWriter(writer), ValueType::F64) => {
+			writer.write_batch(&cells(values, Value::cast)?, levels, None)
+		}
+		(ColumnWriter::ByteArrayColumnWriter(writer), ValueType::Str) => writer.write_batch(
+			&cells(values, |value| {
+				value
+					.cast()
+					.map(|text: String| ByteArray::from(text.into_bytes()))
+			})?,
+			levels,
+			None,
+		),
+		(ColumnWriter::ByteArrayColumnWriter(writer), ValueType::Bytes) => writer.write_batch(
+			&cells(values, |value| {
+				value.cast().map(|bytes: Vec<u8>| ByteArray::from(bytes))
+			})?,
+			levels,
+			None,
+		),
+		_ => return Err(unwritable().into()),
+	}
+	.map(|_| ())
+	.map_err(|error| WIPError::Debug(format!("{:?}", error)).into())
+}
+
+/// The type of a field's values, where they can be written
+fn writable_type(field: &Type) -> Result<ValueType> {
+	match value_type(field) {
+		Some(data_type)
+			if field.is_primitive()
+				&& field.get_basic_info().repetition() != Repetition::REPEATED =>
+		{
+			Ok(data_type)
+		}
+		_ => Err(ParquetDatabaseError::UnwritableColumn(field.name().to_string()).into()),
+	}
+}
+
+fn cells<T>(values: Vec<Value>, convert: impl Fn(Value) -> Result<T>) -> Result<Vec<T>> {
+	values.into_iter().map(convert).collect()
+}
+
+/// Seconds of a timestamp, or of text of one
+fn seconds(value: Value) -> Result<i64> {
+	match value {
+		Value::Timestamp(seconds) => Ok(seconds),
+		value => match value.cast_valuetype(&ValueType::Timestamp)? {
+			Value::Timestamp(seconds) => Ok(seconds),
+			value => value.cast(),
+		},
+	}
+}
@@ -0,0 +1,200 @@
+use {
+	crate::{Column, Result, Schema, Value, ValueType, WIPError},
+	parquet::{
+		basic::{ConvertedType, LogicalType, Repetition, TimeUnit, Type as PhysicalType},
+		format::MilliSeconds,
+		record::Field,
+		schema::types::{Type, TypePtr},
+	},
+	std::sync::Arc,
+};
+
+pub(crate) const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// The type of a field's values, by its logical type where it has one.
+/// Fields of nested groups, lists and maps are read as text.
+/// [None] where values can't be read, as is so of times of day and intervals.
+pub(crate) fn value_type(field: &Type) -> Option<ValueType> {
+	if !field.is_primitive() {
+		return field
+			.get_fields()
+			.iter()
+			.all(|field| value_type(field).is_some())
+			.then_some(ValueType::Str);
+	}
+	let info = field.get_basic_info();
+	let logical_type = info.logical_type();
+	if matches!(logical_type, Some(LogicalType::Time { .. })) {
+		return None;
+	}
+	let value_type = match (field.get_physical_type(), info.converted_type()) {
+		(_, ConvertedType::DECIMAL) => ValueType::F64,
+		(PhysicalType::BOOLEAN, _) => ValueType::Bool,
+		(PhysicalType::INT32, ConvertedType::DATE) => ValueType::Timestamp,
+		(
+			PhysicalType::INT32,
+			ConvertedType::NONE
+			| ConvertedType::INT_8
+			| ConvertedType::INT_16
+			| ConvertedType::INT_32
+			| ConvertedType::UINT_8
+			| ConvertedType::UINT_16
+			| ConvertedType::UINT_32,
+		) => ValueType::I64,
+		(PhysicalType::INT64, ConvertedType::UINT_64) => ValueType::U64,
+		(
+			PhysicalType::INT64,
+			ConvertedType::TIMESTAMP_MILLIS | ConvertedType::TIMESTAMP_MICROS,
+		) => ValueType::Timestamp,
+		(PhysicalType::INT64, ConvertedType::NONE)
+			if matches!(logical_type, Some(LogicalType::Timestamp { .. })) =>
+		{
+			ValueType::Timestamp
+		}
+		(PhysicalType::INT64, ConvertedType::NONE | ConvertedType::INT_64) => ValueType::I64,
+		(PhysicalType::INT96, _) => ValueType::Timestamp,
+		(PhysicalType::FLOAT | PhysicalType::DOUBLE, _) => ValueType::F64,
+		(
+			PhysicalType::BYTE_ARRAY,
+			ConvertedType::UTF8 | ConvertedType::ENUM | ConvertedType::JSON,
+		) => ValueType::Str,
+		(PhysicalType::BYTE_ARRAY, ConvertedType::NONE | ConvertedType::BSON) => ValueType::Bytes,
+		(PhysicalType::FIXED_LEN_BYTE_ARRAY, ConvertedType::NONE)
+			if matches!(logical_type, Some(LogicalType::Float16)) =>
+		{
+			ValueType::F64
+		}
+		(PhysicalType::FIXED_LEN_BYTE_ARRAY, ConvertedType::NONE) => ValueType::Bytes,
+		_ => return None,
+	};
+	Some(match info.repetition() {
+		Repetition::REPEATED => ValueType::Str,
+		_ => value_type,
+	})
+}
+
+/// Units of a timestamp field's values to a second
+pub(crate) fn units_per_second(field: &Type) -> Option<i64> {
+	let info = field.get_basic_info();
+	match (info.converted_type(), info.logical_type()) {
+		(ConvertedType::TIMESTAMP_MILLIS, _) => Some(1_000),
+		(ConvertedType::TIMESTAMP_MICROS, _) => Some(1_000_000),
+		(_, Some(LogicalType::Timestamp { unit, .. })) => Some(match unit {
+			TimeUnit::MILLIS(_) => 1_000,
+			TimeUnit::MICROS(_) => 1_000_000,
+			TimeUnit::NANOS(_) => 1_000_000_000,
+		}),
+		_ => None,
+	}
+}
+
+/// Those top level fields which can be read, with the types of their values
+pub(crate) fn readable_fields(root: &Type) -> Vec<(TypePtr, ValueType)> {
+	root.get_fields()
+		.iter()
+		.filter_map(|field| Some((field.clone(), value_type(field)?)))
+		.collect()
+}
+
+/// Columns are of the top level fields which can be read, see [value_type]
+pub(crate) fn schema_of(table_name: &str, root: &Type) -> Schema {
+	let column_defs = readable_fields(root)
+		.into_iter()
+		.map(|(field, data_type)| Column {
+			name: field.name().to_string(),
+			data_type,
+			is_nullable: field.is_optional(),
+			..Column::default()
+		})
+		.collect();
+	Schema {
+		table_name: table_name.to_string(),
+		column_defs,
+		indexes: vec![],
+	}
+}
+
+/// A Parquet schema of the table's columns; timestamps being kept to the millisecond,
+/// and text, of columns of any type, as UTF-8
+pub(crate) fn parquet_schema(schema: &Schema) -> Result<TypePtr> {
+	let fields = schema
+		.column_defs
+		.iter()
+		.map(|column_def| {
+			let (physical_type, logical_type) = match column_def.data_type {
+				ValueType::Bool => (PhysicalType::BOOLEAN, None),
+				ValueType::I64 => (PhysicalType::INT64, None),
+				ValueType::U64 => (
+					PhysicalType::INT64,
+					Some(LogicalType::Integer {
+						bit_width: 64,
+						is_signed: false,
+					}),
+				),
+				ValueType::F64 => (PhysicalType::DOUBLE, None),
+				ValueType::Timestamp => (
+					PhysicalType::INT64,
+					Some(LogicalType::Timestamp {
+						is_adjusted_to_u_t_c: true,
+						unit: TimeUnit::MILLIS(MilliSeconds::new()),
+					}),
+				),
+				ValueType::Bytes => (PhysicalType::BYTE_ARRAY, None),
+				ValueType::Str | ValueType::Any | ValueType::Array => {
+					(PhysicalType::BYTE_ARRAY, Some(LogicalType::String))
+				}
+			};
+			Type::primitive_type_builder(&column_def.name, physical_type)
+				.with_repetition(if column_def.is_nullable {
+					Repetition::OPTIONAL
+				} else {
+					Repetition::REQUIRED
+				})
+				.with_logical_type(logical_type)
+				.build()
+				.map(Arc::new)
+		})
+		.collect::<parquet::errors::Result<Vec<TypePtr>>>()
+		.map_err(|error| WIPError::Debug(format!("{:?}", error)))?;
+	Type::group_type_builder("schema")
+		.with_fields(fields)
+		.build()
+		.map(Arc::new)
+		.map_err(|error| WIPError::Debug(format!("{:?}", error)).into())
+}
+
+/// A value, as read of a field of values of the type
+pub(crate) fn value_of(field: Field, data_type: &ValueType) -> Value {
+	match field {
+		Field::Null => Value::Null,
+		Field::Bool(value) => Value::Bool(value),
+		Field::Byte(value) => Value::I64(value as i64),
+		Field::Short(value) => Value::I64(value as i64),
+		Field::Int(value) => Value::I64(value as i64),
+		Field::UByte(value) => Value::I64(value as i64),
+		Field::UShort(value) => Value::I64(value as i64),
+		Field::UInt(value) => Value::I64(value as i64),
+		Field::ULong(value) => Value::U64(value),
+		// Timestamps of nanoseconds are only known as such by their logical type
+		Field::Long(value) if matches!(data_type, ValueType::Timestamp) => {
+			Value::Timestamp(value.div_euclid(1_000_000_000))
+		}
+		Field::Long(value) => Value::I64(value),
+		Field::Float16(value) => Value::F64(value.to_f64()),
+		Field::Float(value) => Value::F64(value as f64),
+		Field::Double(value) => Value::F64(value),
+		Field::Decimal(_) => field
+			.to_string()
+			.parse()
+			.map(Value::F64)
+			.unwrap_or(Value::Null),
+		Field::Str(value) => Value::Str(value),
+		Field::Bytes(value) => Value::Bytes(value.data().to_vec()),
+		Field::Date(days) => Value::Timestamp(days as i64 * SECONDS_PER_DAY),
+		Field::TimestampMillis(value) => Value::Timestamp(value.div_euclid(1_000)),
+		Field::TimestampMicros(value) => Value::Timestamp(value.div_euclid(1_000_000)),
+		Field::Group(_) | Field::ListInternal(_) | Field::MapInternal(_) => {
+			Value::Str(field.to_string())
+		}
+	}
+}
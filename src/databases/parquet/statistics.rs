@@ -0,0 +1,124 @@
+use {
+	super::schema::{units_per_second, SECONDS_PER_DAY},
+	crate::{Comparison, Predicate, Value, ValueType},
+	parquet::{
+		basic::{ConvertedType, Repetition},
+		file::statistics::Statistics,
+		schema::types::Type,
+	},
+};
+
+/// Whether any row of a row group may meet the predicate, as far as the statistics of its column tell.
+/// Where they don't tell, as where there are none, rows may.
+pub(crate) fn may_match(
+	predicate: &Predicate,
+	field: &Type,
+	data_type: &ValueType,
+	statistics: Option<&Statistics>,
+	row_count: i64,
+) -> bool {
+	let statistics = match statistics {
+		// Statistics of lists are of their elements
+		Some(_) if field.get_basic_info().repetition() == Repetition::REPEATED => return true,
+		Some(statistics) => statistics,
+		None => return true,
+	};
+	let null_count = statistics.null_count_opt();
+	let all_null = null_count == Some(row_count as u64);
+	let (min, max) = match predicate {
+		Predicate::IsNull(_) => return null_count != Some(0),
+		Predicate::IsNotNull(_) => return !all_null,
+		Predicate::Like(..) => return true,
+		// NULL <> value is true, so a group of any NULLs may match
+		Predicate::Compare(_, Comparison::NotEq, _) if null_count != Some(0) => return true,
+		_ => match bounds(statistics, field, data_type) {
+			Some(bounds) => bounds,
+			// No other comparison is true of null
+			None => return !all_null,
+		},
+	};
+	let within = |value: &Value| !(is(Value::lt, value, &min) || is(Value::gt, value, &max));
+	match predicate {
+		Predicate::Compare(_, comparison, value) => match comparison {
+			Comparison::Eq => within(value),
+			Comparison::NotEq => !(is(Value::eq, &min, value) && is(Value::eq, &max, value)),
+			Comparison::Lt => !is(Value::gt_eq, &min, value),
+			Comparison::LtEq => !is(Value::gt, &min, value),
+			Comparison::Gt => !is(Value::lt_eq, &max, value),
+			Comparison::GtEq => !is(Value::lt, &max, value),
+		},
+		Predicate::In(_, values) => values.iter().any(within),
+		_ => true,
+	}
+}
+
+/// Whether a comparison is certainly true
+fn is(operator: fn(Value, Value) -> crate::Result<Value>, left: &Value, right: &Value) -> bool {
+	matches!(operator(left.clone(), right.clone()), Ok(Value::Bool(true)))
+}
+
+/// The least and greatest values of a column's chunk, as values of the column's type;
+/// [None] where they aren't known, or are of types, such as decimals, of which they can't simply be taken
+fn bounds(statistics: &Statistics, field: &Type, data_type: &ValueType) -> Option<(Value, Value)> {
+	let converted_type = field.get_basic_info().converted_type();
+	let (min, max) = match (statistics, data_type) {
+		(Statistics::Boolean(statistics), ValueType::Bool) => (
+			Value::Bool(*statistics.min_opt()?),
+			Value::Bool(*statistics.max_opt()?),
+		),
+		(Statistics::Int32(statistics), ValueType::I64) => {
+			let integer = |value: i32| match converted_type {
+				// Unsigned integers are ordered as such
+				ConvertedType::UINT_8 | ConvertedType::UINT_16 | ConvertedType::UINT_32 => {
+					Value::I64(value as u32 as i64)
+				}
+				_ => Value::I64(value as i64),
+			};
+			(
+				integer(*statistics.min_opt()?),
+				integer(*statistics.max_opt()?),
+			)
+		}
+		(Statistics::Int32(statistics), ValueType::Timestamp) => {
+			let date = |days: i32| Value::Timestamp(days as i64 * SECONDS_PER_DAY);
+			(date(*statistics.min_opt()?), date(*statistics.max_opt()?))
+		}
+		(Statistics::Int64(statistics), ValueType::I64) => (
+			Value::I64(*statistics.min_opt()?),
+			Value::I64(*statistics.max_opt()?),
+		),
+		(Statistics::Int64(statistics), ValueType::U64) => (
+			Value::U64(*statistics.min_opt()? as u64),
+			Value::U64(*statistics.max_opt()? as u64),
+		),
+		(Statistics::Int64(statistics), ValueType::Timestamp) => {
+			let units = units_per_second(field)?;
+			(
+				Value::Timestamp(statistics.min_opt()?.div_euclid(units)),
+				Value::Timestamp(statistics.max_opt()?.div_euclid(units)),
+			)
+		}
+		(Statistics::Float(statistics), ValueType::F64)
+			if converted_type == ConvertedType::NONE =>
+		{
+			(
+				Value::F64(*statistics.min_opt()? as f64),
+				Value::F64(*statistics.max_opt()? as f64),
+			)
+		}
+		(Statistics::Double(statistics), ValueType::F64) => (
+			Value::F64(*statistics.min_opt()?),
+			Value::F64(*statistics.max_opt()?),
+		),
+		(Statistics::ByteArray(statistics), ValueType::Str) => (
+			Value::Str(statistics.min_opt()?.as_utf8().ok()?.to_string()),
+			Value::Str(statistics.max_opt()?.as_utf8().ok()?.to_string()),
+		),
+		_ => return None,
+	};
+	// Bounds of NaN bound nothing
+	match (&min, &max) {
+		(Value::F64(min), Value::F64(max)) if min.is_nan() || max.is_nan() => None,
+		_ => Some((min, max)),
+	}
+}
@@ -2,6 +2,7 @@ use crate::DatabaseError;
 use {
 	crate::{
 		Cast, Column, DBBase, Plane, Result, Row, Schema, SheetDatabase, SheetDatabaseError, Value,
+		ValueType,
 	},
 	async_trait::async_trait,
 	std::convert::TryFrom,
@@ -53,12 +54,7 @@ impl DBBase for SheetDatabase {
 						.into_iter()
 						.zip(&column_defs)
 						.map(|(cell, Column { data_type, .. })| {
-							Value::Str(
-								cell.map(|cell| cell.get_value().to_string())
-									.unwrap_or_default(),
-							)
-							.cast_valuetype(data_type)
-							.unwrap_or(Value::Null)
+							cell_value(cell.as_ref(), data_type)
 						})
 						.collect()),
 				)
@@ -82,6 +78,16 @@ impl TryFrom<Cell> for Value {
 	}
 }
 
+/// Empty cells are NULL, unlike empty text
+pub(super) fn cell_value(cell: Option<&Cell>, data_type: &ValueType) -> Value {
+	match cell {
+		Some(cell) if !cell.get_data_type().is_empty() => Value::Str(cell.get_value().to_string())
+			.cast_valuetype(data_type)
+			.unwrap_or(Value::Null),
+		_ => Value::Null,
+	}
+}
+
 fn schema_from_sheet(sheet: &Worksheet) -> Result<Schema> {
 	let mut column_defs: Vec<(_, Column)> = sheet
 		.get_comments()
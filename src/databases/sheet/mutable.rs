@@ -1,8 +1,11 @@
-use umya_spreadsheet::{Border, Comment, PatternValues, RichText, Style, TextElement};
+use umya_spreadsheet::{
+	Border, Cell, Comment, PatternValues, RichText, Style, TextElement, Worksheet,
+};
 use {
+	super::base::cell_value,
 	crate::{
-		Cast, DBMut, DatabaseError, Result, Row, Schema, SchemaChange, SchemaDiff, SheetDatabase,
-		SheetDatabaseError, Value,
+		Cast, Column, DBBase, DBMut, DatabaseError, Result, Row, Schema, SchemaChange, SchemaDiff,
+		SheetDatabase, SheetDatabaseError, Value,
 	},
 	async_trait::async_trait,
 };
@@ -10,24 +13,6 @@ use {
 #[async_trait(?Send)]
 impl DBMut for SheetDatabase {
 	async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
-		let mut style = Style::default();
-		style
-			.get_fill_mut()
-			.get_pattern_fill_mut()
-			.set_pattern_type(PatternValues::Gray125);
-		style
-			.get_borders_mut()
-			.get_bottom_mut()
-			.set_border_style(Border::BORDER_MEDIUM);
-		style
-			.get_borders_mut()
-			.get_left_mut()
-			.set_border_style(Border::BORDER_THIN);
-		style
-			.get_borders_mut()
-			.get_right_mut()
-			.set_border_style(Border::BORDER_THIN);
-
 		let Schema {
 			column_defs,
 			table_name: sheet_name,
@@ -40,44 +25,25 @@ impl DBMut for SheetDatabase {
 		column_defs
 			.iter()
 			.enumerate()
-			.try_for_each::<_, Result<_>>(|(index, column_def)| {
-				let col = (index as u32) + 1;
-				let row = 1;
-				sheet
-					.get_cell_by_column_and_row_mut(&col, &row)
-					.set_value(&column_def.name)
-					.set_style(style.clone());
-				let mut comment_text_element = TextElement::default();
-				comment_text_element.set_text(
-					serde_yaml::to_string(&column_def)
-						.map_err(|_| SheetDatabaseError::FailedColumnParse)?,
-				);
-				let mut comment_text = RichText::default();
-				comment_text.add_rich_text_elements(comment_text_element);
-				let mut comment = Comment::default();
-				comment
-					.set_text(comment_text)
-					.get_coordinate_mut()
-					.set_col_num(col)
-					.set_row_num(row);
-				sheet.add_comments(comment);
-				Ok(())
+			.try_for_each(|(index, column_def)| {
+				set_header(sheet, (index as u32) + 1, column_def)
 			})?;
 		self.save()
 	}
 	async fn insert_data(&mut self, sheet_name: &str, rows: Vec<Row>) -> Result<()> {
 		let sheet = self.get_sheet_mut(sheet_name)?;
-		let row_init = sheet.get_row_dimensions().len() + 1; // TODO: Not this
+		let row_init = sheet.get_highest_row() as usize + 1;
 		rows.into_iter()
 			.enumerate()
 			.for_each(|(row_num, Row(row))| {
 				row.into_iter().enumerate().for_each(|(col_num, cell)| {
-					sheet
-						.get_cell_by_column_and_row_mut(
+					set_value(
+						sheet.get_cell_by_column_and_row_mut(
 							&(col_num as u32 + 1),
 							&((row_num + row_init) as u32),
-						)
-						.set_value(cell);
+						),
+						cell,
+					);
 				})
 			});
 		self.save()
@@ -96,39 +62,168 @@ impl DBMut for SheetDatabase {
 			.try_for_each::<_, Result<()>>(|(key, Row(row))| {
 				let row_num: i64 = key.cast()?;
 				row.into_iter().enumerate().for_each(|(col_num, cell)| {
-					sheet
-						.get_cell_by_column_and_row_mut(&(col_num as u32 + 1), &(row_num as u32))
-						.set_value(cell);
+					set_value(
+						sheet.get_cell_by_column_and_row_mut(
+							&(col_num as u32 + 1),
+							&(row_num as u32),
+						),
+						cell,
+					);
 				});
 				Ok(())
 			})?;
 		self.save()
 	}
 
+	/// Rows are removed from the last, so that those before keep their place, and so their keys, until removed
 	async fn delete_data(&mut self, sheet_name: &str, rows: Vec<Value>) -> Result<()> {
 		let sheet = self.get_sheet_mut(sheet_name)?;
-		rows.into_iter().try_for_each::<_, Result<()>>(|key| {
-			let row_num: u64 = key.cast()?;
-			sheet.remove_row(&((row_num as u32) + 1), &1);
-			Ok(())
-		})?;
+		let mut row_nums = rows
+			.into_iter()
+			.map(Cast::<u64>::cast)
+			.collect::<Result<Vec<u64>>>()?;
+		row_nums.sort_unstable_by(|left, right| right.cmp(left));
+		row_nums.dedup();
+		row_nums.into_iter().for_each(|row_num| {
+			let row_num = row_num as u32;
+			// Umya's own `remove_row` leaves the row's cells, to be moved onto those before
+			sheet
+				.get_collection_to_hashmap_mut()
+				.retain(|(row, _col), _| row != &row_num);
+			sheet.remove_row(&row_num, &1);
+		});
 		self.save()
 	}
 
+	/// Changes are made to a copy of the sheet, which replaces it once all are made
 	async fn alter_table(&mut self, sheet_name: &str, schema_diff: SchemaDiff) -> Result<()> {
-		let changes = schema_diff.get_changes();
-		let sheet = self.get_sheet_mut(sheet_name)?;
-		for change in changes.into_iter() {
+		let mut schema = self
+			.fetch_schema(sheet_name)
+			.await?
+			.ok_or(DatabaseError::TableNotFound)?;
+		let mut sheet = self.get_sheet_mut(sheet_name)?.clone();
+		for change in schema_diff.changes().iter() {
 			use SchemaChange::*;
+			let column_defs = &schema.column_defs;
+			let column_col = |column_name: &str| -> Result<u32> {
+				column_defs
+					.iter()
+					.position(|column_def| column_def.name == column_name)
+					.map(|index| (index as u32) + 1)
+					.ok_or_else(|| DatabaseError::ColumnNotFound(column_name.to_string()).into())
+			};
 			match change {
 				RenameTable(new_name) => {
 					sheet.set_name(new_name);
 				}
-				_ => return Err(DatabaseError::Unimplemented.into()),
-				// TODO
+				ColumnUpdate(column_name, column_def) => {
+					let col = column_col(column_name)?;
+					let old_type = &column_defs[(col - 1) as usize].data_type;
+					if old_type != &column_def.data_type {
+						for row in 2..=sheet.get_highest_row() {
+							let cell = sheet.get_cell_by_column_and_row_mut(&col, &row);
+							let value = cell_value(Some(&*cell), old_type)
+								.cast_valuetype(&column_def.data_type)?;
+							set_value(cell, value);
+						}
+					}
+					set_header(&mut sheet, col, column_def)?;
+				}
+				ColumnAdd(column_def) => {
+					// Only NULL may yet be filled, which is an empty cell
+					if column_def.default.is_some() || !column_def.is_nullable {
+						return Err(DatabaseError::Unimplemented.into()); // TODO: #72
+					}
+					set_header(&mut sheet, (column_defs.len() as u32) + 1, column_def)?;
+				}
+				ColumnRemove(column_name) => {
+					// Later columns are moved along, by value, then the last column is cleared.
+					// (Umya's own `remove_column` leaves cells in place)
+					let last = column_defs.len() as u32;
+					for col in column_col(column_name)?..last {
+						for row in 2..=sheet.get_highest_row() {
+							let value = sheet
+								.get_cell_by_column_and_row(&(col + 1), &row)
+								.map(|cell| cell.get_value().to_string())
+								.unwrap_or_default();
+							sheet
+								.get_cell_by_column_and_row_mut(&col, &row)
+								.set_value(value);
+						}
+						set_header(&mut sheet, col, &column_defs[col as usize])?;
+					}
+					sheet
+						.get_collection_to_hashmap_mut()
+						.retain(|(_row, col), _| col != &last);
+					sheet
+						.get_comments_mut()
+						.retain(|comment| comment.get_coordinate().get_col_num() != &last);
+				}
+				// Sheets don't keep indexes
+				IndexAdd(..) | IndexUpdate(..) | IndexRemove(..) => continue,
 			};
+			change.apply(&mut schema)?;
 		}
 
+		*self.get_sheet_mut(sheet_name)? = sheet;
 		self.save()
 	}
 }
+
+/// Text, and bytes as base64, are kept as text, and NULL as an empty cell, rather than as Umya would guess from a string
+fn set_value(cell: &mut Cell, value: Value) {
+	match value {
+		Value::Null => cell.set_value("NULL"),
+		Value::Str(text) => cell.set_value_from_string(text),
+		Value::Bytes(bytes) => cell.set_value_from_string(base64::encode(bytes)),
+		value => cell.set_value(value),
+	};
+}
+
+fn header_style() -> Style {
+	let mut style = Style::default();
+	style
+		.get_fill_mut()
+		.get_pattern_fill_mut()
+		.set_pattern_type(PatternValues::Gray125);
+	style
+		.get_borders_mut()
+		.get_bottom_mut()
+		.set_border_style(Border::BORDER_MEDIUM);
+	style
+		.get_borders_mut()
+		.get_left_mut()
+		.set_border_style(Border::BORDER_THIN);
+	style
+		.get_borders_mut()
+		.get_right_mut()
+		.set_border_style(Border::BORDER_THIN);
+	style
+}
+
+/// Writes the name of a column to its header cell, with its definition in the cell's comment
+fn set_header(sheet: &mut Worksheet, col: u32, column_def: &Column) -> Result<()> {
+	let row = 1;
+	sheet
+		.get_cell_by_column_and_row_mut(&col, &row)
+		.set_value(&column_def.name)
+		.set_style(header_style());
+	let mut comment_text_element = TextElement::default();
+	comment_text_element.set_text(
+		serde_yaml::to_string(&column_def).map_err(|_| SheetDatabaseError::FailedColumnParse)?,
+	);
+	let mut comment_text = RichText::default();
+	comment_text.add_rich_text_elements(comment_text_element);
+	let mut comment = Comment::default();
+	comment
+		.set_text(comment_text)
+		.get_coordinate_mut()
+		.set_col_num(col)
+		.set_row_num(row);
+	sheet.get_comments_mut().retain(|comment| {
+		let coordinate = comment.get_coordinate();
+		!(coordinate.get_col_num() == &col && coordinate.get_row_num() == &row)
+	});
+	sheet.add_comments(comment);
+	Ok(())
+}
@@ -5,12 +5,13 @@ use {
 		SledDatabase,
 	},
 	crate::{
-		join_iters, DBBase, IndexFilter, JoinType, NullOrd, Plane, Result, Row, Schema, Value,
+		join_iters, project, Comparison, DBBase, DatabaseError, IndexFilter, JoinType, NullOrd,
+		Plane, Predicate, Result, Row, RowFilter, Schema, Value, ValueType,
 	},
 	async_trait::async_trait,
 	rayon::slice::ParallelSliceMut,
 	sled::IVec,
-	std::{cmp::Ordering, convert::Into},
+	std::{cmp::Ordering, collections::BTreeSet, convert::Into},
 };
 
 #[async_trait(?Send)]
@@ -43,17 +44,67 @@ impl DBBase for SledDatabase {
 			.collect::<Result<Vec<(Value, Row)>>>()
 	}
 
+	/// Where a predicate requires an indexed column to equal some values, only rows under those keys of the index are read.
+	/// Otherwise rows are filtered as they are deserialised.
+	async fn scan_data_filtered(
+		&self,
+		table_name: &str,
+		predicates: &[Predicate],
+		projection: Option<&[String]>,
+	) -> Result<Plane> {
+		let schema = fetch_schema(&self.tree, table_name)?
+			.1
+			.ok_or(DatabaseError::TableNotFound)?;
+		let columns: Vec<String> = schema
+			.column_defs
+			.iter()
+			.map(|column| column.name.clone())
+			.collect();
+		let filter = RowFilter::new(predicates, &columns);
+
+		let row_keys = predicates
+			.iter()
+			.find_map(|predicate| self.index_keys(table_name, &schema, predicate).transpose())
+			.transpose()?;
+		let items: Box<dyn Iterator<Item = sled::Result<(IVec, IVec)>>> = match row_keys {
+			Some(row_keys) => Box::new(row_keys.into_iter().filter_map(|row_key| {
+				self.tree
+					.get(&row_key)
+					.transpose()
+					.map(|row| row.map(|row| (row_key, row)))
+			})),
+			None => Box::new(
+				self.tree
+					.scan_prefix(format!("data/{}/", table_name).as_bytes()),
+			),
+		};
+		let plane = items
+			.map(|item| {
+				let (key, value) = item.map_err(err_into)?;
+				let row: Row = bincode::deserialize(&value).map_err(err_into)?;
+				Ok(filter.confirm(&row.0).then(|| ((&key).into(), row)))
+			})
+			.filter_map(Result::transpose)
+			.collect::<Result<Vec<(Value, Row)>>>()?;
+		match projection {
+			Some(projection) => project(plane, &columns, projection),
+			None => Ok(plane),
+		}
+	}
+
 	async fn scan_data_indexed(
 		&self,
 		table_name: &str,
 		index_filter: IndexFilter,
 	) -> Result<Plane> {
 		let index_results = self.scan_index(table_name, index_filter).await?;
-		let row_results = index_results.into_iter().map(|pk| {
+		// Index entries without a row are passed over
+		let row_results = index_results.into_iter().filter_map(|pk| {
 			if let Value::Bytes(pk) = pk {
 				self.tree
 					.get(&pk)
-					.map(|row| (pk, row.unwrap() /*TODO: Handle!*/))
+					.transpose()
+					.map(|row| row.map(|row| (pk, row)))
 			} else {
 				unreachable!();
 			}
@@ -113,3 +164,53 @@ impl DBBase for SledDatabase {
 		}
 	}
 }
+
+impl SledDatabase {
+	/// Keys of the rows which an equality predicate could be true of, found by the index of its column, where there is one
+	fn index_keys(
+		&self,
+		table_name: &str,
+		schema: &Schema,
+		predicate: &Predicate,
+	) -> Result<Option<BTreeSet<IVec>>> {
+		let (column, values) = match predicate {
+			Predicate::Compare(column, Comparison::Eq, value) => (column, vec![value.clone()]),
+			Predicate::In(column, values) => (column, values.clone()),
+			_ => return Ok(None),
+		};
+		let data_type = match schema
+			.column_defs
+			.iter()
+			.find(|column_def| &column_def.name == column)
+		{
+			Some(column_def) => &column_def.data_type,
+			None => return Ok(None),
+		};
+		let index = match schema.indexes.iter().find(|index| &index.column == column) {
+			Some(index) => index,
+			None => return Ok(None),
+		};
+		// Only where a value would be stored, in the index, as given
+		if !matches!(
+			data_type,
+			ValueType::Bool | ValueType::U64 | ValueType::I64 | ValueType::Str
+		) || values
+			.iter()
+			.any(|value| &ValueType::from(value) != data_type)
+		{
+			return Ok(None);
+		}
+		let prefix = index_prefix(table_name, &index.name);
+		values
+			.iter()
+			.map(|value| {
+				let value_prefix = [&indexed_key(&prefix, value)?[..], &[0x00]].concat();
+				self.tree
+					.scan_prefix(value_prefix)
+					.map(|item| item.map(|(_, row_key)| row_key).map_err(err_into))
+					.collect::<Result<Vec<IVec>>>()
+			})
+			.collect::<Result<Vec<Vec<IVec>>>>()
+			.map(|row_keys| Some(row_keys.into_iter().flatten().collect()))
+	}
+}
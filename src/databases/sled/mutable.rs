@@ -1,8 +1,7 @@
 use {
 	super::{err_into, fetch_schema, SledDatabase},
 	crate::{
-		BigEndian, Column, DBMut, DatabaseError, Result, Row, Schema, SchemaChange, SchemaDiff,
-		Value,
+		BigEndian, DBMut, DatabaseError, Result, Row, Schema, SchemaChange, SchemaDiff, Value,
 	},
 	async_trait::async_trait,
 	rayon::prelude::*,
@@ -136,98 +135,85 @@ impl DBMut for SledDatabase {
 		self.tree.apply_batch(batch).map_err(err_into)
 	}
 
+	/// All changes are written in one batch, so are made together or not at all
 	async fn alter_table(&mut self, table_name: &str, schema_diff: SchemaDiff) -> Result<()> {
-		let changes = schema_diff.get_changes();
-		for change in changes.into_iter() {
-			use SchemaChange::*;
-			match change {
-				RenameTable(new_name) => self.rename_table(table_name, new_name),
-				ColumnAdd(column_def) => self.add_column(table_name, column_def),
-				ColumnRemove(index) => self.remove_column(table_name, index),
-				IndexRemove(index) => {
-					let schema = fetch_schema(&self.tree, table_name)?
-						.1
-						.ok_or(DatabaseError::TableNotFound)?;
-					if let Some(index) = schema.indexes.get(index) {
-						self.remove_index(table_name, &index.name)
-					} else {
-						Ok(())
+		let (schema_key, schema) = fetch_schema(&self.tree, table_name)?;
+		let schema = schema.ok_or(DatabaseError::TableNotFound)?;
+		let new_schema = schema_diff.merge(schema.clone())?;
+		let rewriter = schema_diff.row_rewriter(&schema)?;
+		let new_table_name = new_schema.table_name.as_str();
+		let renamed = new_table_name != table_name;
+
+		let mut batch = sled::Batch::default();
+
+		if rewriter.is_some() || renamed {
+			let prefix = format!("data/{}/", table_name);
+			let new_prefix = format!("data/{}/", new_table_name);
+			for item in self.tree.scan_prefix(prefix.as_bytes()) {
+				let (key, row) = item?;
+				let row = match &rewriter {
+					Some(rewriter) => {
+						let Row(row) = bincode::deserialize(&row)?;
+						IVec::from(bincode::serialize(&Row(rewriter.rewrite(row)?))?)
 					}
+					None => row,
+				};
+				if renamed {
+					batch.remove(key.clone());
 				}
-				ColumnUpdate(..) | IndexAdd(..) => Ok(()),
-				_ => Err(DatabaseError::Unimplemented.into()),
-				// TODO: Column remove & add: manipulate all rows
-				// TODO: Index remove, add and update: rebuild
-			}?;
+				batch.insert(rekey(&key, &prefix, &new_prefix), row);
+			}
 		}
 
-		let (key, schema) = fetch_schema(&self.tree, table_name)?;
-		let schema = schema.ok_or(DatabaseError::TableNotFound)?;
-		let schema = schema_diff.merge(schema);
-		let schema_value = bincode::serialize(&schema)?;
-		self.tree.insert(key, schema_value)?;
-
-		Ok(())
-	}
-}
-
-impl SledDatabase {
-	pub fn rename_table(&mut self, old_name: &str, new_name: String) -> Result<()> {
-		let (key, schema) = fetch_schema(&self.tree, old_name)?;
-		let schema = schema.ok_or(DatabaseError::TableNotFound)?;
-		self.tree.remove(key)?;
-
-		let value = bincode::serialize(&schema)?;
-		let key = format!("schema/{}", new_name);
-		let key = key.as_bytes();
-		self.tree.insert(key, value)?;
-
-		let prefix = format!("data/{}/", old_name);
-
-		for item in self.tree.scan_prefix(prefix.as_bytes()) {
-			let (key, value) = item?;
-
-			let new_key = std::str::from_utf8(key.as_ref())?;
-			let new_key = new_key.replace(old_name, &new_name);
-			self.tree.insert(new_key, value)?;
-
-			self.tree.remove(key)?;
+		// Entries of removed or replaced indexes are no longer kept, the rest move with the table,
+		// as do the keys of rows which they hold
+		let dropped_indexes: Vec<&str> = schema_diff
+			.changes()
+			.iter()
+			.filter_map(|change| match change {
+				SchemaChange::IndexRemove(name) | SchemaChange::IndexUpdate(name, _) => {
+					Some(name.as_str())
+				}
+				_ => None,
+			})
+			.collect();
+		for index in schema.indexes.iter() {
+			let prefix = index_prefix(table_name, &index.name);
+			let dropped = dropped_indexes.contains(&index.name.as_str());
+			if !dropped && !renamed {
+				continue;
+			}
+			let new_prefix = index_prefix(new_table_name, &index.name);
+			let data_prefix = format!("data/{}/", table_name);
+			let new_data_prefix = format!("data/{}/", new_table_name);
+			for item in self.tree.scan_prefix(prefix.as_bytes()) {
+				let (key, row_key) = item?;
+				batch.remove(key.clone());
+				if !dropped {
+					batch.insert(
+						rekey(&key, &prefix, &new_prefix),
+						rekey(&row_key, &data_prefix, &new_data_prefix),
+					);
+				}
+			}
 		}
 
-		Ok(())
+		batch.remove(schema_key.as_bytes());
+		batch.insert(
+			format!("schema/{}", new_table_name).as_bytes(),
+			bincode::serialize(&new_schema)?,
+		);
+		self.tree.apply_batch(batch).map_err(err_into)
 	}
-	pub fn add_column(&mut self, table_name: &str, column: Column) -> Result<()> {
-		let value = match (&column.default, &column.is_nullable) {
-			(Some(_expr), _) => Err(DatabaseError::Unimplemented), // TODO
-			(None, true) => Ok(Value::Null),
-			(None, false) => Err(DatabaseError::Unimplemented),
-		}?;
-
-		let prefix = format!("data/{}/", table_name);
-
-		for item in self.tree.scan_prefix(prefix.as_bytes()) {
-			let (key, row) = item?;
-			let row: Row = bincode::deserialize(&row)?;
-			let row = Row(row.0.into_iter().chain([value.clone()]).collect());
-			let row = bincode::serialize(&row)?;
+}
 
-			self.tree.insert(key, row)?;
-		}
-		Ok(())
-	}
-	pub fn remove_column(&mut self, table_name: &str, index: usize) -> Result<()> {
-		let prefix = format!("data/{}/", table_name);
-		for item in self.tree.scan_prefix(prefix.as_bytes()) {
-			let (key, row) = item?;
-			let row: Row = bincode::deserialize(&row)?;
-			let mut row = row.0;
-			row.remove(index);
-			let row = bincode::serialize(&Row(row))?;
+fn rekey(key: &IVec, prefix: &str, new_prefix: &str) -> IVec {
+	[new_prefix.as_bytes(), &key[prefix.len()..]]
+		.concat()
+		.into()
+}
 
-			self.tree.insert(key, row)?;
-		}
-		Ok(())
-	}
+impl SledDatabase {
 	pub fn remove_index(&mut self, table_name: &str, index_name: &str) -> Result<()> {
 		let prefix = index_prefix(table_name, index_name);
 		let remove_keys = self
@@ -0,0 +1,40 @@
+use {
+	super::quoted,
+	crate::{AutoIncrement, Result, SQLiteDatabase},
+	async_trait::async_trait,
+	rusqlite::types::ValueRef,
+};
+
+/// Values follow on from the greatest of the column
+#[async_trait(?Send)]
+impl AutoIncrement for SQLiteDatabase {
+	async fn generate_increment_values(
+		&mut self,
+		table_name: String,
+		columns: Vec<(usize, String, i64)>,
+	) -> Result<Vec<((usize, String), i64)>> {
+		columns
+			.into_iter()
+			.map(|(index, name, _)| {
+				let query = format!("SELECT MAX({}) FROM {}", quoted(&name), quoted(&table_name));
+				let greatest = self.connection.query_row(&query, [], |row| {
+					Ok(match row.get_ref(0)? {
+						ValueRef::Integer(greatest) => greatest,
+						ValueRef::Real(greatest) => greatest as i64,
+						_ => 0,
+					})
+				})?;
+				Ok(((index, name), greatest + 1))
+			})
+			.collect()
+	}
+
+	async fn set_increment_value(
+		&mut self,
+		_table_name: &str,
+		_column_name: &str,
+		_end: i64,
+	) -> Result<()> {
+		Ok(())
+	}
+}
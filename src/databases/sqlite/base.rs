@@ -0,0 +1,341 @@
+use {
+	super::{
+		quoted, row_key,
+		types::{comparable, sqlite_value, value_of, value_type},
+		SQLiteDatabaseError,
+	},
+	crate::{
+		Column, Comparison, DBBase, DatabaseError, Index, IndexFilter, Plane, Predicate, Result,
+		Row, RowFilter, SQLiteDatabase, Schema, Value, ValueType,
+	},
+	async_trait::async_trait,
+	rusqlite::{params_from_iter, types::Value as SQLiteValue},
+};
+
+/// A SQL condition, with the values of its parameters
+type Condition = (String, Vec<SQLiteValue>);
+
+#[async_trait(?Send)]
+impl DBBase for SQLiteDatabase {
+	async fn scan_schemas(&self) -> Result<Vec<Schema>> {
+		let mut statement = self.connection.prepare(
+			"SELECT name FROM sqlite_schema WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+		)?;
+		let table_names = statement
+			.query_map([], |row| row.get(0))?
+			.collect::<rusqlite::Result<Vec<String>>>()?;
+		table_names
+			.into_iter()
+			.filter_map(|table_name| self.describe_table(&table_name).transpose())
+			.collect()
+	}
+	async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
+		self.describe_table(table_name)
+	}
+
+	async fn scan_data(&self, table_name: &str) -> Result<Plane> {
+		let schema = self.schema_of(table_name)?;
+		self.select_rows(&schema, vec![], None)
+	}
+
+	/// Predicates are given to SQLite as a `WHERE` clause, where SQLite would agree with the engine, and projections as the columns selected
+	async fn scan_data_filtered(
+		&self,
+		table_name: &str,
+		predicates: &[Predicate],
+		projection: Option<&[String]>,
+	) -> Result<Plane> {
+		let schema = self.schema_of(table_name)?;
+		let conditions = predicates
+			.iter()
+			.filter_map(|predicate| predicate_sql(predicate, &schema))
+			.collect();
+		self.select_rows(&schema, conditions, projection)
+	}
+
+	/// As the engine leaves the constraint to the index, rows SQLite gives are confirmed as the engine would
+	async fn scan_data_indexed(
+		&self,
+		table_name: &str,
+		index_filter: IndexFilter,
+	) -> Result<Plane> {
+		let schema = self.schema_of(table_name)?;
+		let condition = index_filter_sql(&index_filter, &schema)?;
+		let columns: Vec<String> = schema
+			.column_defs
+			.iter()
+			.map(|column_def| column_def.name.clone())
+			.collect();
+		Ok(self
+			.select_rows(&schema, vec![condition], None)?
+			.into_iter()
+			.filter(|(_, Row(row))| confirm_index_filter(&index_filter, &schema, &columns, row))
+			.collect())
+	}
+}
+
+impl SQLiteDatabase {
+	/// The table's columns, of `PRAGMA table_info`, and indexes, of those made by `CREATE INDEX` of one column.
+	/// Columns alone in a unique constraint, or primary key, are unique.
+	fn describe_table(&self, table_name: &str) -> Result<Option<Schema>> {
+		let mut statement = self
+			.connection
+			.prepare("SELECT name, type, \"notnull\" FROM pragma_table_info(?1) ORDER BY cid")?;
+		let mut column_defs = statement
+			.query_map([table_name], |row| {
+				Ok(Column {
+					name: row.get(0)?,
+					data_type: value_type(&row.get::<_, String>(1)?),
+					is_nullable: !row.get::<_, bool>(2)?,
+					..Column::default()
+				})
+			})?
+			.collect::<rusqlite::Result<Vec<Column>>>()?;
+		if column_defs.is_empty() {
+			return Ok(None);
+		}
+
+		let mut statement = self.connection.prepare(
+			"SELECT il.name, il.\"unique\", il.origin, ii.name \
+			FROM pragma_index_list(?1) il \
+			JOIN pragma_index_info(il.name) ii \
+			WHERE il.partial = 0 \
+			GROUP BY il.name \
+			HAVING COUNT(*) = 1 AND ii.name IS NOT NULL",
+		)?;
+		let single_column_indexes = statement
+			.query_map([table_name], |row| {
+				Ok((
+					row.get::<_, String>(0)?,
+					row.get::<_, bool>(1)?,
+					row.get::<_, String>(2)?,
+					row.get::<_, String>(3)?,
+				))
+			})?
+			.collect::<rusqlite::Result<Vec<(String, bool, String, String)>>>()?;
+		let mut indexes = vec![];
+		for (name, is_unique, origin, column) in single_column_indexes {
+			if origin == "c" {
+				indexes.push(Index::new(name, column, is_unique));
+			} else if let Some(column_def) = column_defs
+				.iter_mut()
+				.find(|column_def| column_def.name == column)
+			{
+				column_def.is_unique = true;
+			}
+		}
+
+		Ok(Some(Schema {
+			table_name: table_name.to_string(),
+			column_defs,
+			indexes,
+		}))
+	}
+
+	pub(crate) fn schema_of(&self, table_name: &str) -> Result<Schema> {
+		self.describe_table(table_name)?
+			.ok_or_else(|| SQLiteDatabaseError::TableNotFound(table_name.to_string()).into())
+	}
+
+	/// Rows, keyed as by [SQLiteDatabase::key_columns], meeting all conditions
+	fn select_rows(
+		&self,
+		schema: &Schema,
+		conditions: Vec<Condition>,
+		projection: Option<&[String]>,
+	) -> Result<Plane> {
+		let table_name = schema.table_name.as_str();
+		let find_column = |name: &String| {
+			schema
+				.column_defs
+				.iter()
+				.find(|column_def| &column_def.name == name)
+				.cloned()
+				.ok_or_else(|| DatabaseError::ColumnNotFound(name.clone()).into())
+		};
+		// Rows are keyed by their key, which is selected ahead of the other columns
+		let key_types = self
+			.key_columns(table_name)?
+			.iter()
+			.map(|name| match name.as_str() {
+				"rowid" => Ok((name.clone(), ValueType::I64)),
+				_ => find_column(name).map(|column_def| (column_def.name, column_def.data_type)),
+			})
+			.collect::<Result<Vec<(String, ValueType)>>>()?;
+		let column_types = match projection {
+			Some(projection) => projection
+				.iter()
+				.map(find_column)
+				.collect::<Result<Vec<Column>>>()?,
+			None => schema.column_defs.clone(),
+		}
+		.into_iter()
+		.map(|column_def| (column_def.name, column_def.data_type));
+		let selected: Vec<(String, ValueType)> =
+			key_types.iter().cloned().chain(column_types).collect();
+
+		let columns = selected
+			.iter()
+			.map(|(name, _)| quoted(name))
+			.collect::<Vec<String>>()
+			.join(", ");
+		let (conditions, parameters): (Vec<String>, Vec<Vec<SQLiteValue>>) =
+			conditions.into_iter().unzip();
+		let where_clause = if conditions.is_empty() {
+			String::new()
+		} else {
+			format!(" WHERE {}", conditions.join(" AND "))
+		};
+		let query = format!(
+			"SELECT {columns} FROM {table}{where_clause}",
+			columns = columns,
+			table = quoted(table_name),
+			where_clause = where_clause
+		);
+
+		let mut statement = self.connection.prepare(&query)?;
+		let mut rows = statement.query(params_from_iter(parameters.into_iter().flatten()))?;
+		let mut plane = vec![];
+		while let Some(row) = rows.next()? {
+			let mut values = selected
+				.iter()
+				.enumerate()
+				.map(|(index, (_, data_type))| Ok(value_of(row.get_ref(index)?, data_type)))
+				.collect::<Result<Vec<_>>>()?;
+			let values_of_row = values.split_off(key_types.len());
+			plane.push((row_key(values), Row(values_of_row)));
+		}
+		Ok(plane)
+	}
+}
+
+/// A predicate as a SQL condition, where SQLite would keep every row the engine would;
+/// see [comparable]. Case insensitive `LIKE`s are left to the engine, as SQLite only folds ASCII.
+fn predicate_sql(predicate: &Predicate, schema: &Schema) -> Option<Condition> {
+	let data_type = schema
+		.column_defs
+		.iter()
+		.find(|column_def| column_def.name == predicate.column())
+		.map(|column_def| &column_def.data_type)?;
+	let column = quoted(predicate.column());
+	Some(match predicate {
+		// NULL <> value is true to the engine, where to SQLite it's NULL
+		Predicate::Compare(_, Comparison::NotEq, value) if comparable(value, data_type) => (
+			format!("({column} <> ? OR {column} IS NULL)", column = column),
+			vec![sqlite_value(value.clone()).ok()?],
+		),
+		Predicate::Compare(_, comparison, value) if comparable(value, data_type) => (
+			format!("{} {} ?", column, comparison.as_sql()),
+			vec![sqlite_value(value.clone()).ok()?],
+		),
+		Predicate::In(_, values)
+			if !values.is_empty() && values.iter().all(|value| comparable(value, data_type)) =>
+		{
+			(
+				format!("{} IN ({})", column, vec!["?"; values.len()].join(", ")),
+				values
+					.iter()
+					.cloned()
+					.map(sqlite_value)
+					.collect::<Result<Vec<SQLiteValue>>>()
+					.ok()?,
+			)
+		}
+		// SQLite's `LIKE` is of no escapes and is insensitive to the case of ASCII, and so keeps as many rows or more
+		Predicate::Like(_, pattern, false)
+			if matches!(data_type, ValueType::Str) && !pattern.contains('\\') =>
+		{
+			(
+				format!("{} LIKE ?", column),
+				vec![SQLiteValue::Text(pattern.clone())],
+			)
+		}
+		Predicate::IsNull(_) => (format!("{} IS NULL", column), vec![]),
+		Predicate::IsNotNull(_) => (format!("{} IS NOT NULL", column), vec![]),
+		_ => return None,
+	})
+}
+
+/// An index filter as a SQL condition; bounds which can't be given to SQLite, see [comparable], are left out
+fn index_filter_sql(index_filter: &IndexFilter, schema: &Schema) -> Result<Condition> {
+	use IndexFilter::*;
+	Ok(match index_filter {
+		LessThan(index_name, value) | MoreThan(index_name, value) => {
+			let column = index_column(index_name, schema)?;
+			let operator = match index_filter {
+				LessThan(..) => "<",
+				_ => ">=",
+			};
+			match sqlite_value(value.clone()) {
+				Ok(parameter) if comparable(value, &column.data_type) => (
+					format!("{} {} ?", quoted(&column.name), operator),
+					vec![parameter],
+				),
+				_ => (String::from("1"), vec![]),
+			}
+		}
+		Inner(left, right) | Outer(left, right) => {
+			let (left, mut left_parameters) = index_filter_sql(left, schema)?;
+			let (right, right_parameters) = index_filter_sql(right, schema)?;
+			left_parameters.extend(right_parameters);
+			let operator = match index_filter {
+				Inner(..) => "AND",
+				_ => "OR",
+			};
+			(
+				format!("({} {} {})", left, operator, right),
+				left_parameters,
+			)
+		}
+	})
+}
+
+/// Whether a row, of `columns`, is of an index filter; as the engine would find by [RowFilter]
+fn confirm_index_filter(
+	index_filter: &IndexFilter,
+	schema: &Schema,
+	columns: &[String],
+	row: &[Value],
+) -> bool {
+	use IndexFilter::*;
+	match index_filter {
+		LessThan(index_name, value) | MoreThan(index_name, value) => {
+			let column = match index_column(index_name, schema) {
+				Ok(column) => column.name,
+				Err(_) => return true,
+			};
+			let comparison = match index_filter {
+				LessThan(..) => Comparison::Lt,
+				_ => Comparison::GtEq,
+			};
+			RowFilter::new(
+				&[Predicate::Compare(column, comparison, value.clone())],
+				columns,
+			)
+			.confirm(row)
+		}
+		Inner(left, right) => {
+			confirm_index_filter(left, schema, columns, row)
+				&& confirm_index_filter(right, schema, columns, row)
+		}
+		Outer(left, right) => {
+			confirm_index_filter(left, schema, columns, row)
+				|| confirm_index_filter(right, schema, columns, row)
+		}
+	}
+}
+
+fn index_column(index_name: &str, schema: &Schema) -> Result<Column> {
+	schema
+		.indexes
+		.iter()
+		.find(|index| index.name == index_name)
+		.and_then(|index| {
+			schema
+				.column_defs
+				.iter()
+				.find(|column_def| column_def.name == index.column)
+		})
+		.cloned()
+		.ok_or_else(|| DatabaseError::IndexNotFound(index_name.to_string()).into())
+}
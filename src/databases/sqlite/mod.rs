@@ -0,0 +1,100 @@
+mod auto_increment;
+mod base;
+mod mutable;
+mod types;
+
+use {
+	crate::{DBFull, Database, Result, Value},
+	rusqlite::{Connection, OptionalExtension},
+	serde::Serialize,
+	std::fmt::Debug,
+	thiserror::Error,
+};
+
+const EXTENSIONS: [&str; 3] = [".sqlite", ".sqlite3", ".db"];
+
+#[derive(Error, Serialize, Debug, PartialEq)]
+pub enum SQLiteDatabaseError {
+	#[error("table not found: {0}")]
+	TableNotFound(String),
+	#[error("column can't be changed but for its name: {0}")]
+	UnalterableColumn(String),
+}
+
+/// A SQLite database file, of which every table is a table.
+/// Rows are keyed by their `rowid`, or, of tables `WITHOUT ROWID`, by their primary key.
+pub struct SQLiteDatabase {
+	connection: Connection,
+}
+
+impl DBFull for SQLiteDatabase {}
+
+impl Database {
+	pub fn new_sqlite(storage: SQLiteDatabase) -> Self {
+		Self::new(Box::new(storage))
+	}
+}
+impl SQLiteDatabase {
+	/// Creates the file where there isn't one
+	pub fn new(path: &str) -> Result<Self> {
+		Ok(Self {
+			connection: Connection::open(path)?,
+		})
+	}
+
+	/// Whether a path is of a SQLite file, by its extension
+	pub fn is_sqlite_path(path: &str) -> bool {
+		EXTENSIONS.iter().any(|extension| path.ends_with(extension))
+	}
+
+	/// Columns by which rows are found, see [SQLiteDatabase]
+	pub(crate) fn key_columns(&self, table_name: &str) -> Result<Vec<String>> {
+		let without_rowid: bool = self
+			.connection
+			.query_row(
+				"SELECT wr FROM pragma_table_list WHERE schema = 'main' AND name = ?1",
+				[table_name],
+				|row| row.get(0),
+			)
+			.optional()?
+			.unwrap_or(false);
+		if !without_rowid {
+			return Ok(vec![String::from("rowid")]);
+		}
+		let mut statement = self
+			.connection
+			.prepare("SELECT name FROM pragma_table_info(?1) WHERE pk > 0 ORDER BY pk")?;
+		let key = statement
+			.query_map([table_name], |row| row.get(0))?
+			.collect::<rusqlite::Result<Vec<String>>>()?;
+		Ok(key)
+	}
+}
+
+/// An identifier, quoted, so that it may be of any name
+pub(crate) fn quoted(identifier: &str) -> String {
+	format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// The key of a row, of the values of its key columns
+pub(crate) fn row_key(mut key: Vec<Value>) -> Value {
+	match key.len() {
+		1 => key.remove(0),
+		_ => Value::Array(key),
+	}
+}
+
+/// Values of a row's key, of as many columns as the key has, see [row_key]
+pub(crate) fn key_values(row_key: Value, columns: usize) -> Vec<Value> {
+	match row_key {
+		Value::Array(values) if columns != 1 => values,
+		row_key => vec![row_key],
+	}
+}
+
+pub(crate) fn key_conditions(key: &[String]) -> String {
+	key.iter()
+		.map(|column| format!("{} = ?", quoted(column)))
+		.collect::<Vec<String>>()
+		.join(" AND ")
+}
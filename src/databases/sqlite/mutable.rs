@@ -0,0 +1,278 @@
+use {
+	super::{
+		key_conditions, key_values, quoted,
+		types::{declared_type, sqlite_value, value_of},
+		SQLiteDatabaseError,
+	},
+	crate::{
+		Column, DBMut, Index, Payload, Result, Row, SQLiteDatabase, Schema, SchemaChange,
+		SchemaDiff, Value, ValueType,
+	},
+	async_trait::async_trait,
+	rusqlite::{params_from_iter, types::Value as SQLiteValue, Transaction},
+};
+
+#[async_trait(?Send)]
+impl DBMut for SQLiteDatabase {
+	/// Creates the table, and its indexes
+	async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
+		let columns = schema
+			.column_defs
+			.iter()
+			.map(column_sql)
+			.collect::<Vec<String>>()
+			.join(", ");
+		let transaction = self.connection.transaction()?;
+		transaction.execute(
+			&format!(
+				"CREATE TABLE {table} ({columns})",
+				table = quoted(&schema.table_name),
+				columns = columns
+			),
+			[],
+		)?;
+		for index in schema.indexes.iter() {
+			create_index(&transaction, &schema.table_name, index)?;
+		}
+		transaction.commit()?;
+		Ok(())
+	}
+
+	async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
+		self.connection
+			.execute(&format!("DROP TABLE {}", quoted(table_name)), [])?;
+		Ok(())
+	}
+
+	async fn insert_data(&mut self, table_name: &str, rows: Vec<Row>) -> Result<()> {
+		let schema = self.schema_of(table_name)?;
+		let query = format!(
+			"INSERT INTO {table} ({columns}) VALUES ({parameters})",
+			table = quoted(table_name),
+			columns = column_list(&schema.column_defs),
+			parameters = vec!["?"; schema.column_defs.len()].join(", ")
+		);
+		let transaction = self.connection.transaction()?;
+		{
+			let mut statement = transaction.prepare(&query)?;
+			for Row(values) in rows {
+				statement.execute(params_from_iter(sqlite_values(values)?))?;
+			}
+		}
+		transaction.commit()?;
+		Ok(())
+	}
+
+	/// Rows are found by their keys, see [SQLiteDatabase]
+	async fn update_data(&mut self, table_name: &str, rows: Vec<(Value, Row)>) -> Result<()> {
+		let schema = self.schema_of(table_name)?;
+		let key = self.key_columns(table_name)?;
+		let assignments = schema
+			.column_defs
+			.iter()
+			.map(|column_def| format!("{} = ?", quoted(&column_def.name)))
+			.collect::<Vec<String>>()
+			.join(", ");
+		let query = format!(
+			"UPDATE {table} SET {assignments} WHERE {conditions}",
+			table = quoted(table_name),
+			assignments = assignments,
+			conditions = key_conditions(&key)
+		);
+		let transaction = self.connection.transaction()?;
+		{
+			let mut statement = transaction.prepare(&query)?;
+			for (row_key, Row(values)) in rows {
+				let values = values
+					.into_iter()
+					.chain(key_values(row_key, key.len()))
+					.collect();
+				statement.execute(params_from_iter(sqlite_values(values)?))?;
+			}
+		}
+		transaction.commit()?;
+		Ok(())
+	}
+
+	/// Rows are found by their keys, see [SQLiteDatabase]
+	async fn delete_data(&mut self, table_name: &str, keys: Vec<Value>) -> Result<()> {
+		let key = self.key_columns(table_name)?;
+		let query = format!(
+			"DELETE FROM {table} WHERE {conditions}",
+			table = quoted(table_name),
+			conditions = key_conditions(&key)
+		);
+		let transaction = self.connection.transaction()?;
+		{
+			let mut statement = transaction.prepare(&query)?;
+			for row_key in keys {
+				statement.execute(params_from_iter(sqlite_values(key_values(
+					row_key,
+					key.len(),
+				))?))?;
+			}
+		}
+		transaction.commit()?;
+		Ok(())
+	}
+
+	/// Indexes are kept by SQLite, so there is nothing to do
+	async fn update_index(
+		&mut self,
+		_table_name: &str,
+		_index_name: &str,
+		_keys: Vec<(Value, Value)>,
+	) -> Result<()> {
+		Ok(())
+	}
+
+	/// Changes are made by `ALTER TABLE`, `CREATE INDEX` and `DROP INDEX`, in one transaction.
+	/// As SQLite can't change a column but for its name, columns otherwise changed fail the whole diff.
+	async fn alter_table(&mut self, table_name: &str, schema_diff: SchemaDiff) -> Result<()> {
+		let mut schema = self.schema_of(table_name)?;
+		schema_diff.merge(schema.clone())?;
+
+		let transaction = self.connection.transaction()?;
+		for change in schema_diff.changes() {
+			let table = quoted(&schema.table_name);
+			match change {
+				SchemaChange::RenameTable(new_name) if new_name != &schema.table_name => {
+					transaction.execute(
+						&format!("ALTER TABLE {} RENAME TO {}", table, quoted(new_name)),
+						[],
+					)?;
+				}
+				SchemaChange::RenameTable(_) => (),
+				SchemaChange::ColumnUpdate(column_name, column_def) => {
+					let old = schema
+						.column_defs
+						.iter()
+						.find(|old| &old.name == column_name)
+						.cloned()
+						.unwrap_or_default();
+					if old.data_type != column_def.data_type
+						|| old.is_nullable != column_def.is_nullable
+						|| old.is_unique != column_def.is_unique
+					{
+						return Err(
+							SQLiteDatabaseError::UnalterableColumn(column_name.clone()).into()
+						);
+					}
+					if &column_def.name != column_name {
+						transaction.execute(
+							&format!(
+								"ALTER TABLE {} RENAME COLUMN {} TO {}",
+								table,
+								quoted(column_name),
+								quoted(&column_def.name)
+							),
+							[],
+						)?;
+					}
+				}
+				SchemaChange::ColumnAdd(column_def) => {
+					transaction.execute(
+						&format!(
+							"ALTER TABLE {} ADD COLUMN {}",
+							table,
+							column_sql(column_def)
+						),
+						[],
+					)?;
+				}
+				SchemaChange::ColumnRemove(column_name) => {
+					transaction.execute(
+						&format!("ALTER TABLE {} DROP COLUMN {}", table, quoted(column_name)),
+						[],
+					)?;
+				}
+				SchemaChange::IndexAdd(index) => {
+					create_index(&transaction, &schema.table_name, index)?;
+				}
+				SchemaChange::IndexRemove(index_name) => {
+					transaction.execute(&format!("DROP INDEX {}", quoted(index_name)), [])?;
+				}
+				SchemaChange::IndexUpdate(index_name, index) => {
+					let unchanged = schema.indexes.iter().any(|old| {
+						&old.name == index_name
+							&& old.column == index.column
+							&& old.is_unique == index.is_unique
+					});
+					if &index.name != index_name || !unchanged {
+						transaction.execute(&format!("DROP INDEX {}", quoted(index_name)), [])?;
+						create_index(&transaction, &schema.table_name, index)?;
+					}
+				}
+			}
+			change.apply(&mut schema)?;
+		}
+		transaction.commit()?;
+		Ok(())
+	}
+
+	/// Sends the query, as it is, to SQLite
+	async fn execute_native(&mut self, query: &str) -> Result<Payload> {
+		let mut statement = self.connection.prepare(query)?;
+		if statement.column_count() == 0 {
+			statement.execute([])?;
+			return Ok(Payload::Success);
+		}
+		let labels = statement
+			.column_names()
+			.into_iter()
+			.map(String::from)
+			.collect::<Vec<String>>();
+		let mut rows = statement.query([])?;
+		let mut payload_rows = vec![];
+		while let Some(row) = rows.next()? {
+			payload_rows.push(Row((0..labels.len())
+				.map(|index| Ok(value_of(row.get_ref(index)?, &ValueType::Any)))
+				.collect::<Result<Vec<Value>>>()?));
+		}
+		Ok(Payload::Select {
+			labels,
+			rows: payload_rows,
+		})
+	}
+}
+
+/// A column's definition, as of `CREATE TABLE`
+fn column_sql(column_def: &Column) -> String {
+	format!(
+		"{name} {data_type}{nullable}{unique}",
+		name = quoted(&column_def.name),
+		data_type = declared_type(&column_def.data_type),
+		nullable = if column_def.is_nullable {
+			""
+		} else {
+			" NOT NULL"
+		},
+		unique = if column_def.is_unique { " UNIQUE" } else { "" }
+	)
+}
+
+fn column_list(column_defs: &[Column]) -> String {
+	column_defs
+		.iter()
+		.map(|column_def| quoted(&column_def.name))
+		.collect::<Vec<String>>()
+		.join(", ")
+}
+
+fn create_index(transaction: &Transaction, table_name: &str, index: &Index) -> Result<()> {
+	transaction.execute(
+		&format!(
+			"CREATE {unique}INDEX {name} ON {table} ({column})",
+			unique = if index.is_unique { "UNIQUE " } else { "" },
+			name = quoted(&index.name),
+			table = quoted(table_name),
+			column = quoted(&index.column)
+		),
+		[],
+	)?;
+	Ok(())
+}
+
+fn sqlite_values(values: Vec<Value>) -> Result<Vec<SQLiteValue>> {
+	values.into_iter().map(sqlite_value).collect()
+}
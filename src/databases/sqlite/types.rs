@@ -0,0 +1,95 @@
+use {
+	crate::{Cast, Result, Value, ValueType},
+	rusqlite::types::{Value as SQLiteValue, ValueRef},
+};
+
+/// The type of a column, by the affinity SQLite gives its declared type.
+/// Columns of `NUMERIC` affinity are told apart by their declared type, as SQLite has no type of booleans or timestamps;
+/// columns of no declared type may hold anything.
+pub(crate) fn value_type(declared_type: &str) -> ValueType {
+	let declared_type = declared_type.to_uppercase();
+	let declares = |names: &[&str]| names.iter().any(|name| declared_type.contains(name));
+	if declares(&["INT"]) {
+		ValueType::I64
+	} else if declares(&["CHAR", "CLOB", "TEXT"]) {
+		ValueType::Str
+	} else if declared_type.is_empty() {
+		ValueType::Any
+	} else if declares(&["BLOB"]) {
+		ValueType::Bytes
+	} else if declares(&["REAL", "FLOA", "DOUB"]) {
+		ValueType::F64
+	} else if declares(&["BOOL"]) {
+		ValueType::Bool
+	} else if declares(&["DATE", "TIMESTAMP"]) {
+		ValueType::Timestamp
+	} else {
+		ValueType::F64
+	}
+}
+
+/// Opposite of [value_type]
+pub(crate) fn declared_type(data_type: &ValueType) -> &'static str {
+	match data_type {
+		ValueType::Bool => "BOOLEAN",
+		ValueType::I64 | ValueType::U64 => "INTEGER",
+		ValueType::F64 => "REAL",
+		ValueType::Str => "TEXT",
+		ValueType::Timestamp => "TIMESTAMP",
+		ValueType::Bytes => "BLOB",
+		ValueType::Any | ValueType::Array => "",
+	}
+}
+
+/// A value, as read of a column of the type.
+/// Any column may hold any value, so those which aren't of the column's type are read as they are stored.
+pub(crate) fn value_of(value: ValueRef, data_type: &ValueType) -> Value {
+	match (value, data_type) {
+		(ValueRef::Null, _) => Value::Null,
+		(ValueRef::Integer(value), ValueType::Bool) => Value::Bool(value != 0),
+		(ValueRef::Integer(value), ValueType::Timestamp) => Value::Timestamp(value),
+		(ValueRef::Integer(value), ValueType::F64) => Value::F64(value as f64),
+		(ValueRef::Integer(value), _) => Value::I64(value),
+		(ValueRef::Real(value), _) => Value::F64(value),
+		(ValueRef::Text(text), data_type) => {
+			let text = Value::Str(String::from_utf8_lossy(text).into_owned());
+			match data_type {
+				ValueType::Timestamp => text
+					.clone()
+					.cast_valuetype(&ValueType::Timestamp)
+					.unwrap_or(text),
+				_ => text,
+			}
+		}
+		(ValueRef::Blob(bytes), _) => Value::Bytes(bytes.to_vec()),
+	}
+}
+
+/// A value as it is stored; booleans as `0` or `1`, and timestamps as text, as SQLite's `datetime` gives them
+pub(crate) fn sqlite_value(value: Value) -> Result<SQLiteValue> {
+	Ok(match value {
+		Value::Null => SQLiteValue::Null,
+		Value::Bool(value) => SQLiteValue::Integer(value as i64),
+		Value::I64(value) => SQLiteValue::Integer(value),
+		Value::U64(value) => match i64::try_from(value) {
+			Ok(value) => SQLiteValue::Integer(value),
+			Err(_) => SQLiteValue::Real(value as f64),
+		},
+		Value::F64(value) => SQLiteValue::Real(value),
+		Value::Str(value) => SQLiteValue::Text(value),
+		Value::Bytes(value) => SQLiteValue::Blob(value),
+		value => SQLiteValue::Text(value.cast()?),
+	})
+}
+
+/// Whether SQLite compares the value with those of a column of the type as the engine would.
+/// Only then may a comparison be given to SQLite.
+pub(crate) fn comparable(value: &Value, data_type: &ValueType) -> bool {
+	match (value, data_type) {
+		(Value::I64(_), ValueType::I64 | ValueType::F64) => true,
+		(Value::U64(value), ValueType::I64 | ValueType::F64) => i64::try_from(*value).is_ok(),
+		(Value::F64(value), ValueType::I64 | ValueType::F64) => value.is_finite(),
+		(Value::Bool(_), ValueType::Bool) | (Value::Str(_), ValueType::Str) => true,
+		_ => false,
+	}
+}
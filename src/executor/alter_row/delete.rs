@@ -19,6 +19,7 @@ impl Glue {
 			database,
 			..
 		} = table_name.try_into()?;
+		let database = self.resolve_database(database, &table_name);
 		let Schema {
 			column_defs,
 			indexes,
@@ -38,7 +39,8 @@ impl Glue {
 			.clone()
 			.map(|selection| {
 				PlannedRecipe::new(
-					MetaRecipe::new(selection)?.simplify_by_tempdb(&self.tempdb)?,
+					MetaRecipe::new(selection, &self.functions)?
+						.simplify_by_tempdb(&self.tempdb)?,
 					&columns,
 				)
 			})
@@ -63,7 +65,7 @@ impl Glue {
 
 		let num_keys = keys.len();
 
-		let database = &mut **self.get_mut_database(&None)?;
+		let database = &mut **self.get_mut_database(&database)?;
 		let result = database
 			.delete_data(&table_name, keys)
 			.await
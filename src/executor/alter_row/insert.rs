@@ -19,6 +19,7 @@ impl Glue {
 			database,
 			..
 		} = &table_name.try_into()?;
+		let database = &self.resolve_database(database.clone(), table_name);
 
 		let columns = columns
 			.iter()
@@ -55,7 +56,7 @@ impl Glue {
 			.ok_or(ExecuteError::TableNotExists)?;
 		let column_positions = columns_to_positions(&column_defs, columns)?;
 
-		validate(&column_defs, &column_positions, &mut rows)?;
+		validate(&column_defs, &column_positions, &mut rows, &self.functions)?;
 		let mut rows: Vec<Row> = rows.into_iter().map(Row).collect();
 		#[cfg(feature = "auto-increment")]
 		self.auto_increment(database, table, &column_defs, &mut rows)
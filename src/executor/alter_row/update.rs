@@ -25,6 +25,7 @@ impl Glue {
 			TableFactor::Table { name, .. } => name.try_into(),
 			_ => Err(ExecuteError::QueryNotSupported.into()),
 		}?;
+		let database = self.resolve_database(database, &table);
 		let Schema {
 			column_defs,
 			indexes,
@@ -49,7 +50,8 @@ impl Glue {
 			.clone()
 			.map(|selection| {
 				PlannedRecipe::new(
-					MetaRecipe::new(selection)?.simplify_by_tempdb(&self.tempdb)?,
+					MetaRecipe::new(selection, &self.functions)?
+						.simplify_by_tempdb(&self.tempdb)?,
 					&columns,
 				)
 			})
@@ -69,7 +71,8 @@ impl Glue {
 					.position(|column| column == &column_compare)
 					.ok_or(ExecuteError::ColumnNotFound)?;
 				let recipe = PlannedRecipe::new(
-					MetaRecipe::new(value.clone())?.simplify_by_tempdb(&self.tempdb)?,
+					MetaRecipe::new(value.clone(), &self.functions)?
+						.simplify_by_tempdb(&self.tempdb)?,
 					&columns,
 				)?;
 				Ok((index, recipe))
@@ -77,7 +80,7 @@ impl Glue {
 			.collect::<Result<Vec<(usize, PlannedRecipe)>>>()?;
 
 		let keyed_rows = self
-			.get_database(&None)?
+			.get_database(&database)?
 			.scan_data(&table)
 			.await?
 			.into_iter()
@@ -112,7 +115,7 @@ impl Glue {
 
 		let column_positions = columns_to_positions(&column_defs, &[])?;
 		let (keys, mut rows): (Vec<Value>, Vec<VecRow>) = keyed_rows.into_iter().unzip();
-		validate(&column_defs, &column_positions, &mut rows)?;
+		validate(&column_defs, &column_positions, &mut rows, &self.functions)?;
 
 		let table = table.as_str();
 		let mut rows: Vec<Row> = rows.into_iter().map(Row).collect();
@@ -1,6 +1,6 @@
 use {
 	crate::{
-		recipe::{Recipe, RecipeUtilities, Resolve, SimplifyBy},
+		recipe::{FunctionRegistry, Recipe, RecipeUtilities, Resolve, SimplifyBy},
 		types::Row,
 		Column, Error, Result, ValueDefault, ValueType,
 	},
@@ -43,7 +43,12 @@ pub fn columns_to_positions(column_defs: &[Column], columns: &[&str]) -> Result<
 	}
 }
 
-pub fn validate(columns: &[Column], stated_columns: &[usize], rows: &mut Vec<Row>) -> Result<()> {
+pub fn validate(
+	columns: &[Column],
+	stated_columns: &[usize],
+	rows: &mut Vec<Row>,
+	functions: &FunctionRegistry,
+) -> Result<()> {
 	if rows.iter().any(|row| row.len() != stated_columns.len()) {
 		return Err(ValidateError::WrongNumberOfValues.into());
 	}
@@ -59,7 +64,7 @@ pub fn validate(columns: &[Column], stated_columns: &[usize], rows: &mut Vec<Row
 			let nullable = column.is_nullable || column.default.is_some();
 
 			let failure_recipe = if let Some(ValueDefault::Recipe(expr)) = &column.default {
-				Some(Recipe::new_without_meta(expr.clone())?)
+				Some(Recipe::new_without_meta(expr.clone(), functions)?)
 			} else if nullable {
 				Some(Recipe::NULL)
 			} else {
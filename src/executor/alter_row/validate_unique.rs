@@ -85,6 +85,10 @@ impl Glue {
 			.map(|mut new_values| {
 				let mut existing_values = existing_values_iter.next()?;
 
+				// NULLs are never duplicates, nor are they ordered amongst other values
+				existing_values.retain(|value| !matches!(value, Value::Null));
+				new_values.retain(|value| !matches!(value, Value::Null));
+
 				existing_values.sort_unstable_by(|value_l, value_r| {
 					value_l.partial_cmp(value_r).unwrap_or(Ordering::Equal)
 				});
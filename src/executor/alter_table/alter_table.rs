@@ -1,7 +1,11 @@
 use {
-	super::{validate, AlterError},
-	crate::{data::get_name, Error, Glue, Result, SchemaDiff},
-	sqlparser::ast::{AlterTableOperation, ObjectName},
+	super::{validate, validate_data_type, AlterError},
+	crate::{
+		data::get_name, types::ComplexTableName, Cast, Column, DatabaseInner, Error, Glue, Result,
+		SchemaChange, SchemaDiff, Value, ValueDefault, ValueType,
+	},
+	sqlparser::ast::{AlterColumnOperation, AlterTableOperation, ObjectName, TableConstraint},
+	std::collections::HashSet,
 };
 
 impl Glue {
@@ -10,14 +14,38 @@ impl Glue {
 		name: &ObjectName,
 		operation: &AlterTableOperation,
 	) -> Result<()> {
-		let table_name = get_name(name).map_err(Error::from)?;
-		let database = &mut **self.get_mut_database(&None)?;
+		let ComplexTableName {
+			name: table_name,
+			database,
+			..
+		} = &name.try_into()?;
+		let database = self.resolve_database(database.clone(), table_name);
+		let database = &mut **self.get_mut_database(&database)?;
+
+		let schema = database
+			.fetch_schema(table_name)
+			.await?
+			.ok_or(AlterError::TableNotFound(table_name.clone()))?;
+		let find_column = |column_name: &str| -> Result<(usize, Column)> {
+			schema
+				.column_defs
+				.iter()
+				.cloned()
+				.enumerate()
+				.find(|(_, column)| column.name == column_name)
+				.ok_or_else(|| {
+					AlterError::ColumnNotFound(table_name.clone(), column_name.to_string()).into()
+				})
+		};
 
 		let diff = match operation {
 			AlterTableOperation::RenameTable {
 				table_name: new_table_name,
 			} => {
 				let new_table_name = get_name(new_table_name).map_err(Error::from)?;
+				if database.fetch_schema(new_table_name).await?.is_some() {
+					return Err(AlterError::TableAlreadyExists(new_table_name.clone()).into());
+				}
 
 				SchemaDiff::new_rename(new_table_name.clone())
 			}
@@ -25,20 +53,8 @@ impl Glue {
 				old_column_name,
 				new_column_name,
 			} => {
-				let schema = database
-					.fetch_schema(table_name)
-					.await?
-					.ok_or(AlterError::TableNotFound(table_name.clone()))?;
-				let (column_index, column) = schema
-					.column_defs
-					.into_iter()
-					.enumerate()
-					.find(|(_, column)| column.name == old_column_name.value)
-					.ok_or(AlterError::ColumnNotFound(
-						table_name.clone(),
-						old_column_name.value.clone(),
-					))?;
-				SchemaDiff::new_rename_column(column_index, column, new_column_name.value.clone())
+				let (_, column) = find_column(&old_column_name.value)?;
+				SchemaDiff::new_rename_column(column, new_column_name.value.clone())
 			}
 			AlterTableOperation::AddColumn { column_def } => {
 				validate(column_def).map_err(Error::from)?;
@@ -47,24 +63,125 @@ impl Glue {
 			}
 			AlterTableOperation::DropColumn {
 				column_name,
-				if_exists: _,
+				if_exists,
 				..
+			} => match find_column(&column_name.value) {
+				Ok((_, column)) => SchemaDiff::new_remove_column(column.name),
+				Err(_) if *if_exists => return Ok(()),
+				Err(error) => return Err(error),
+			},
+			AlterTableOperation::AlterColumn { column_name, op } => {
+				let (column_index, mut column) = find_column(&column_name.value)?;
+				match op {
+					AlterColumnOperation::SetNotNull => {
+						let nulls = column_values(database, table_name, column_index)
+							.await?
+							.into_iter()
+							.filter(|value| matches!(value, Value::Null))
+							.count();
+						if nulls > 0 {
+							return Err(AlterError::NullValuesInColumn(column.name, nulls).into());
+						}
+						column.is_nullable = false;
+					}
+					AlterColumnOperation::DropNotNull => column.is_nullable = true,
+					AlterColumnOperation::SetDefault { value } => {
+						column.default = Some(ValueDefault::Recipe(value.clone()))
+					}
+					AlterColumnOperation::DropDefault => column.default = None,
+					AlterColumnOperation::SetDataType {
+						data_type,
+						using: None,
+					} => {
+						validate_data_type(data_type)?;
+						let data_type: ValueType = data_type.clone().into();
+						let mut failures: Vec<String> =
+							column_values(database, table_name, column_index)
+								.await?
+								.into_iter()
+								.filter(|value| value.cast_valuetype(&data_type).is_err())
+								.map(|value| {
+									Cast::<String>::cast(value.clone())
+										.unwrap_or_else(|_| format!("{:?}", value))
+								})
+								.collect();
+						if !failures.is_empty() {
+							failures.sort();
+							return Err(AlterError::ColumnConversionFailed(
+								column.name,
+								data_type.to_string(),
+								failures,
+							)
+							.into());
+						}
+						column.data_type = data_type;
+					}
+					AlterColumnOperation::SetDataType { using: Some(_), .. } => {
+						return Err(AlterError::UnsupportedAlterTableOperation(
+							operation.to_string(),
+						)
+						.into())
+					}
+				}
+				SchemaDiff::new_update_column(column.name.clone(), column)
+			}
+			AlterTableOperation::AddConstraint(TableConstraint::Unique {
+				name,
+				columns,
+				is_primary,
+			}) => {
+				let (column_index, mut column) = match columns.as_slice() {
+					[column_name] => find_column(&column_name.value)?,
+					_ => {
+						return Err(AlterError::UnsupportedNumberOfIndexColumns(
+							name.as_ref()
+								.map(|name| name.value.clone())
+								.unwrap_or_else(|| table_name.clone()),
+						)
+						.into())
+					}
+				};
+				let values = column_values(database, table_name, column_index).await?;
+				let nulls = values
+					.iter()
+					.filter(|value| matches!(value, Value::Null))
+					.count();
+				if *is_primary && nulls > 0 {
+					return Err(AlterError::NullValuesInColumn(column.name, nulls).into());
+				}
+				let mut seen = HashSet::new();
+				if !values
+					.into_iter()
+					.filter(|value| !matches!(value, Value::Null))
+					.all(|value| seen.insert(value))
+				{
+					return Err(AlterError::DuplicateValuesInColumn(column.name).into());
+				}
+				column.is_unique = true;
+				if *is_primary {
+					column.is_nullable = false;
+				}
+				SchemaDiff::new_update_column(column.name.clone(), column)
+			}
+			AlterTableOperation::DropConstraint {
+				name, if_exists, ..
 			} => {
-				let schema = database
-					.fetch_schema(table_name)
-					.await?
-					.ok_or(AlterError::TableNotFound(table_name.clone()))?;
-				let (column_index, _) = schema
+				let found = schema
 					.column_defs
-					.into_iter()
-					.enumerate()
-					.find(|(_, column)| column.name == column_name.value)
-					.ok_or(AlterError::ColumnNotFound(
-						table_name.clone(),
-						column_name.value.clone(),
-					))?;
-
-				SchemaDiff::new_remove_column(column_index)
+					.iter()
+					.find(|column| {
+						column.is_unique
+							&& unique_constraint_name(table_name, &column.name) == name.value
+					})
+					.cloned();
+				match found {
+					Some(mut column) => {
+						column.is_unique = false;
+						SchemaDiff::new_update_column(column.name.clone(), column)
+					}
+					None if *if_exists => return Ok(()),
+					None => return Err(AlterError::ConstraintNotFound(name.value.clone()).into()),
+				}
 			}
 			_ => {
 				return Err(
@@ -72,6 +189,53 @@ impl Glue {
 				)
 			}
 		};
-		database.alter_table(table_name, diff).await
+
+		let new_schema = diff.merge(schema.clone())?;
+		let converted: Vec<String> = diff
+			.changes()
+			.iter()
+			.filter_map(|change| match change {
+				SchemaChange::ColumnUpdate(column_name, column) => schema
+					.column_defs
+					.iter()
+					.any(|old| &old.name == column_name && old.data_type != column.data_type)
+					.then_some(column_name.clone()),
+				_ => None,
+			})
+			.collect();
+
+		database.alter_table(table_name, diff).await?;
+
+		// Indexed values of converted columns are no longer as they were
+		for index in schema
+			.indexes
+			.iter()
+			.filter(|index| converted.contains(&index.column))
+		{
+			index
+				.reset(database, &new_schema.table_name, &new_schema.column_defs)
+				.await?;
+		}
+		Ok(())
 	}
 }
+
+/// Constraints aren't kept by name, a column's `UNIQUE` (or `PRIMARY KEY`) constraint is named as by PostgreSQL
+fn unique_constraint_name(table_name: &str, column_name: &str) -> String {
+	format!("{}_{}_key", table_name, column_name)
+}
+
+async fn column_values(
+	database: &DatabaseInner,
+	table_name: &str,
+	column_index: usize,
+) -> Result<Vec<Value>> {
+	Ok(database
+		.scan_data(table_name)
+		.await?
+		.into_iter()
+		.filter_map(|(_, mut row)| {
+			(column_index < row.0.len()).then(|| row.0.swap_remove(column_index))
+		})
+		.collect())
+}
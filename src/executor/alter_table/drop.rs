@@ -1,6 +1,6 @@
 use {
 	super::AlterError,
-	crate::{types::ComplexTableName, Glue, Result, ValueDefault},
+	crate::{types::ComplexTableName, Glue, Result, ValueDefault, TEMPORARY_DATABASE},
 	sqlparser::ast::{ObjectName, ObjectType},
 };
 
@@ -21,6 +21,8 @@ impl Glue {
 				database,
 				..
 			} = name.try_into()?;
+			let database = self.resolve_database(database, &table_name);
+			let temporary = database.as_deref() == Some(TEMPORARY_DATABASE);
 
 			let database = &mut **self.get_mut_database(&database)?;
 			let schema = database.fetch_schema(&table_name).await?;
@@ -35,6 +37,9 @@ impl Glue {
 				}
 
 				database.delete_schema(&table_name).await?;
+				if temporary {
+					self.tempdb.set_table_exists(&table_name, false);
+				}
 			} else if !if_exists {
 				return Err(AlterError::TableNotFound(table_name.to_owned()).into());
 			}
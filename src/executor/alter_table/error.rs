@@ -14,6 +14,22 @@ pub enum AlterError {
 	#[error("unsupported alter table operation: {0}")]
 	UnsupportedAlterTableOperation(String),
 
+	#[cfg(feature = "alter-table")]
+	#[error("values of column {0} could not be converted to {1}: {values}", values = .2.join(", "))]
+	ColumnConversionFailed(String, String, Vec<String>),
+
+	#[cfg(feature = "alter-table")]
+	#[error("column {0} has {1} NULL values")]
+	NullValuesInColumn(String, usize),
+
+	#[cfg(feature = "alter-table")]
+	#[error("column {0} has duplicate values")]
+	DuplicateValuesInColumn(String),
+
+	#[cfg(feature = "alter-table")]
+	#[error("constraint does not exist: {0}")]
+	ConstraintNotFound(String),
+
 	// DROP
 	#[error("drop type not supported: {0}")]
 	DropTypeNotSupported(String),
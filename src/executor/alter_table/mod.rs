@@ -4,4 +4,4 @@ mod error;
 mod truncate;
 mod validate;
 pub use error::AlterError;
-use validate::validate;
+use validate::{validate, validate_data_type};
@@ -1,13 +1,18 @@
 use {
-	crate::{data::get_name, AlterError, DatabaseInner, Glue, Result, ValueDefault},
+	crate::{types::ComplexTableName, AlterError, DatabaseInner, Glue, Result, ValueDefault},
 	futures::stream::{self, TryStreamExt},
 	sqlparser::ast::ObjectName,
 };
 
 impl Glue {
 	pub async fn ast_truncate(&mut self, table_name: &ObjectName) -> Result<()> {
-		let database = &mut **self.get_mut_database(&None)?;
-		let table_name = get_name(table_name)?;
+		let ComplexTableName {
+			name: table_name,
+			database,
+			..
+		} = &table_name.try_into()?;
+		let database = self.resolve_database(database.clone(), table_name);
+		let database = &mut **self.get_mut_database(&database)?;
 		let schema = database.fetch_schema(table_name).await?;
 
 		if let Some(schema) = schema {
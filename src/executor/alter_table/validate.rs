@@ -11,10 +11,24 @@ pub fn validate(column_def: &ColumnDef) -> Result<()> {
 		..
 	} = column_def;
 
+	validate_data_type(data_type)
+}
+
+pub fn validate_data_type(data_type: &DataType) -> Result<()> {
 	if !matches!(
 		data_type,
-		DataType::Boolean | DataType::Int(_) | DataType::Float(_) | DataType::Text
-	) {
+		DataType::Boolean
+			| DataType::Int(_)
+			| DataType::Float(_)
+			| DataType::Text
+			| DataType::Timestamp
+			| DataType::Array(_)
+			| DataType::Bytea
+			| DataType::Blob(_)
+			| DataType::Binary(_)
+			| DataType::Varbinary(_)
+	) && !matches!(data_type, DataType::Custom(name) if name.to_string().to_uppercase() == "BLOB")
+	{
 		return Err(AlterError::UnsupportedDataType(data_type.to_string()).into());
 	}
 
@@ -1,6 +1,6 @@
 use {
 	crate::{
-		data::get_name, AlterError, CreateError, Error, ExecuteError, Glue, Index, Result,
+		types::ComplexTableName, AlterError, CreateError, Error, ExecuteError, Glue, Index, Result,
 		SchemaDiff,
 	},
 	sqlparser::ast::{Expr, ObjectName, OrderByExpr},
@@ -22,8 +22,13 @@ impl Glue {
 			.value
 			.clone();
 
-		let table_name = get_name(table)?;
-		let database = &mut **self.get_mut_database(&None)?;
+		let ComplexTableName {
+			name: table_name,
+			database,
+			..
+		} = &table.try_into()?;
+		let database = self.resolve_database(database.clone(), table_name);
+		let database = &mut **self.get_mut_database(&database)?;
 
 		let schema = database
 			.fetch_schema(table_name)
@@ -1,5 +1,8 @@
 use {
-	crate::{data::Schema, types::ComplexTableName, Column, CreateError, Error, Glue, Result},
+	crate::{
+		data::Schema, types::ComplexTableName, Column, CreateError, Error, Glue, Result,
+		TEMPORARY_DATABASE,
+	},
 	sqlparser::ast::{ColumnDef, ObjectName},
 };
 
@@ -9,12 +12,18 @@ impl Glue {
 		name: &ObjectName,
 		column_defs: &[ColumnDef],
 		if_not_exists: bool,
+		temporary: bool,
 	) -> Result<()> {
 		let ComplexTableName {
 			name: table_name,
 			database,
 			..
 		} = name.try_into()?;
+		let database = if temporary {
+			Some(TEMPORARY_DATABASE.to_string())
+		} else {
+			database
+		};
 
 		let schema = Schema {
 			table_name,
@@ -29,6 +38,7 @@ impl Glue {
 		schema: Schema,
 		if_not_exists: bool,
 	) -> Result<()> {
+		let temporary = database.as_deref() == Some(TEMPORARY_DATABASE);
 		let database = &mut **self.get_mut_database(&database)?;
 		if database.fetch_schema(&schema.table_name).await?.is_some() {
 			if !if_not_exists {
@@ -39,7 +49,11 @@ impl Glue {
 				Ok(())
 			}
 		} else {
-			database.insert_schema(&schema).await
+			database.insert_schema(&schema).await?;
+			if temporary {
+				self.tempdb.set_table_exists(&schema.table_name, true);
+			}
+			Ok(())
 		}
 	}
 }
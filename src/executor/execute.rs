@@ -22,6 +22,8 @@ pub enum ExecuteError {
 	Unimplemented,
 	#[error("database already exists")]
 	DatabaseExists(String),
+	#[error("database name is reserved: {0}")]
+	ReservedDatabaseName(String),
 	#[error("invalid file location")]
 	InvalidFileLocation,
 	#[error("invalid database location")]
@@ -80,9 +82,10 @@ impl Glue {
 				name,
 				columns,
 				if_not_exists,
+				temporary,
 				..
 			} => self
-				.ast_create_table(name, columns, *if_not_exists)
+				.ast_create_table(name, columns, *if_not_exists, *temporary)
 				.await
 				.map(|_| Payload::Create),
 			Statement::CreateView {
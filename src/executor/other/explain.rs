@@ -23,6 +23,12 @@ impl Glue {
 						rows: databases,
 					});
 				}
+				if name == "REFRESH" {
+					for db_name in self.get_database_list().into_iter() {
+						self.get_database(&Some(db_name.clone()))?.refresh().await?;
+					}
+					return Ok(Payload::Success);
+				}
 				if name == "ALL_TABLE" {
 					let mut tables = vec![];
 					for db_name in self.get_database_list().into_iter() {
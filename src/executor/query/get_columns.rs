@@ -1,5 +1,8 @@
 use {
-	super::select::{refine_items, Manual},
+	super::{
+		select::{refine_items, Manual},
+		Scope,
+	},
 	crate::{
 		executor::fetch::fetch_columns,
 		types::{ColumnInfo, ComplexTableName},
@@ -10,30 +13,19 @@ use {
 
 impl Glue {
 	pub async fn get_columns(&self, table: ComplexTableName) -> Result<Vec<ColumnInfo>> {
-		if let Some((context_table_labels, ..)) = self.tempdb.get_table(&table.name) {
-			Ok(context_table_labels
-				.iter()
+		let view_labels = self.get_view_columns(&table.name, &table.database).await?;
+		if let Some(labels) = view_labels {
+			let labels = labels
+				.into_iter()
 				.map(|name| ColumnInfo {
 					table: table.clone(),
-					name: name.clone(),
+					name,
 					index: None,
 				})
-				.collect::<Vec<ColumnInfo>>())
+				.collect();
+			Ok(labels)
 		} else {
-			let view_labels = self.get_view_columns(&table.name, &table.database).await?;
-			if let Some(labels) = view_labels {
-				let labels = labels
-					.into_iter()
-					.map(|name| ColumnInfo {
-						table: table.clone(),
-						name,
-						index: None,
-					})
-					.collect();
-				Ok(labels)
-			} else {
-				fetch_columns(&**self.get_database(&table.database)?, table).await
-			}
+			fetch_columns(&**self.get_database(&table.database)?, table).await
 		}
 	}
 	#[async_recursion(?Send)]
@@ -45,7 +37,7 @@ impl Glue {
 		let query = self.get_view_query(view_name, database).await?;
 		if let Some(query) = query {
 			let plan = Manual::new(self, query)?;
-			let (_, columns) = self.arrange_joins(plan.joins).await?;
+			let (_, columns) = self.arrange_joins(plan.joins, &Scope::default()).await?;
 			let labels = refine_items(plan.select_items, &columns, false)?
 				.into_iter()
 				.map(|(_recipe, label)| label)
@@ -1,5 +1,5 @@
 use {
-	crate::{types::LabelsAndRows, Cast, Glue, Result, VIEW_TABLE_NAME},
+	crate::{query::Scope, types::LabelsAndRows, Cast, Glue, Result, VIEW_TABLE_NAME},
 	sqlparser::ast::Select,
 };
 
@@ -10,7 +10,9 @@ impl Glue {
 		database: &Option<String>,
 	) -> Result<Option<LabelsAndRows>> {
 		if let Some(query) = self.get_view_query(view_name, database).await? {
-			self.select_query(query, vec![]).await.map(Some)
+			self.select_query(query, vec![], &Scope::default())
+				.await
+				.map(Some)
 		} else {
 			Ok(None)
 		}
@@ -20,7 +22,9 @@ impl Glue {
 		view_name: &str,
 		database: &Option<String>,
 	) -> Result<Option<Select>> {
-		let views = self.get_table_rows(VIEW_TABLE_NAME, database, &None).await;
+		let views = self
+			.get_table_rows(VIEW_TABLE_NAME, database, &None, &[], None)
+			.await;
 		if let Ok(views) = views {
 			let query = views.into_iter().find_map(|row| {
 				let name: String = row[0].clone().cast().unwrap();
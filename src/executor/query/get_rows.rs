@@ -1,5 +1,5 @@
 use {
-	crate::{Glue, IndexFilter, Result, Value},
+	crate::{query::Projection, Glue, IndexFilter, Plane, Predicate, Result, Value},
 	async_recursion::async_recursion,
 };
 
@@ -10,16 +10,18 @@ impl Glue {
 		table: &str,
 		database: &Option<String>,
 		index_filter: &Option<IndexFilter>,
+		predicates: &[Predicate],
+		projection: Option<&Projection>,
 	) -> Result<Vec<Vec<Value>>> {
-		if let Some((.., context_table_rows)) = self.tempdb.get_table(table) {
-			Ok(context_table_rows.clone())
+		let rows = self.get_view_rows(table, database).await?;
+		if let Some(rows) = rows {
+			Ok(match projection {
+				Some(projection) => projection.rows(rows),
+				None => rows,
+			})
 		} else {
-			let rows = self.get_view_rows(table, database).await?;
-			if let Some(rows) = rows {
-				Ok(rows)
-			} else {
-				self.get_table_rows(table, database, index_filter).await
-			}
+			self.get_table_rows(table, database, index_filter, predicates, projection)
+				.await
 		}
 	}
 	pub async fn get_view_rows(
@@ -31,24 +33,34 @@ impl Glue {
 			.await
 			.map(|opt| opt.map(|(_, rows)| rows))
 	}
+	/// Where given a projection, databases are asked for only those columns
 	pub async fn get_table_rows(
 		&self,
 		table: &str,
 		database: &Option<String>,
 		index_filter: &Option<IndexFilter>,
+		predicates: &[Predicate],
+		projection: Option<&Projection>,
 	) -> Result<Vec<Vec<Value>>> {
 		let storage = &**self.get_database(database)?;
 		if let Some(index_filter) = index_filter.clone() {
-			storage.scan_data_indexed(table, index_filter)
+			let rows = rows_of(storage.scan_data_indexed(table, index_filter).await?);
+			return Ok(match projection {
+				Some(projection) => projection.rows(rows),
+				None => rows,
+			});
+		}
+		let names = projection.map(|projection| projection.names.as_slice());
+		if !predicates.is_empty() || names.is_some() {
+			storage.scan_data_filtered(table, predicates, names)
 		} else {
 			storage.scan_data(table)
 		}
 		.await
-		.map(|plane| {
-			plane
-				.into_iter()
-				.map(|(_, row)| row.0)
-				.collect::<Vec<Vec<Value>>>()
-		})
+		.map(rows_of)
 	}
 }
+
+fn rows_of(plane: Plane) -> Vec<Vec<Value>> {
+	plane.into_iter().map(|(_, row)| row.0).collect()
+}
@@ -1,22 +1,32 @@
 mod get_columns;
 mod get_data;
 mod get_rows;
+mod scope;
 mod select;
 mod set_expr;
+mod set_operation;
 
-pub use select::{join::*, ManualError, Plan, PlanError, SelectError};
 use {
 	crate::{
+		macros::warning,
 		recipe::{MetaRecipe, RecipeUtilities},
 		result::Result,
-		types::LabelsAndRows,
-		Cast, Glue, Value,
+		types::{LabelsAndRows, Row},
+		Cast, DistinctKey, Glue, Value, ValueType,
 	},
 	async_recursion::async_recursion,
 	serde::Serialize,
-	sqlparser::ast::{Cte, Query, TableAlias, With},
+	sqlparser::ast::{
+		Cte, Expr, ObjectName, Offset, OrderByExpr, Query, SetExpr, SetOperator, TableAlias,
+		TableFactor, TableWithJoins, With,
+	},
+	std::{collections::HashSet, iter::once},
 	thiserror::Error as ThisError,
 };
+pub use {
+	scope::Scope,
+	select::{join::*, Arrangement, ManualError, Outer, Plan, PlanError, SelectError},
+};
 
 const ENSURE_SIZE: bool = true;
 
@@ -36,11 +46,28 @@ pub enum QueryError {
 		"UNION/EXCEPT/INTERSECT columns misaligned, sides should have an equal number of columns"
 	)]
 	OperationColumnsMisaligned,
+	#[error("UNION/EXCEPT/INTERSECT column {column} is of type {left:?} on one side but {right:?} on the other")]
+	OperationTypesMismatched {
+		column: String,
+		left: ValueType,
+		right: ValueType,
+	},
+	#[error("recursive CTEs should be of the form: <initial query> UNION [ALL] <recursive query>")]
+	RecursiveQueryNotUnion,
+	#[error("recursive CTE did not finish within {0} iterations")]
+	RecursionLimitReached(usize),
+	#[error("CTE has {expected} columns but {found} names were given")]
+	CTEColumnsMisaligned { expected: usize, found: usize },
 }
 
 impl Glue {
-	#[async_recursion(?Send)]
 	pub async fn ast_query(&mut self, query: Query) -> Result<LabelsAndRows> {
+		self.scoped_query(query, &Scope::default()).await
+	}
+
+	/// Queries which may alter data; CTEs of these may do so too, for example: `WITH x AS (INSERT ...)`
+	#[async_recursion(?Send)]
+	async fn scoped_query(&mut self, query: Query, scope: &Scope) -> Result<LabelsAndRows> {
 		let Query {
 			body,
 			order_by,
@@ -52,9 +79,185 @@ impl Glue {
 			lock: _,
 		} = query;
 
+		let limits = self.query_limits(limit, offset)?;
+		let mut scope = scope.clone();
+		if let Some(With {
+			recursive,
+			cte_tables,
+		}) = with
+		{
+			for cte in cte_tables.into_iter() {
+				let (name, columns, query) = cte_parts(cte);
+				let data = if let SetExpr::Insert(_) = &query.body {
+					let (labels, rows) = self.scoped_query(query, &scope).await?;
+					(alias_labels(labels, columns)?, rows)
+				} else {
+					self.define_cte(&name, query, columns, recursive, &scope)
+						.await?
+				};
+				scope.set_table(name, data);
+			}
+		}
+
+		if let SetExpr::Insert(_) = body {
+			let data = self.from_body(body, order_by, &scope).await?;
+			Ok(apply_limits(data, limits))
+		} else {
+			self.query_body(body, order_by, limits, &scope, &Outer::None)
+				.await
+		}
+	}
+
+	/// Queries within other queries, such as derived tables; these may reference an [Outer] row.
+	#[async_recursion(?Send)]
+	pub async fn nested_query(
+		&self,
+		query: Query,
+		scope: &Scope,
+		outer: &Outer<'_>,
+	) -> Result<LabelsAndRows> {
+		let Query {
+			body,
+			order_by,
+			limit,
+			offset,
+			with,
+			// TODO (below)
+			fetch: _,
+			lock: _,
+		} = query;
+
+		let limits = self.query_limits(limit, offset)?;
+		let mut scope = scope.clone();
+		if let Some(With {
+			recursive,
+			cte_tables,
+		}) = with
+		{
+			for cte in cte_tables.into_iter() {
+				let (name, columns, query) = cte_parts(cte);
+				let data = self
+					.define_cte(&name, query, columns, recursive, &scope)
+					.await?;
+				scope.set_table(name, data);
+			}
+		}
+
+		self.query_body(body, order_by, limits, &scope, outer).await
+	}
+
+	async fn query_body(
+		&self,
+		body: SetExpr,
+		order_by: Vec<OrderByExpr>,
+		limits: (Option<usize>, Option<usize>),
+		scope: &Scope,
+		outer: &Outer<'_>,
+	) -> Result<LabelsAndRows> {
+		match body {
+			SetExpr::Select(select) => {
+				let plan = Plan::new(self, *select, order_by, scope, outer).await?;
+				self.limited_select(plan, limits).await
+			}
+			body => Ok(apply_limits(
+				self.from_set_expr(body, order_by, scope).await?,
+				limits,
+			)),
+		}
+	}
+
+	/// Selects only as many rows as are needed of the limits
+	#[async_recursion(?Send)]
+	pub(crate) async fn limited_select(
+		&self,
+		mut plan: Plan,
+		limits: (Option<usize>, Option<usize>),
+	) -> Result<LabelsAndRows> {
+		let (limit, offset) = limits;
+		plan.top = limit.map(|limit| limit.saturating_add(offset.unwrap_or(0)));
+		let data = self.select(plan).await?;
+		Ok(apply_limits(data, limits))
+	}
+
+	/// Finds the data of a CTE; each CTE sees those defined before it, which shadow any outer CTEs or tables of the same name
+	async fn define_cte(
+		&self,
+		name: &str,
+		query: Query,
+		columns: Vec<String>,
+		recursive: bool,
+		scope: &Scope,
+	) -> Result<LabelsAndRows> {
+		if recursive && is_recursive(name, &query) {
+			self.recursive_query(name, query, columns, scope).await
+		} else {
+			let (labels, rows) = self.nested_query(query, scope, &Outer::None).await?;
+			Ok((alias_labels(labels, columns)?, rows))
+		}
+	}
+
+	/// Finds a recursive CTE by repeating its recursive part, over the rows it last gave, until it gives no new rows
+	async fn recursive_query(
+		&self,
+		name: &str,
+		query: Query,
+		columns: Vec<String>,
+		scope: &Scope,
+	) -> Result<LabelsAndRows> {
+		let Query {
+			body,
+			order_by,
+			limit,
+			offset,
+			..
+		} = query;
+		let (anchor, recursive, all) = match body {
+			SetExpr::SetOperation {
+				op: SetOperator::Union,
+				all,
+				left,
+				right,
+			} => (*left, *right, all),
+			_ => return Err(QueryError::RecursiveQueryNotUnion.into()),
+		};
+		if !order_by.is_empty() {
+			warning!("recursive CTEs do not currently support ordering");
+		}
+		let limits = self.query_limits(limit, offset)?;
+
+		let (labels, anchor_rows) = self.from_set_expr(anchor, vec![], scope).await?;
+		let labels = alias_labels(labels, columns)?;
+		let mut rows = vec![];
+		let mut found = HashSet::new();
+		let mut working_rows = new_rows(&mut found, anchor_rows, all);
+		let mut iterations = 0;
+		while !working_rows.is_empty() {
+			rows.extend(working_rows.clone());
+			if iterations == self.recursion_limit {
+				return Err(QueryError::RecursionLimitReached(self.recursion_limit).into());
+			}
+			iterations += 1;
+			let mut scope = scope.clone();
+			scope.set_table(name.to_string(), (labels.clone(), working_rows));
+			let (recursive_labels, recursive_rows) = self
+				.from_set_expr(recursive.clone(), vec![], &scope)
+				.await?;
+			if recursive_labels.len() != labels.len() {
+				return Err(QueryError::OperationColumnsMisaligned.into());
+			}
+			working_rows = new_rows(&mut found, recursive_rows, all);
+		}
+		Ok(apply_limits((labels, rows), limits))
+	}
+
+	pub(crate) fn query_limits(
+		&self,
+		limit: Option<Expr>,
+		offset: Option<Offset>,
+	) -> Result<(Option<usize>, Option<usize>)> {
 		let limit: Option<usize> = limit
 			.map(|expression| {
-				MetaRecipe::new(expression)?
+				MetaRecipe::new(expression, &self.functions)?
 					.simplify_by_tempdb(&self.tempdb)?
 					.confirm_or_err(QueryError::MissingComponentsForLimit.into())?
 					.cast()
@@ -62,59 +265,123 @@ impl Glue {
 			.transpose()?;
 		let offset: Option<usize> = offset
 			.map(|offset| {
-				MetaRecipe::new(offset.value)?
+				MetaRecipe::new(offset.value, &self.functions)?
 					.simplify_by_tempdb(&self.tempdb)?
 					.confirm_or_err(QueryError::MissingComponentsForOffset.into())?
 					.cast()
 			})
 			.transpose()?;
+		Ok((limit, offset))
+	}
+}
 
-		if let Some(with) = with {
-			let With {
-				recursive: _, // Recursive not currently supported
-				cte_tables,
-			} = with;
-			for cte in cte_tables.into_iter() {
-				let Cte {
-					alias,
-					query,
-					from: _, // What is `from` for?
-				} = cte;
-				let TableAlias {
-					name,
-					columns: _, // TODO: Columns - Check that number is same and then rename labels
-				} = alias;
-				let name = name.value;
-				let data = self.ast_query(query).await?;
-				self.tempdb.set_table(name, data);
-			}
-		}
+fn cte_parts(cte: Cte) -> (String, Vec<String>, Query) {
+	let Cte {
+		alias: TableAlias { name, columns },
+		query,
+		from: _, // What is `from` for?
+	} = cte;
+	let columns = columns.into_iter().map(|column| column.value).collect();
+	(name.value, columns, query)
+}
 
-		let (mut labels, mut rows) = self.from_body(body, order_by).await?;
+/// Whether the CTE is a `UNION` of which the second part reads the CTE itself, as a table
+fn is_recursive(name: &str, query: &Query) -> bool {
+	match &query.body {
+		SetExpr::SetOperation {
+			op: SetOperator::Union,
+			right,
+			..
+		} => reads_table(name, right),
+		_ => false,
+	}
+}
 
-		if let Some(offset) = offset {
-			rows.drain(0..offset);
-		}
-		if let Some(limit) = limit {
-			rows.truncate(limit);
+/// Whether any of the tables of a query's `FROM`s and joins, or of those of its subqueries, is the table
+fn reads_table(name: &str, body: &SetExpr) -> bool {
+	match body {
+		SetExpr::Select(select) => select
+			.from
+			.iter()
+			.any(|table| joins_read_table(name, table)),
+		SetExpr::SetOperation { left, right, .. } => {
+			reads_table(name, left) || reads_table(name, right)
 		}
-		if ENSURE_SIZE {
-			let row_width = rows
-				.iter()
-				.map(|values_row| values_row.len())
-				.max()
-				.unwrap_or(0);
-			if row_width > 0 {
-				rows = rows
-					.into_iter()
-					.map(|mut row| {
-						row.resize(row_width, Value::Null);
-						row
-					})
-					.collect();
-				labels.resize(row_width, String::new())
-			};
+		SetExpr::Query(query) => reads_table(name, &query.body),
+		_ => false,
+	}
+}
+
+fn joins_read_table(name: &str, table: &TableWithJoins) -> bool {
+	once(&table.relation)
+		.chain(table.joins.iter().map(|join| &join.relation))
+		.any(|table_factor| match table_factor {
+			TableFactor::Table {
+				name: ObjectName(identifiers),
+				args,
+				..
+			} => {
+				args.is_empty()
+					&& matches!(identifiers.as_slice(), [identifier] if identifier.value == name)
+			}
+			TableFactor::Derived { subquery, .. } => reads_table(name, &subquery.body),
+			TableFactor::NestedJoin(table) => joins_read_table(name, table),
+			_ => false,
+		})
+}
+
+/// Rows not yet `found`; all rows where `all` (as in `UNION ALL`), otherwise only those which are distinct
+fn new_rows(found: &mut HashSet<Vec<DistinctKey>>, rows: Vec<Row>, all: bool) -> Vec<Row> {
+	if all {
+		return rows;
+	}
+	rows.into_iter()
+		.filter(|row| found.insert(DistinctKey::of_row(row)))
+		.collect()
+}
+
+/// Renames labels to those given by a CTE, for example: `WITH numbers(number) AS ...`
+fn alias_labels(labels: Vec<String>, columns: Vec<String>) -> Result<Vec<String>> {
+	if columns.len() > labels.len() {
+		return Err(QueryError::CTEColumnsMisaligned {
+			expected: labels.len(),
+			found: columns.len(),
 		}
-		Ok((labels, rows))
+		.into());
+	}
+	let mut columns = columns.into_iter();
+	Ok(labels
+		.into_iter()
+		.map(|label| columns.next().unwrap_or(label))
+		.collect())
+}
+
+fn apply_limits(
+	(mut labels, mut rows): LabelsAndRows,
+	(limit, offset): (Option<usize>, Option<usize>),
+) -> LabelsAndRows {
+	if let Some(offset) = offset {
+		rows.drain(0..offset.min(rows.len()));
+	}
+	if let Some(limit) = limit {
+		rows.truncate(limit);
+	}
+	if ENSURE_SIZE {
+		let row_width = rows
+			.iter()
+			.map(|values_row| values_row.len())
+			.max()
+			.unwrap_or(0);
+		if row_width > 0 {
+			rows = rows
+				.into_iter()
+				.map(|mut row| {
+					row.resize(row_width, Value::Null);
+					row
+				})
+				.collect();
+			labels.resize(row_width, String::new())
+		};
 	}
+	(labels, rows)
 }
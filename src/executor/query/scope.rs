@@ -0,0 +1,20 @@
+use {
+	crate::types::LabelsAndRows,
+	std::{collections::HashMap, sync::Arc},
+};
+
+/// Tables defined by `WITH`, visible to the query which defines them and to queries within it.
+/// A definition shadows tables, and outer definitions, of the same name.
+#[derive(Debug, Clone, Default)]
+pub struct Scope {
+	tables: HashMap<String, Arc<LabelsAndRows>>,
+}
+
+impl Scope {
+	pub fn get_table(&self, name: &str) -> Option<&LabelsAndRows> {
+		self.tables.get(name).map(|data| &**data)
+	}
+	pub fn set_table(&mut self, name: String, data: LabelsAndRows) {
+		self.tables.insert(name, Arc::new(data));
+	}
+}
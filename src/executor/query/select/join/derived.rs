@@ -0,0 +1,108 @@
+use {
+	crate::{
+		query::{Arrangement, Outer, Plan, Scope},
+		types::{ColumnInfo, ComplexTableName, ObjectName, Row},
+		Glue, JoinError, Result,
+	},
+	async_recursion::async_recursion,
+	sqlparser::ast::{Query, SetExpr, TableAlias},
+};
+
+/// A subquery used in place of a table, for example: `SELECT * FROM (SELECT id FROM people) AS ids`
+///
+/// `LATERAL` subqueries may reference columns of earlier tables and so are run for each of their rows;
+/// they are arranged once, as they are planned, and only bound to each row.
+#[derive(Debug, Clone)]
+pub struct DerivedTable {
+	pub query: Box<Query>,
+	pub lateral: bool,
+	pub labels: Vec<String>,
+	/// Columns of other tables which a `LATERAL` subquery references, found when planned
+	pub outer_references: Vec<ObjectName>,
+	/// Of a `LATERAL` subquery referencing other tables, its arrangement
+	arrangement: Option<Box<Arrangement>>,
+	limits: (Option<usize>, Option<usize>),
+}
+
+impl DerivedTable {
+	pub fn new(query: Query, lateral: bool, alias: Option<TableAlias>) -> (ComplexTableName, Self) {
+		let (alias, labels) = alias
+			.map(|TableAlias { name, columns }| {
+				(
+					Some(name.value),
+					columns.into_iter().map(|column| column.value).collect(),
+				)
+			})
+			.unwrap_or((None, vec![]));
+		let table = ComplexTableName {
+			database: None,
+			name: alias.clone().unwrap_or_else(|| String::from("derived")),
+			alias,
+		};
+		(
+			table,
+			Self {
+				query: Box::new(query),
+				lateral,
+				labels,
+				outer_references: vec![],
+				arrangement: None,
+				limits: (None, None),
+			},
+		)
+	}
+	/// Gives labels and, where the subquery does not reference other tables, the resulting rows
+	#[async_recursion(?Send)]
+	pub async fn plan(
+		&mut self,
+		glue: &Glue,
+		scope: &Scope,
+	) -> Result<(Vec<String>, Option<Vec<Row>>)> {
+		if self.lateral {
+			if let (SetExpr::Select(select), None) = (&self.query.body, &self.query.with) {
+				let arrangement =
+					Plan::arrange(glue, *select.clone(), self.query.order_by.clone(), scope)
+						.await?;
+				let plan = arrangement.clone().bind(glue, &Outer::Unknown)?;
+				if !plan.outer_references.is_empty() {
+					self.limits =
+						glue.query_limits(self.query.limit.clone(), self.query.offset.clone())?;
+					self.outer_references = plan.outer_references;
+					self.arrangement = Some(Box::new(arrangement));
+					return Ok((self.relabel(plan.labels), None));
+				}
+			}
+		}
+		let (labels, rows) = glue
+			.nested_query(*self.query.clone(), scope, &Outer::None)
+			.await?;
+		Ok((self.relabel(labels), Some(rows)))
+	}
+	pub async fn execute_lateral(
+		&self,
+		glue: &Glue,
+		plane_columns: &[ColumnInfo],
+		plane_row: &Row,
+	) -> Result<Vec<Row>> {
+		let arrangement = self.arrangement.clone().ok_or(JoinError::Unreachable)?;
+		let plan = arrangement.bind(glue, &Outer::Row(plane_columns, plane_row))?;
+		glue.limited_select(plan, self.limits)
+			.await
+			.map(|(_, rows)| rows)
+	}
+	/// Labels given by the alias take precedence, others lose any table name
+	fn relabel(&self, labels: Vec<String>) -> Vec<String> {
+		labels
+			.into_iter()
+			.enumerate()
+			.map(|(index, label)| {
+				self.labels.get(index).cloned().unwrap_or_else(|| {
+					label
+						.rsplit_once('.')
+						.map(|(_, column)| column.to_string())
+						.unwrap_or(label)
+				})
+			})
+			.collect()
+	}
+}
@@ -1,9 +1,9 @@
 use {
-	super::{JoinError, JoinMethod, JoinPlan, JoinType},
+	super::{JoinError, JoinMethod, JoinPlan, JoinSource, JoinType, Projection, TableFunction},
 	crate::{
-		recipe::{Ingredient, MetaRecipe, Method, PlannedRecipe, Recipe},
+		recipe::{Ingredient, MetaRecipe, Method, PlannedRecipe, Recipe, RecipeError},
 		types::{ColumnInfo, Row},
-		Glue, IndexFilter, Result, Value,
+		Glue, IndexFilter, Predicate, Result, Value,
 	},
 };
 
@@ -11,10 +11,14 @@ use {
 pub struct JoinExecute {
 	pub database: Option<String>,
 	pub table: String,
+	pub source: JoinSource,
 	pub method: JoinMethod,
 	pub join_type: JoinType,
 	pub widths: (usize, usize),
 	pub index_filter: Option<IndexFilter>,
+	/// Parts of the constraint for the database to filter by as it scans, where the source is a table
+	pub predicates: Vec<Predicate>,
+	pub projection: Option<Projection>,
 }
 
 impl JoinExecute {
@@ -22,33 +26,117 @@ impl JoinExecute {
 		plan: JoinPlan,
 		plane_columns: &[ColumnInfo],
 		index_filter: Option<IndexFilter>,
+		predicates: Vec<Predicate>,
 	) -> Result<Self> {
 		let JoinPlan {
 			database,
 			table,
+			source,
 			join_type,
 			constraint,
 			columns,
+			projection,
 			..
 		} = plan;
+		let source = match source {
+			JoinSource::Function(function) => {
+				let arguments = function
+					.arguments
+					.into_iter()
+					.map(|argument| PlannedRecipe::new(argument, plane_columns))
+					.collect::<Result<Vec<PlannedRecipe>>>()?;
+				JoinSource::Lateral(function.operator, arguments)
+			}
+			JoinSource::Derived(derived) => {
+				let other_columns = plane_columns
+					.iter()
+					.filter(|column| !columns.iter().any(|own| own.table == column.table))
+					.collect::<Vec<&ColumnInfo>>();
+				if let Some(missing) = derived
+					.outer_references
+					.iter()
+					.find(|reference| !other_columns.iter().any(|column| column == reference))
+				{
+					return Err(RecipeError::MissingColumn(missing.clone()).into());
+				}
+				JoinSource::LateralDerived(Box::new(derived), plane_columns.to_vec())
+			}
+			source => source,
+		};
 		let widths = (plane_columns.len(), columns.len());
 		let method = decide_method(constraint, columns, plane_columns)?;
 		Ok(Self {
 			database,
 			table,
+			source,
 			method,
 			join_type,
 			widths,
 			index_filter,
+			predicates,
+			projection,
 		})
 	}
 	pub fn set_first_table(&mut self) {
 		self.method = JoinMethod::FirstTable;
 	}
 	pub async fn execute<'a>(self, glue: &Glue, plane_rows: Vec<Row>) -> Result<Vec<Row>> {
-		let rows = glue
-			.get_rows(&self.table, &self.database, &self.index_filter)
-			.await?;
+		let project = |rows: Vec<Row>| match &self.projection {
+			Some(projection) => projection.rows(rows),
+			None => rows,
+		};
+		let rows = match self.source {
+			JoinSource::Table => {
+				glue.get_rows(
+					&self.table,
+					&self.database,
+					&self.index_filter,
+					&self.predicates,
+					self.projection.as_ref(),
+				)
+				.await?
+			}
+			JoinSource::Rows(rows) => project(rows),
+			JoinSource::Lateral(operator, arguments) => {
+				return plane_rows
+					.into_iter()
+					.map(|plane_row| {
+						let rows = project(TableFunction::execute_lateral(
+							operator, &arguments, &plane_row,
+						)?);
+						self.method.clone().run(
+							&self.join_type,
+							self.widths.0,
+							self.widths.1,
+							vec![plane_row],
+							rows,
+						)
+					})
+					.collect::<Result<Vec<Vec<Row>>>>()
+					.map(|rows| rows.into_iter().flatten().collect());
+			}
+			JoinSource::LateralDerived(derived, plane_columns) => {
+				let mut rows = vec![];
+				for plane_row in plane_rows.into_iter() {
+					let derived_rows = project(
+						derived
+							.execute_lateral(glue, &plane_columns, &plane_row)
+							.await?,
+					);
+					rows.extend(self.method.clone().run(
+						&self.join_type,
+						self.widths.0,
+						self.widths.1,
+						vec![plane_row],
+						derived_rows,
+					)?);
+				}
+				return Ok(rows);
+			}
+			JoinSource::Function(_) | JoinSource::Derived(_) => {
+				return Err(JoinError::Unreachable.into())
+			}
+		};
 		self.method.run(
 			&self.join_type,
 			self.widths.0,
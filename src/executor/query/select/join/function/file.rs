@@ -0,0 +1,91 @@
+#[cfg(feature = "csv-database")]
+use crate::CSVSettings;
+use {
+	crate::{types::LabelsAndRows, Cast, JSONObject, JoinError, Result, Value, ValueError},
+	serde::Deserialize,
+	serde_json::Deserializer,
+	std::fs::read_to_string,
+};
+
+/// `READ_CSV(path[, delimiter[, has_header]])`: reads a file as a table without attaching it as a database.
+/// Columns are discerned as they would be for a CSV database.
+#[cfg(feature = "csv-database")]
+pub fn read_csv(arguments: Vec<Value>) -> Result<LabelsAndRows> {
+	if !(1..=3).contains(&arguments.len()) {
+		return Err(ValueError::NumberOfFunctionParamsNotMatching {
+			expected: 1,
+			found: arguments.len(),
+		}
+		.into());
+	}
+	let mut arguments = arguments.into_iter();
+	let path: String = arguments.next().unwrap_or(Value::Null).cast()?;
+	let mut csv_settings = CSVSettings::default();
+	if let Some(delimiter) = arguments.next() {
+		let delimiter: String = delimiter.cast()?;
+		csv_settings.delimiter = match delimiter.as_bytes() {
+			[delimiter] => *delimiter,
+			_ => return Err(JoinError::InvalidDelimiter(delimiter).into()),
+		};
+	}
+	if let Some(has_header) = arguments.next() {
+		csv_settings.has_header = Some(has_header.cast()?);
+	}
+	csv_settings.read_file(&path)
+}
+
+/// `READ_JSON(path)`: reads a file of objects, either as an array or one after another (as in NDJSON).
+/// Columns are the keys of all objects, in the order they are first found.
+pub fn read_json(arguments: Vec<Value>) -> Result<LabelsAndRows> {
+	if arguments.len() != 1 {
+		return Err(ValueError::NumberOfFunctionParamsNotMatching {
+			expected: 1,
+			found: arguments.len(),
+		}
+		.into());
+	}
+	let path: String = arguments.into_iter().next().unwrap_or(Value::Null).cast()?;
+	let text = read_to_string(&path)
+		.map_err(|error| JoinError::FailedToReadFile(path.clone(), error.to_string()))?;
+
+	let objects = Deserializer::from_str(&text)
+		.into_iter::<Objects>()
+		.map(|objects| {
+			objects.map_err(|error| JoinError::FailedToParseJSON(error.to_string()).into())
+		})
+		.collect::<Result<Vec<Objects>>>()?
+		.into_iter()
+		.flat_map(|objects| match objects {
+			Objects::Many(objects) => objects,
+			Objects::One(object) => vec![object],
+		})
+		.collect::<Vec<JSONObject>>();
+
+	let mut labels: Vec<String> = vec![];
+	objects
+		.iter()
+		.flat_map(|object| object.keys())
+		.for_each(|key| {
+			if !labels.contains(key) {
+				labels.push(key.clone())
+			}
+		});
+	let rows = objects
+		.into_iter()
+		.map(|mut object| {
+			labels
+				.iter()
+				.map(|label| object.remove(label).map(Value::from).unwrap_or(Value::Null))
+				.collect()
+		})
+		.collect();
+	Ok((labels, rows))
+}
+
+/// Objects of a file of JSON, given either alone or as an array
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Objects {
+	Many(Vec<JSONObject>),
+	One(JSONObject),
+}
@@ -0,0 +1,131 @@
+mod file;
+mod series;
+mod unnest;
+
+use {
+	super::JoinError,
+	crate::{
+		recipe::{MetaRecipe, PlannedRecipe, RecipeError, RecipeUtilities},
+		types::{ComplexTableName, LabelsAndRows, Row},
+		Glue, Result, Value,
+	},
+	sqlparser::ast::{FunctionArg, FunctionArgExpr, ObjectName, TableAlias},
+};
+
+pub type TableFunctionOperator = fn(Vec<Value>) -> Result<LabelsAndRows>;
+
+/// A function used in place of a table, for example: `SELECT * FROM UNNEST(ARRAY[1, 2]) AS numbers(number)`
+#[derive(Debug, Clone)]
+pub struct TableFunction {
+	pub name: String,
+	pub operator: TableFunctionOperator,
+	pub arguments: Vec<MetaRecipe>,
+	pub labels: Vec<String>,
+}
+
+impl TableFunction {
+	pub fn new(
+		name: ObjectName,
+		arguments: Vec<FunctionArg>,
+		alias: Option<TableAlias>,
+		glue: &Glue,
+	) -> Result<(ComplexTableName, Self)> {
+		if name.0.len() != 1 {
+			return Err(JoinError::UnimplementedNumberOfComponents.into());
+		}
+		let name = name.0[0].value.to_uppercase();
+		let operator = table_function_operator(&name)?;
+		let arguments = arguments
+			.into_iter()
+			.map(|argument| match argument {
+				FunctionArg::Named { arg, .. } | FunctionArg::Unnamed(arg) => match arg {
+					FunctionArgExpr::Expr(arg) => {
+						MetaRecipe::new(arg, &glue.functions)?.simplify_by_tempdb(&glue.tempdb)
+					}
+					_ => Err(RecipeError::Unimplemented.into()),
+				},
+			})
+			.collect::<Result<Vec<MetaRecipe>>>()?;
+		let (alias, labels) = alias
+			.map(|TableAlias { name, columns }| {
+				(
+					Some(name.value),
+					columns.into_iter().map(|column| column.value).collect(),
+				)
+			})
+			.unwrap_or((None, vec![]));
+		let table = ComplexTableName {
+			database: None,
+			alias,
+			name: name.clone(),
+		};
+		Ok((
+			table,
+			Self {
+				name,
+				operator,
+				arguments,
+				labels,
+			},
+		))
+	}
+	/// Whether arguments reference columns of other tables in the query
+	pub fn is_lateral(&self) -> bool {
+		self.arguments
+			.iter()
+			.any(|argument| argument.meta.objects.iter().any(Option::is_some))
+	}
+	/// Gives labels and, where arguments are constant, the resulting rows
+	pub fn plan(&self) -> Result<(Vec<String>, Option<Vec<Row>>)> {
+		let (labels, rows) = if self.is_lateral() {
+			// Rows depend on other tables, so only labels are known
+			(lateral_labels(&self.name, self.arguments.len())?, None)
+		} else {
+			let arguments = self
+				.arguments
+				.iter()
+				.map(|argument| argument.clone().simplify_by_basic()?.confirm())
+				.collect::<Result<Vec<Value>>>()?;
+			let (labels, rows) = (self.operator)(arguments)?;
+			(labels, Some(rows))
+		};
+		let labels = labels
+			.into_iter()
+			.enumerate()
+			.map(|(index, label)| self.labels.get(index).cloned().unwrap_or(label))
+			.collect();
+		Ok((labels, rows))
+	}
+	pub fn execute_lateral(
+		operator: TableFunctionOperator,
+		arguments: &[PlannedRecipe],
+		plane_row: &Row,
+	) -> Result<Vec<Row>> {
+		let arguments = arguments
+			.iter()
+			.map(|argument| argument.clone().simplify_by_row(plane_row)?.confirm())
+			.collect::<Result<Vec<Value>>>()?;
+		operator(arguments).map(|(_, rows)| rows)
+	}
+}
+
+/// Labels of a function's columns, as known without its arguments.
+/// Functions of files can't be lateral, as their columns are of the files they read.
+fn lateral_labels(name: &str, arguments: usize) -> Result<Vec<String>> {
+	match name {
+		"UNNEST" => unnest::labels(arguments),
+		"GENERATE_SERIES" => Ok(series::labels()),
+		name => Err(JoinError::UnimplementedLateralFunction(String::from(name)).into()),
+	}
+}
+
+fn table_function_operator(name: &str) -> Result<TableFunctionOperator> {
+	match name {
+		"UNNEST" => Ok(unnest::unnest),
+		"GENERATE_SERIES" => Ok(series::generate_series),
+		#[cfg(feature = "csv-database")]
+		"READ_CSV" => Ok(file::read_csv),
+		"READ_JSON" => Ok(file::read_json),
+		unimplemented => Err(RecipeError::UnimplementedMethod(String::from(unimplemented)).into()),
+	}
+}
@@ -0,0 +1,165 @@
+use {
+	crate::{types::LabelsAndRows, Cast, JoinError, Result, Value, ValueError, ValueType},
+	chrono::{DateTime, Months, TimeZone, Utc},
+};
+
+const SECONDS_IN_DAY: i64 = 24 * 60 * 60;
+/// Most rows a series may be of, beyond which it fails rather than fill memory
+const MAX_SERIES_ROWS: usize = 1_000_000;
+
+/// `GENERATE_SERIES(start, stop[, step])`: from `start` to `stop` inclusive, `step` defaults to `1`.
+///
+/// Where `start` or `stop` is a timestamp, `step` is either a number of seconds
+/// or an interval such as `'2 HOURS'` or `'1 MONTH'`, defaulting to a day.
+pub fn generate_series(arguments: Vec<Value>) -> Result<LabelsAndRows> {
+	let labels = labels();
+	let found = arguments.len();
+	let mut arguments = arguments.into_iter();
+	let (start, stop, step) = match (
+		arguments.next(),
+		arguments.next(),
+		arguments.next(),
+		arguments.next(),
+	) {
+		(Some(start), Some(stop), step, None) => (start, stop, step),
+		_ => {
+			return Err(ValueError::NumberOfFunctionParamsNotMatching { expected: 2, found }.into())
+		}
+	};
+	if matches!(start, Value::Null)
+		|| matches!(stop, Value::Null)
+		|| matches!(step, Some(Value::Null))
+	{
+		return Ok((labels, vec![]));
+	}
+
+	let values = if matches!(start, Value::Timestamp(_)) || matches!(stop, Value::Timestamp(_)) {
+		timestamp_series(start, stop, step)?
+	} else if [Some(&start), Some(&stop), step.as_ref()]
+		.into_iter()
+		.flatten()
+		.any(|value| matches!(value, Value::F64(_)))
+	{
+		let step = step.map(Cast::cast).transpose()?.unwrap_or(1.0);
+		float_series(start.cast()?, stop.cast()?, step)?
+			.into_iter()
+			.map(Value::F64)
+			.collect()
+	} else {
+		let step = step.map(Cast::cast).transpose()?.unwrap_or(1);
+		integer_series(start.cast()?, stop.cast()?, step)?
+			.into_iter()
+			.map(Value::I64)
+			.collect()
+	};
+	Ok((
+		labels,
+		values.into_iter().map(|value| vec![value]).collect(),
+	))
+}
+
+pub fn labels() -> Vec<String> {
+	vec![String::from("generate_series")]
+}
+
+/// Takes the `nth` values for as long as they do not pass `stop`; failing where there'd be more than [MAX_SERIES_ROWS]
+fn series<T: PartialOrd>(
+	stop: T,
+	ascending: bool,
+	nth: impl Fn(i64) -> Option<T>,
+) -> Result<Vec<T>> {
+	let values: Vec<T> = (0..)
+		.map_while(nth)
+		.take_while(|value| {
+			if ascending {
+				value <= &stop
+			} else {
+				value >= &stop
+			}
+		})
+		.take(MAX_SERIES_ROWS + 1)
+		.collect();
+	if values.len() > MAX_SERIES_ROWS {
+		return Err(JoinError::SeriesTooLong(MAX_SERIES_ROWS).into());
+	}
+	Ok(values)
+}
+
+fn integer_series(start: i64, stop: i64, step: i64) -> Result<Vec<i64>> {
+	if step == 0 {
+		return Err(JoinError::InvalidSeriesStep(step.to_string()).into());
+	}
+	series(stop, step > 0, |index| {
+		step.checked_mul(index)?.checked_add(start)
+	})
+}
+
+fn float_series(start: f64, stop: f64, step: f64) -> Result<Vec<f64>> {
+	if step == 0.0 || !step.is_finite() {
+		return Err(JoinError::InvalidSeriesStep(step.to_string()).into());
+	}
+	series(stop, step > 0.0, |index| Some(start + step * index as f64))
+}
+
+fn timestamp_series(start: Value, stop: Value, step: Option<Value>) -> Result<Vec<Value>> {
+	let (start, stop) = match (
+		start.cast_valuetype(&ValueType::Timestamp)?,
+		stop.cast_valuetype(&ValueType::Timestamp)?,
+	) {
+		(Value::Timestamp(start), Value::Timestamp(stop)) => (start, stop),
+		_ => return Err(JoinError::Unreachable.into()),
+	};
+	let timestamps = match parse_interval(step)? {
+		Interval::Seconds(seconds) => integer_series(start, stop, seconds)?,
+		Interval::Months(months) => {
+			if months == 0 {
+				return Err(JoinError::InvalidSeriesStep(String::from("0 MONTHS")).into());
+			}
+			let start: DateTime<Utc> = Utc
+				.timestamp_opt(start, 0)
+				.single()
+				.ok_or(ValueError::DateError)?;
+			// Each value is found from the start so that days of month do not drift
+			series(stop, months > 0, |index| {
+				let months = months.checked_mul(index)?;
+				let offset = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+				if months > 0 {
+					start.checked_add_months(offset)
+				} else {
+					start.checked_sub_months(offset)
+				}
+				.map(|datetime| datetime.timestamp())
+			})?
+		}
+	};
+	Ok(timestamps.into_iter().map(Value::Timestamp).collect())
+}
+
+enum Interval {
+	Seconds(i64),
+	Months(i64),
+}
+
+/// Intervals are given as an amount and a unit, for example: `'3 DAYS'`
+fn parse_interval(step: Option<Value>) -> Result<Interval> {
+	let text = match step {
+		None => return Ok(Interval::Seconds(SECONDS_IN_DAY)),
+		Some(Value::Str(text)) => text,
+		Some(seconds) => return seconds.cast().map(Interval::Seconds),
+	};
+	let invalid = || JoinError::InvalidSeriesStep(text.clone());
+	let (amount, unit) = text.trim().split_once(' ').ok_or_else(invalid)?;
+	let amount: i64 = amount.parse().map_err(|_| invalid())?;
+	let unit = unit.trim().to_uppercase();
+	let multiply = |by: i64| amount.checked_mul(by).ok_or_else(invalid);
+	Ok(match unit.trim_end_matches('S') {
+		"SECOND" => Interval::Seconds(amount),
+		"MINUTE" => Interval::Seconds(multiply(60)?),
+		"HOUR" => Interval::Seconds(multiply(60 * 60)?),
+		"DAY" => Interval::Seconds(multiply(SECONDS_IN_DAY)?),
+		"WEEK" => Interval::Seconds(multiply(7 * SECONDS_IN_DAY)?),
+		"MONTH" => Interval::Months(amount),
+		"YEAR" => Interval::Months(multiply(12)?),
+		_ => return Err(invalid().into()),
+	})
+}
@@ -0,0 +1,41 @@
+use crate::{types::LabelsAndRows, Result, Value, ValueError};
+
+/// Each array becomes a column, shorter arrays are padded with NULLs
+pub fn unnest(arguments: Vec<Value>) -> Result<LabelsAndRows> {
+	let labels = labels(arguments.len())?;
+	let arrays = arguments
+		.into_iter()
+		.map(|argument| match argument {
+			Value::Array(array) => Ok(array),
+			Value::Null => Ok(vec![]),
+			other => Err(ValueError::OnlySupportsArray(other, "UNNEST").into()),
+		})
+		.collect::<Result<Vec<Vec<Value>>>>()?;
+	let height = arrays.iter().map(Vec::len).max().unwrap_or(0);
+	let rows = (0..height)
+		.map(|index| {
+			arrays
+				.iter()
+				.map(|array| array.get(index).cloned().unwrap_or(Value::Null))
+				.collect()
+		})
+		.collect();
+	Ok((labels, rows))
+}
+
+/// Labels of the columns of as many arrays
+pub fn labels(arrays: usize) -> Result<Vec<String>> {
+	Ok(match arrays {
+		0 => {
+			return Err(ValueError::NumberOfFunctionParamsNotMatching {
+				expected: 1,
+				found: 0,
+			}
+			.into())
+		}
+		1 => vec![String::from("unnest")],
+		arrays => (0..arrays)
+			.map(|index| format!("unnest_{}", index))
+			.collect(),
+	})
+}
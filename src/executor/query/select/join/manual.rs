@@ -1,37 +1,69 @@
 use {
-	super::{JoinError, JoinType},
-	crate::{recipe::MetaRecipe, types::ComplexTableName, Result, TempDB},
-	sqlparser::ast::{Join as AstJoin, JoinConstraint, JoinOperator, TableFactor},
+	super::{DerivedTable, JoinError, JoinSource, JoinType, TableFunction},
+	crate::{
+		recipe::{FunctionRegistry, MetaRecipe},
+		types::ComplexTableName,
+		Glue, Result,
+	},
+	sqlparser::ast::{Expr, Join as AstJoin, JoinConstraint, JoinOperator, TableFactor},
 };
 
 #[derive(Debug, Clone)]
 pub struct JoinManual {
 	pub table: ComplexTableName,
+	pub source: JoinSource,
 	pub constraint: MetaRecipe,
 	pub join_type: JoinType,
 }
 
 impl JoinManual {
-	pub fn new(join: AstJoin, tempdb: &TempDB) -> Result<Self> {
-		let table = join.relation.try_into()?;
-		let (join_type, constraint) = Self::convert_join(join.join_operator)?;
-		let constraint = constraint.simplify_by_tempdb(tempdb)?;
+	pub fn new(join: AstJoin, glue: &Glue) -> Result<Self> {
+		let (table, source) = Self::convert_table(join.relation, glue)?;
+		let (join_type, constraint) = Self::convert_join(join.join_operator, &glue.functions)?;
+		let constraint = constraint.simplify_by_tempdb(&glue.tempdb)?;
 		Ok(Self {
 			table,
+			source,
 			join_type,
 			constraint,
 		})
 	}
-	pub fn new_implicit_join(table: TableFactor) -> Result<Self> {
-		let table = table.try_into()?;
+	pub fn new_implicit_join(table: TableFactor, glue: &Glue) -> Result<Self> {
+		let (table, source) = Self::convert_table(table, glue)?;
 		let (join_type, constraint) = (JoinType::CrossJoin, MetaRecipe::TRUE);
 		Ok(Self {
 			table,
+			source,
 			join_type,
 			constraint,
 		})
 	}
-	fn convert_join(from: JoinOperator) -> Result<(JoinType, MetaRecipe)> {
+	fn convert_table(table: TableFactor, glue: &Glue) -> Result<(ComplexTableName, JoinSource)> {
+		match table {
+			TableFactor::Table {
+				name, alias, args, ..
+			} if !args.is_empty() => TableFunction::new(name, args, alias, glue)
+				.map(|(table, function)| (table, JoinSource::Function(function))),
+			TableFactor::TableFunction {
+				expr: Expr::Function(function),
+				alias,
+			} => TableFunction::new(function.name, function.args, alias, glue)
+				.map(|(table, function)| (table, JoinSource::Function(function))),
+			TableFactor::Derived {
+				lateral,
+				subquery,
+				alias,
+			} => {
+				let (table, derived) = DerivedTable::new(*subquery, lateral, alias);
+				Ok((table, JoinSource::Derived(derived)))
+			}
+			table => Ok((table.try_into()?, JoinSource::Table)),
+		}
+	}
+	fn convert_join(
+		from: JoinOperator,
+		functions: &FunctionRegistry,
+	) -> Result<(JoinType, MetaRecipe)> {
 		let (join_type, constraint) = match from {
 			JoinOperator::Inner(constraint) => (JoinType::Inner, Some(constraint)),
 			JoinOperator::LeftOuter(constraint) => (JoinType::Left, Some(constraint)),
@@ -41,7 +73,7 @@ impl JoinManual {
 			_ => return Err(JoinError::UnimplementedJoinType.into()),
 		};
 		let constraint = match constraint {
-			Some(JoinConstraint::On(constraint)) => MetaRecipe::new(constraint)?,
+			Some(JoinConstraint::On(constraint)) => MetaRecipe::new(constraint, functions)?,
 			Some(JoinConstraint::None) | None => MetaRecipe::TRUE,
 			_ => return Err(JoinError::UnimplementedJoinConstaint.into()),
 		};
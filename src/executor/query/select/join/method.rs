@@ -18,7 +18,7 @@ macro_rules! unwrap_or_break {
 	};
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum JoinMethod {
 	General(PlannedRecipe),
 	ColumnEqColumn {
@@ -45,6 +45,10 @@ impl JoinMethod {
 		Ok(match self {
 			JoinMethod::Ignore => plane_rows,
 			JoinMethod::FirstTable => self_rows,
+			JoinMethod::All if self_rows.is_empty() && join.includes_left() => plane_rows
+				.into_iter()
+				.map(|plane_row| join_parts(plane_row, vec![Value::Null; right_width]))
+				.collect(),
 			JoinMethod::All => self_rows
 				.into_iter()
 				.fold(vec![], |mut result_rows, self_row| {
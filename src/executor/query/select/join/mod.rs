@@ -1,12 +1,28 @@
+mod derived;
 mod execute;
+mod function;
 mod manual;
 mod method;
 mod plan;
+mod projection;
 use {
-	crate::types::ComplexTableName, serde::Serialize, std::fmt::Debug,
+	crate::{
+		recipe::PlannedRecipe,
+		types::{ColumnInfo, ComplexTableName, Row},
+	},
+	serde::Serialize,
+	std::fmt::Debug,
 	thiserror::Error as ThisError,
 };
-pub use {execute::JoinExecute, manual::JoinManual, method::JoinMethod, plan::JoinPlan};
+pub use {
+	derived::DerivedTable,
+	execute::JoinExecute,
+	function::{TableFunction, TableFunctionOperator},
+	manual::JoinManual,
+	method::JoinMethod,
+	plan::JoinPlan,
+	projection::Projection,
+};
 
 #[derive(ThisError, Serialize, Debug, PartialEq)]
 pub enum JoinError {
@@ -24,6 +40,19 @@ pub enum JoinError {
 	#[error("amount of components in identifier not yet supported")]
 	UnimplementedNumberOfComponents,
 
+	#[error("invalid step for series: {0}")]
+	InvalidSeriesStep(String),
+	#[error("series would be of more than {0} rows")]
+	SeriesTooLong(usize),
+	#[error("table function can't take columns of other tables: {0}")]
+	UnimplementedLateralFunction(String),
+	#[error("delimiter must be a single character, found: {0}")]
+	InvalidDelimiter(String),
+	#[error("failed to read file '{0}': {1}")]
+	FailedToReadFile(String, String),
+	#[error("failed to parse JSON: {0}")]
+	FailedToParseJSON(String),
+
 	#[error("this should be impossible, please report")]
 	UnreachableCellNotFound,
 	#[error("this should be impossible, please report")]
@@ -47,3 +76,16 @@ impl JoinType {
 		matches!(self, JoinType::Right | JoinType::Full)
 	}
 }
+
+/// Where the rows of a join come from.
+/// Functions and derived tables become [JoinSource::Rows] when planned,
+/// or [JoinSource::Lateral]/[JoinSource::LateralDerived] if they depend on other tables.
+#[derive(Debug, Clone)]
+pub enum JoinSource {
+	Table,
+	Function(TableFunction),
+	Derived(DerivedTable),
+	Lateral(TableFunctionOperator, Vec<PlannedRecipe>),
+	LateralDerived(Box<DerivedTable>, Vec<ColumnInfo>),
+	Rows(Vec<Row>),
+}
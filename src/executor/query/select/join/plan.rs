@@ -1,6 +1,7 @@
 use {
-	super::{JoinManual, JoinType},
+	super::{JoinManual, JoinSource, JoinType, Projection},
 	crate::{
+		query::Scope,
 		recipe::MetaRecipe,
 		types::{ColumnInfo, ComplexTableName},
 		Glue, Result,
@@ -8,14 +9,17 @@ use {
 	std::cmp::Ordering,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct JoinPlan {
 	pub database: Option<String>,
 	pub table: String,
+	pub source: JoinSource,
 	pub columns: Vec<ColumnInfo>,
 	pub join_type: JoinType,
 	pub constraint: MetaRecipe,
 	pub needed_tables: Vec<usize>,
+	/// Where not all of `columns` are needed, which are; `columns` then being only those
+	pub projection: Option<Projection>,
 }
 impl PartialEq for JoinPlan {
 	fn eq(&self, _other: &Self) -> bool {
@@ -35,13 +39,51 @@ impl Ord for JoinPlan {
 }
 
 impl JoinPlan {
-	pub async fn new<'a>(join_manual: JoinManual, glue: &Glue) -> Result<Self> {
+	pub async fn new<'a>(join_manual: JoinManual, glue: &Glue, scope: &Scope) -> Result<Self> {
 		let JoinManual {
-			table,
+			mut table,
+			source,
 			constraint,
 			join_type,
 		} = join_manual;
-		let columns = glue.get_columns(table.clone()).await?;
+		let (labels, source) = match source {
+			JoinSource::Function(function) => {
+				let (labels, rows) = function.plan()?;
+				let source = rows
+					.map(JoinSource::Rows)
+					.unwrap_or(JoinSource::Function(function));
+				(labels, source)
+			}
+			JoinSource::Derived(mut derived) => {
+				let (labels, rows) = derived.plan(glue, scope).await?;
+				let source = rows
+					.map(JoinSource::Rows)
+					.unwrap_or(JoinSource::Derived(derived));
+				(labels, source)
+			}
+			JoinSource::Table => match scope.get_table(&table.name) {
+				Some((labels, rows)) if table.database.is_none() => {
+					(labels.clone(), JoinSource::Rows(rows.clone()))
+				}
+				_ => {
+					table.database = glue.resolve_database(table.database, &table.name);
+					(vec![], JoinSource::Table)
+				}
+			},
+			source => (vec![], source),
+		};
+		let columns = if let JoinSource::Table = source {
+			glue.get_columns(table.clone()).await?
+		} else {
+			labels
+				.into_iter()
+				.map(|name| ColumnInfo {
+					table: table.clone(),
+					name,
+					index: None,
+				})
+				.collect()
+		};
 		let ComplexTableName {
 			database,
 			name: table,
@@ -50,28 +92,47 @@ impl JoinPlan {
 		Ok(Self {
 			database,
 			table,
+			source,
 			join_type,
 			columns,
 			constraint,
 			needed_tables: vec![],
+			projection: None,
 		})
 	}
+	/// Drops columns which aren't `needed`, these being left out of the rows of the join
+	pub fn project(&mut self, needed: impl Fn(&ColumnInfo) -> bool) {
+		self.projection = Projection::new(&self.columns, needed);
+		if let Some(projection) = &self.projection {
+			self.columns = projection
+				.indexes
+				.iter()
+				.map(|index| self.columns[*index].clone())
+				.collect();
+		}
+	}
 	pub fn calculate_needed_tables(&mut self, table_columns: &[Vec<ColumnInfo>]) {
+		let mut needed_columns = self.constraint.meta.objects.clone();
+		match &self.source {
+			JoinSource::Function(function) => function.arguments.iter().for_each(|argument| {
+				needed_columns.extend(argument.meta.objects.clone());
+			}),
+			JoinSource::Derived(derived) => {
+				needed_columns.extend(derived.outer_references.iter().cloned().map(Some))
+			}
+			_ => (),
+		}
 		self.needed_tables = table_columns
 			.iter()
 			.enumerate()
 			.filter_map(|(index, columns)| {
 				if columns.iter().any(|table_column| {
-					self.constraint
-						.meta
-						.objects
-						.iter()
-						.any(|constraint_column| {
-							constraint_column
-								.as_ref()
-								.map(|constraint_column| table_column == constraint_column)
-								.unwrap_or(false)
-						})
+					needed_columns.iter().any(|constraint_column| {
+						constraint_column
+							.as_ref()
+							.map(|constraint_column| table_column == constraint_column)
+							.unwrap_or(false)
+					})
 				}) {
 					Some(index)
 				} else {
@@ -0,0 +1,34 @@
+use crate::{
+	types::{ColumnInfo, Row},
+	Value,
+};
+
+/// Those of a source's columns which a query needs, where it doesn't need all of them
+#[derive(Debug, Clone)]
+pub struct Projection {
+	/// Positions of the needed columns amongst all of the source's columns
+	pub indexes: Vec<usize>,
+	pub names: Vec<String>,
+}
+
+impl Projection {
+	/// Gives `None` where every column is needed
+	pub fn new(columns: &[ColumnInfo], needed: impl Fn(&ColumnInfo) -> bool) -> Option<Self> {
+		let (indexes, names): (Vec<usize>, Vec<String>) = columns
+			.iter()
+			.enumerate()
+			.filter(|(_, column)| needed(column))
+			.map(|(index, column)| (index, column.name.clone()))
+			.unzip();
+		(indexes.len() != columns.len()).then_some(Self { indexes, names })
+	}
+	pub fn row(&self, row: &Row) -> Row {
+		self.indexes
+			.iter()
+			.map(|index| row.get(*index).cloned().unwrap_or(Value::Null))
+			.collect()
+	}
+	pub fn rows(&self, rows: Vec<Row>) -> Vec<Row> {
+		rows.iter().map(|row| self.row(row)).collect()
+	}
+}
@@ -3,10 +3,12 @@ use {
 	crate::{
 		recipe::MetaRecipe,
 		types::{Alias, ObjectName},
-		Glue, Result,
+		Glue, Result, DISTINCT_ON,
 	},
 	serde::Serialize,
-	sqlparser::ast::{Expr, Ident, Select, SelectItem as SelectItemAst},
+	sqlparser::ast::{
+		Expr, Function, FunctionArg, FunctionArgExpr, Ident, Select, SelectItem as SelectItemAst,
+	},
 	std::fmt::Debug,
 	thiserror::Error as ThisError,
 };
@@ -23,13 +25,18 @@ pub enum ManualError {
 	Unreachable,
 }
 
+#[derive(Debug, Clone)]
 pub struct Manual {
 	pub joins: Vec<JoinManual>,
 	pub select_items: Vec<SelectItem>,
 	pub constraint: MetaRecipe,
 	pub group_constraint: MetaRecipe,
 	pub groups: Vec<MetaRecipe>,
+	pub distinct: bool,
+	/// Keys of `DISTINCT ON (...)`
+	pub distinct_on: Vec<MetaRecipe>,
 }
+#[derive(Debug, Clone)]
 pub enum SelectItem {
 	Recipe(MetaRecipe, Alias),
 	Wildcard(Option<ObjectName>),
@@ -43,8 +50,8 @@ impl Manual {
 			selection,
 			group_by,
 			having,
+			distinct,
 			// TODO (below)
-			distinct: _,
 			top: _,
 			lateral_views: _,
 			cluster_by: _,
@@ -54,18 +61,47 @@ impl Manual {
 		} = select;
 
 		let constraint = selection
-			.map(|selection| MetaRecipe::new(selection)?.simplify_by_tempdb(&glue.tempdb))
+			.map(|selection| {
+				MetaRecipe::new(selection, &glue.functions)?.simplify_by_tempdb(&glue.tempdb)
+			})
 			.unwrap_or(Ok(MetaRecipe::TRUE))?;
 
 		let group_constraint = having
-			.map(|having| MetaRecipe::new(having)?.simplify_by_tempdb(&glue.tempdb))
+			.map(|having| {
+				MetaRecipe::new(having, &glue.functions)?.simplify_by_tempdb(&glue.tempdb)
+			})
 			.unwrap_or(Ok(MetaRecipe::TRUE))?;
 
 		let groups = group_by
 			.into_iter()
-			.map(|expression| MetaRecipe::new(expression)?.simplify_by_tempdb(&glue.tempdb))
+			.map(|expression| {
+				MetaRecipe::new(expression, &glue.functions)?.simplify_by_tempdb(&glue.tempdb)
+			})
 			.collect::<Result<Vec<MetaRecipe>>>()?;
 
+		let mut projection = projection;
+		let distinct_on = match projection.first() {
+			Some(SelectItemAst::UnnamedExpr(Expr::Function(function)))
+				if distinct
+					&& matches!(function.name.0.as_slice(), [Ident { value, quote_style: None }] if value == DISTINCT_ON) =>
+			{
+				match projection.remove(0) {
+					SelectItemAst::UnnamedExpr(Expr::Function(Function { args, .. })) => args
+						.into_iter()
+						.map(|argument| match argument {
+							FunctionArg::Unnamed(FunctionArgExpr::Expr(argument)) => {
+								MetaRecipe::new(argument, &glue.functions)?
+									.simplify_by_tempdb(&glue.tempdb)
+							}
+							_ => Err(ManualError::UncaughtASTError(argument.to_string()).into()),
+						})
+						.collect::<Result<Vec<MetaRecipe>>>()?,
+					_ => return Err(ManualError::Unreachable.into()),
+				}
+			}
+			_ => vec![],
+		};
+
 		let (select_items, _subqueries): (Vec<SelectItem>, Vec<Vec<JoinManual>>) = projection
 			.into_iter()
 			.map(|select_item| convert_select_item(glue, select_item))
@@ -76,11 +112,11 @@ impl Manual {
 		let joins = from
 			.into_iter()
 			.map(|from| {
-				let main = JoinManual::new_implicit_join(from.relation)?;
+				let main = JoinManual::new_implicit_join(from.relation, glue)?;
 				let mut joins = from
 					.joins
 					.into_iter()
-					.map(|join| JoinManual::new(join, &glue.tempdb))
+					.map(|join| JoinManual::new(join, glue))
 					.collect::<Result<Vec<JoinManual>>>()?;
 				joins.push(main);
 				Ok(joins)
@@ -99,6 +135,8 @@ impl Manual {
 			constraint,
 			group_constraint,
 			groups,
+			distinct,
+			distinct_on,
 		})
 	}
 }
@@ -128,7 +166,8 @@ fn convert_select_item(
 				SelectItemAst::ExprWithAlias { expr, alias } => (expr, Some(alias.value)),
 				_ => unreachable!(),
 			};
-			let recipe = MetaRecipe::new(expression)?.simplify_by_tempdb(&glue.tempdb)?;
+			let recipe =
+				MetaRecipe::new(expression, &glue.functions)?.simplify_by_tempdb(&glue.tempdb)?;
 			let subqueries = recipe.meta.subqueries.clone();
 			(SelectItem::Recipe(recipe, alias), subqueries)
 		}
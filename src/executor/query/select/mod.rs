@@ -4,16 +4,18 @@ mod order;
 mod plan;
 
 use {
+	super::Scope,
 	crate::{
 		macros::try_option,
 		recipe::{PlannedRecipe, RecipeUtilities},
 		types::{LabelsAndRows, Row},
-		Glue, Result, Value,
+		DistinctKey, Glue, Result, Value,
 	},
 	futures::stream::{self, StreamExt, TryStreamExt},
 	rayon::prelude::*,
 	serde::Serialize,
 	sqlparser::ast::{OrderByExpr, Select},
+	std::collections::{hash_map::Entry, HashMap},
 	thiserror::Error as ThisError,
 };
 pub use {
@@ -33,6 +35,15 @@ pub enum SelectError {
 	#[error("HAVING does not yet support aggregates")]
 	UnimplementedAggregateHaving,
 
+	#[error("DISTINCT ON does not yet support aggregates")]
+	UnimplementedDistinctOnAggregate,
+
+	#[error("ORDER BY of grouped rows should be of selected columns")]
+	UnimplementedOrderOfUngrouped,
+
+	#[error("ORDER BY position {0} is not in the select list")]
+	OrderPositionOutOfRange(String),
+
 	#[error("this should be impossible, please report")]
 	UnreachableFinalSolveFailure,
 	#[error("this should be impossible, please report")]
@@ -47,18 +58,35 @@ impl Glue {
 			constraint,
 			group_constraint,
 			groups,
-			order_by,
+			mut order_by,
+			top,
+			distinct,
+			distinct_on,
 			labels,
+			outer_references: _,
 		} = plan;
-		let rows = stream::iter(joins)
+		let mut rows = stream::iter(joins)
 			.map(Ok)
 			.try_fold(vec![], |rows, join| async {
 				join.execute(self, rows).await
 			})
 			.await?;
 
-		let rows = order_by.execute(rows)?; // TODO: This should be done after filtering
-
+		let do_group = !groups.is_empty()
+			|| select_items
+				.iter()
+				.any(|select_item| !select_item.aggregates.is_empty());
+		if do_group && !distinct_on.is_empty() {
+			return Err(SelectError::UnimplementedDistinctOnAggregate.into());
+		}
+		let order_selected = order_by.prefer_selected(&select_items);
+		if do_group && !order_selected {
+			if order_by.any_selected() {
+				return Err(SelectError::UnimplementedOrderOfUngrouped.into());
+			}
+			// Rows are instead ordered as they are given to aggregates, as for `ARRAY_AGG`
+			rows = order_by.execute(rows)?;
+		}
 		let selected_rows =
 			rows.into_par_iter()
 				.filter_map(|row| match constraint.confirm_constraint(&row) {
@@ -73,10 +101,8 @@ impl Glue {
 					Ok(false) => None,
 					Err(error) => Some(Err(error)),
 				});
-		let do_group = !groups.is_empty()
-			|| select_items
-				.iter()
-				.any(|select_item| !select_item.aggregates.is_empty());
+		// Only the first rows are needed, unless some are yet to be removed
+		let top = top.filter(|_| !distinct && distinct_on.is_empty());
 
 		let final_rows = if do_group {
 			let groups = if groups.is_empty() {
@@ -119,18 +145,51 @@ impl Glue {
 						.collect::<Result<Vec<Value>>>()
 				})
 				.collect::<Result<Vec<Vec<Value>>>>()?
+				.into_par_iter()
+				.map(|row| Ok((order_by.values(&row, &row)?, row)))
+				.collect::<Result<Vec<(Vec<Value>, Row)>>>()
+				.map(|rows| {
+					if order_selected {
+						order_by.sort(rows, top)
+					} else {
+						rows.into_iter().map(|(_, row)| row).collect()
+					}
+				})?
 		// TODO: Manage grouper and constraint
 		} else {
-			selected_rows
+			let keyed_rows = selected_rows
 				.map(|selection| {
-					selection.and_then(|(selection, _)| {
-						selection
+					selection.and_then(|(selection, row)| {
+						let selected = selection
 							.into_iter()
 							.map(|selected| selected.confirm())
-							.collect::<Result<Row>>()
+							.collect::<Result<Row>>()?;
+						let key = distinct_on
+							.iter()
+							.map(|key| key.clone().simplify_by_row(&row)?.confirm())
+							.collect::<Result<Row>>()?;
+						let order = order_by.values(&row, &selected)?;
+						Ok((order, (DistinctKey::of_row(&key), selected)))
 					})
 				})
-				.collect::<Result<Vec<Row>>>()?
+				.collect::<Result<Vec<(Vec<Value>, (Vec<DistinctKey>, Row))>>>()?;
+			let keyed_rows = order_by.sort(keyed_rows, top);
+			if distinct_on.is_empty() {
+				keyed_rows.into_iter().map(|(_, row)| row).collect()
+			} else {
+				first_of_keys(keyed_rows)
+			}
+		};
+
+		let final_rows = if distinct {
+			first_of_keys(
+				final_rows
+					.into_par_iter()
+					.map(|row| (DistinctKey::of_row(&row), row))
+					.collect(),
+			)
+		} else {
+			final_rows
 		};
 
 		Ok((labels, final_rows))
@@ -139,12 +198,42 @@ impl Glue {
 		&self,
 		query: Select,
 		order_by: Vec<OrderByExpr>,
+		scope: &Scope,
 	) -> Result<LabelsAndRows> {
-		let plan = Plan::new(self, query, order_by).await?;
+		let plan = Plan::new(self, query, order_by, scope, &Outer::None).await?;
 		self.select(plan).await
 	}
 }
 
+/// Keeps the first row of each key, in the order they were given; as for `DISTINCT` and `DISTINCT ON`
+fn first_of_keys(keyed_rows: Vec<(Vec<DistinctKey>, Row)>) -> Vec<Row> {
+	let mut firsts = keyed_rows
+		.into_par_iter()
+		.enumerate()
+		.fold(HashMap::new, |mut firsts, (index, (key, row))| {
+			firsts.entry(key).or_insert((index, row));
+			firsts
+		})
+		.reduce(HashMap::new, |mut firsts, others| {
+			others
+				.into_iter()
+				.for_each(|(key, (index, row))| match firsts.entry(key) {
+					Entry::Occupied(mut first) if index < first.get().0 => {
+						first.insert((index, row));
+					}
+					Entry::Occupied(_) => (),
+					Entry::Vacant(first) => {
+						first.insert((index, row));
+					}
+				});
+			firsts
+		})
+		.into_values()
+		.collect::<Vec<(usize, Row)>>();
+	firsts.par_sort_unstable_by_key(|(index, _)| *index);
+	firsts.into_iter().map(|(_, row)| row).collect()
+}
+
 #[allow(clippy::type_complexity)] // TODO
 fn accumulate(
 	mut rows_l: Vec<(Vec<Value>, Option<PlannedRecipe>, Vec<PlannedRecipe>)>,
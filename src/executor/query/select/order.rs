@@ -1,76 +1,186 @@
 use {
+	super::SelectError,
 	crate::{
-		recipe::{MetaRecipe, PlannedRecipe, RecipeUtilities},
+		recipe::{FunctionRegistry, MetaRecipe, PlannedRecipe, RecipeUtilities},
 		types::{ColumnInfo, Row},
 		Result, Value,
 	},
 	rayon::prelude::*,
-	sqlparser::ast::OrderByExpr,
-	std::cmp::Ordering,
+	sqlparser::ast::{Expr, OrderByExpr, Value as AstValue},
+	std::{cmp::Ordering, collections::BinaryHeap},
 };
 
 pub struct Order(Vec<PlannedOrderItem>);
 impl Order {
-	pub fn new(order_by: Vec<OrderByExpr>, columns: &[ColumnInfo]) -> Result<Self> {
+	/// Terms may be expressions of `columns`, or name (by label/alias) or number (from 1) a selected column
+	pub fn new(
+		order_by: Vec<OrderByExpr>,
+		columns: &[ColumnInfo],
+		labels: &[String],
+		functions: &FunctionRegistry,
+	) -> Result<Self> {
 		let order_items = order_by
 			.into_iter()
-			.map(|order_by_item| PlannedOrderItem::new(order_by_item, columns))
+			.map(|order_by_item| PlannedOrderItem::new(order_by_item, columns, labels, functions))
 			.collect::<Result<Vec<PlannedOrderItem>>>()?;
 		Ok(Order(order_items))
 	}
-	pub fn execute(self, rows: Vec<Row>) -> Result<Vec<Row>> {
-		// TODO: Optimise
-		if self.0.is_empty() {
-			return Ok(rows);
+	pub fn is_empty(&self) -> bool {
+		self.0.is_empty()
+	}
+	/// Terms which are expressions of columns are replaced by the selected column of the same recipe, where there is one.
+	/// Returns whether all terms are now of selected columns, as they need be to order grouped rows.
+	pub fn prefer_selected(&mut self, select_items: &[PlannedRecipe]) -> bool {
+		self.0.iter_mut().all(|PlannedOrderItem(_, key)| {
+			if let OrderKey::Recipe(recipe) = key {
+				if let Some(index) = select_items.iter().position(|select_item| {
+					select_item.recipe == recipe.recipe
+						&& select_item.needed_column_indexes == recipe.needed_column_indexes
+				}) {
+					*key = OrderKey::Selected(index);
+				}
+			}
+			matches!(key, OrderKey::Selected(_))
+		})
+	}
+	pub fn any_selected(&self) -> bool {
+		self.0
+			.iter()
+			.any(|PlannedOrderItem(_, key)| matches!(key, OrderKey::Selected(_)))
+	}
+	/// Values by which to order; `row` being that from which `selected` was selected
+	pub fn values(&self, row: &Row, selected: &[Value]) -> Result<Vec<Value>> {
+		self.0
+			.iter()
+			.map(|PlannedOrderItem(_, key)| match key {
+				OrderKey::Recipe(recipe) => recipe.clone().simplify_by_row(row)?.confirm(),
+				OrderKey::Selected(index) => {
+					Ok(selected.get(*index).cloned().unwrap_or(Value::Null))
+				}
+			})
+			.collect()
+	}
+	/// Sorts by values found with [Order::values]; rows of equal values keep their order.
+	/// Where `top` is given only that many rows are needed, these are kept in a bounded heap rather than sorting all rows.
+	pub fn sort<T: Send>(&self, rows: Vec<(Vec<Value>, T)>, top: Option<usize>) -> Vec<T> {
+		if self.is_empty() {
+			return rows
+				.into_iter()
+				.take(top.unwrap_or(usize::MAX))
+				.map(|(_, row)| row)
+				.collect();
 		}
-
-		let (order_terms, order_item_recipes): (Vec<OrderTerm>, Vec<PlannedRecipe>) = self
-			.0
+		let terms = OrderTerms(
+			self.0
+				.iter()
+				.map(|PlannedOrderItem(term, _)| term.clone())
+				.collect(),
+		);
+		let ranked = rows
 			.into_iter()
-			.map(|planned_order_item| {
-				let PlannedOrderItem(order_term, recipe) = planned_order_item;
-				(order_term, recipe)
-			})
-			.unzip();
-		let order_terms = OrderTerms(order_terms);
-
-		let mut order_rows = rows
+			.enumerate()
+			.map(|(index, (values, row))| Ranked {
+				terms: &terms,
+				values,
+				index,
+				row,
+			});
+		let mut ranked: Vec<Ranked<T>> = if let Some(top) = top {
+			ranked
+				.par_bridge()
+				.fold(BinaryHeap::new, |heap, ranked| {
+					bounded_push(heap, ranked, top)
+				})
+				.reduce(BinaryHeap::new, |heap, other| {
+					other
+						.into_iter()
+						.fold(heap, |heap, ranked| bounded_push(heap, ranked, top))
+				})
+				.into_vec()
+		} else {
+			ranked.collect()
+		};
+		ranked.par_sort_unstable();
+		ranked.into_iter().map(|ranked| ranked.row).collect()
+	}
+	/// Orders rows by themselves, as where they are both the source and selection
+	pub fn execute(&self, rows: Vec<Row>) -> Result<Vec<Row>> {
+		if self.is_empty() {
+			return Ok(rows);
+		}
+		let rows = rows
 			.into_par_iter()
-			.map(|row| {
-				let order_row = order_item_recipes
-					.clone()
-					.into_iter()
-					.map(|recipe| recipe.simplify_by_row(&row)?.confirm())
-					.collect::<Result<Vec<Value>>>();
-				order_row.map(|order_row| (row, order_row))
-			})
-			.collect::<Result<Vec<(Row, Vec<Value>)>>>()?;
+			.map(|row| Ok((self.values(&row, &row)?, row)))
+			.collect::<Result<Vec<(Vec<Value>, Row)>>>()?;
+		Ok(self.sort(rows, None))
+	}
+}
 
-		order_rows.par_sort_unstable_by(|(_, order_row_a), (_, order_row_b)| {
-			order_terms.sort(order_row_a, order_row_b)
-		});
-		Ok(order_rows.into_iter().map(|(row, _)| row).collect())
+/// Keeps the `top` least, the greatest being at the top of the heap
+fn bounded_push<'a, T>(
+	mut heap: BinaryHeap<Ranked<'a, T>>,
+	ranked: Ranked<'a, T>,
+	top: usize,
+) -> BinaryHeap<Ranked<'a, T>> {
+	if heap.len() < top {
+		heap.push(ranked);
+	} else if let Some(mut greatest) = heap.peek_mut() {
+		if ranked < *greatest {
+			*greatest = ranked;
+		}
 	}
+	heap
+}
+
+enum OrderKey {
+	Recipe(PlannedRecipe),
+	Selected(usize),
 }
 
-struct PlannedOrderItem(OrderTerm, PlannedRecipe);
+struct PlannedOrderItem(OrderTerm, OrderKey);
 impl PlannedOrderItem {
-	pub fn new(order_by_item: OrderByExpr, columns: &[ColumnInfo]) -> Result<Self> {
+	pub fn new(
+		order_by_item: OrderByExpr,
+		columns: &[ColumnInfo],
+		labels: &[String],
+		functions: &FunctionRegistry,
+	) -> Result<Self> {
 		let OrderByExpr {
 			expr,
 			asc,
 			nulls_first,
 		} = order_by_item;
-		let recipe = PlannedRecipe::new(MetaRecipe::new(expr)?.simplify_by_basic()?, columns)?;
+		let selected = match &expr {
+			Expr::Value(AstValue::Number(number, _)) => {
+				let position = number
+					.parse::<usize>()
+					.ok()
+					.filter(|position| (1..=labels.len()).contains(position))
+					.ok_or_else(|| SelectError::OrderPositionOutOfRange(number.clone()))?;
+				Some(position - 1)
+			}
+			Expr::Identifier(_) | Expr::CompoundIdentifier(_) => {
+				let name = expr.to_string();
+				labels.iter().position(|label| label == &name)
+			}
+			_ => None,
+		};
+		let key = if let Some(index) = selected {
+			OrderKey::Selected(index)
+		} else {
+			OrderKey::Recipe(PlannedRecipe::new(
+				MetaRecipe::new(expr, functions)?.simplify_by_basic()?,
+				columns,
+			)?)
+		};
 		let is_asc = asc.unwrap_or(true);
-		let prefer_nulls = nulls_first.unwrap_or(false);
 
 		Ok(PlannedOrderItem(
 			OrderTerm {
 				is_asc,
-				prefer_nulls,
+				nulls_first,
 			},
-			recipe,
+			key,
 		))
 	}
 }
@@ -78,18 +188,25 @@ impl PlannedOrderItem {
 #[derive(Clone)]
 struct OrderTerm {
 	pub is_asc: bool,
-	pub prefer_nulls: bool,
+	/// Where not given, `NULL` is ordered as the least value
+	pub nulls_first: Option<bool>,
 }
 impl OrderTerm {
 	pub fn sort(&self, order_item_a: &Value, order_item_b: &Value) -> Option<Ordering> {
+		let nulls_first = self.nulls_first.unwrap_or(self.is_asc);
 		let order = match (order_item_a, order_item_b) {
-			(Value::Null, Value::Null) => Ordering::Equal,
+			(Value::Null, Value::Null) => return None,
 			(Value::Null, _) | (_, Value::Null) => {
-				if self.prefer_nulls {
+				let order = if nulls_first {
+					Ordering::Less
+				} else {
 					Ordering::Greater
+				};
+				return Some(if matches!(order_item_a, Value::Null) {
+					order
 				} else {
-					Ordering::Less
-				}
+					order.reverse()
+				});
 			}
 			(other_a, other_b) => other_a.partial_cmp(other_b).unwrap_or(Ordering::Equal),
 		};
@@ -110,10 +227,36 @@ impl OrderTerms {
 		order_items_a
 			.iter()
 			.zip(order_items_b)
-			.zip(self.0.clone())
+			.zip(self.0.iter())
 			.find_map(|((order_item_a, order_item_b), order_term)| {
 				order_term.sort(order_item_a, order_item_b)
 			})
 			.unwrap_or(Ordering::Equal)
 	}
 }
+
+/// A row with its values to order by; ties are broken by the order in which rows were given
+struct Ranked<'a, T> {
+	terms: &'a OrderTerms,
+	values: Vec<Value>,
+	index: usize,
+	row: T,
+}
+impl<T> Ord for Ranked<'_, T> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.terms
+			.sort(&self.values, &other.values)
+			.then(self.index.cmp(&other.index))
+	}
+}
+impl<T> PartialOrd for Ranked<'_, T> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+impl<T> PartialEq for Ranked<'_, T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+impl<T> Eq for Ranked<'_, T> {}
@@ -2,10 +2,19 @@ mod organise_joins;
 mod refine_item;
 pub(crate) use refine_item::*;
 use {
-	super::{join::JoinExecute, Manual, Order, SelectItem},
-	crate::{recipe::PlannedRecipe, Glue, Result},
+	super::{
+		super::Scope,
+		join::{JoinExecute, JoinPlan, JoinSource},
+		Manual, Order, SelectItem,
+	},
+	crate::{
+		recipe::{MetaRecipe, PlannedRecipe},
+		types::{ColumnInfo, ObjectName},
+		Glue, Result, Value,
+	},
 	serde::Serialize,
 	sqlparser::ast::{OrderByExpr, Select},
+	std::mem::take,
 	thiserror::Error as ThisError,
 };
 
@@ -16,7 +25,33 @@ pub struct Plan {
 	pub groups: Vec<PlannedRecipe>,
 	pub group_constraint: PlannedRecipe,
 	pub order_by: Order,
+	/// Where limited, the number of rows needed; ordering need only find these
+	pub top: Option<usize>,
+	pub distinct: bool,
+	/// Keys of `DISTINCT ON (...)`, of which the first row is kept
+	pub distinct_on: Vec<PlannedRecipe>,
 	pub labels: Vec<String>,
+	/// Columns which were found in the [Outer] row
+	pub outer_references: Vec<ObjectName>,
+}
+
+/// Values from outside of a query which its columns may reference, as for `LATERAL` subqueries.
+/// Columns of the query itself take precedence.
+pub enum Outer<'a> {
+	None,
+	/// Values are not yet known; references are noted and given `NULL`
+	Unknown,
+	Row(&'a [ColumnInfo], &'a [Value]),
+}
+
+/// A query of which tables are found and joins arranged, but of which references to the [Outer] row are yet to be bound.
+/// `LATERAL` subqueries are arranged once, and bound for each outer row.
+#[derive(Debug, Clone)]
+pub struct Arrangement {
+	manual: Manual,
+	requested_joins: Vec<(usize, JoinPlan)>,
+	columns: Vec<ColumnInfo>,
+	order_by: Vec<OrderByExpr>,
 }
 
 #[derive(ThisError, Serialize, Debug, PartialEq)]
@@ -30,24 +65,167 @@ pub enum PlanError {
 }
 
 impl Plan {
-	pub async fn new(glue: &Glue, select: Select, order_by: Vec<OrderByExpr>) -> Result<Plan> {
-		let Manual {
-			joins,
-			select_items,
-			constraint,
-			group_constraint,
-			groups,
-		} = Manual::new(glue, select)?;
+	pub async fn new(
+		glue: &Glue,
+		select: Select,
+		order_by: Vec<OrderByExpr>,
+		scope: &Scope,
+		outer: &Outer<'_>,
+	) -> Result<Plan> {
+		Self::arrange(glue, select, order_by, scope)
+			.await?
+			.bind(glue, outer)
+	}
+	pub async fn arrange(
+		glue: &Glue,
+		select: Select,
+		order_by: Vec<OrderByExpr>,
+		scope: &Scope,
+	) -> Result<Arrangement> {
+		let mut manual = Manual::new(glue, select)?;
+		let (requested_joins, columns) = glue.arrange_joins(take(&mut manual.joins), scope).await?;
+		Ok(Arrangement {
+			manual,
+			requested_joins,
+			columns,
+			order_by,
+		})
+	}
+}
 
-		let (requested_joins, columns) = glue.arrange_joins(joins).await?;
+impl Arrangement {
+	/// Plans the query, giving values to references to the [Outer] row
+	pub fn bind(self, glue: &Glue, outer: &Outer<'_>) -> Result<Plan> {
+		let Arrangement {
+			manual:
+				Manual {
+					joins: _,
+					select_items,
+					constraint,
+					group_constraint,
+					groups,
+					distinct,
+					distinct_on,
+				},
+			mut requested_joins,
+			columns,
+			order_by,
+		} = self;
+
+		let mut outer_references = vec![];
+		let mut bind_outer =
+			|recipe: MetaRecipe| recipe.simplify_by_outer(&columns, outer, &mut outer_references);
+		let constraint = bind_outer(constraint)?;
+		let group_constraint = bind_outer(group_constraint)?;
+		let groups = groups
+			.into_iter()
+			.map(&mut bind_outer)
+			.collect::<Result<Vec<MetaRecipe>>>()?;
+		let distinct_on = distinct_on
+			.into_iter()
+			.map(&mut bind_outer)
+			.collect::<Result<Vec<MetaRecipe>>>()?;
+		let select_items = select_items
+			.into_iter()
+			.map(|select_item| match select_item {
+				SelectItem::Recipe(recipe, alias) => {
+					Ok(SelectItem::Recipe(bind_outer(recipe)?, alias))
+				}
+				wildcard => Ok(wildcard),
+			})
+			.collect::<Result<Vec<SelectItem>>>()?;
+		for (_, join) in requested_joins.iter_mut() {
+			join.constraint = bind_outer(join.constraint.clone())?;
+		}
+
+		// Only columns which are referenced are read
+		let mut referenced: Vec<ObjectName> = [&constraint, &group_constraint]
+			.into_iter()
+			.chain(groups.iter())
+			.chain(distinct_on.iter())
+			.chain(
+				select_items
+					.iter()
+					.filter_map(|select_item| match select_item {
+						SelectItem::Recipe(recipe, _) => Some(recipe),
+						SelectItem::Wildcard(_) => None,
+					}),
+			)
+			.chain(requested_joins.iter().map(|(_, join)| &join.constraint))
+			.flat_map(|recipe| recipe.meta.objects.iter().flatten().cloned())
+			.collect();
+		for (_, join) in requested_joins.iter() {
+			match &join.source {
+				JoinSource::Function(function) => referenced.extend(
+					function
+						.arguments
+						.iter()
+						.flat_map(|argument| argument.meta.objects.iter().flatten().cloned()),
+				),
+				JoinSource::Derived(derived) => {
+					referenced.extend(derived.outer_references.iter().cloned())
+				}
+				_ => (),
+			}
+		}
+		for order_by_item in order_by.iter() {
+			// Terms which aren't recipes of columns are found, or fail, when ordering is planned
+			if let Ok(recipe) = MetaRecipe::new(order_by_item.expr.clone(), &glue.functions) {
+				referenced.extend(recipe.meta.objects.into_iter().flatten());
+			}
+		}
+		let wildcards: Vec<&Option<ObjectName>> = select_items
+			.iter()
+			.filter_map(|select_item| match select_item {
+				SelectItem::Wildcard(specifier) => Some(specifier),
+				SelectItem::Recipe(..) => None,
+			})
+			.collect();
+		for (_, join) in requested_joins.iter_mut() {
+			join.project(|column| {
+				referenced.iter().any(|object| column == object)
+					|| wildcards
+						.iter()
+						.any(|specifier| wildcard_includes(specifier, column))
+			});
+		}
+		let columns: Vec<ColumnInfo> = requested_joins
+			.iter()
+			.flat_map(|(_, join)| join.columns.iter().cloned())
+			.collect();
 
 		let (constraint, mut index_filters) = PlannedRecipe::new_constraint(constraint, &columns)?;
 
+		// Joins which may fill a table with NULLs can't have predicates which accept NULL pushed down to it
+		let fills_right = requested_joins
+			.iter()
+			.enumerate()
+			.map(|(position, (_, join))| position != 0 && join.join_type.includes_left())
+			.collect::<Vec<bool>>();
+		let fills_left = requested_joins
+			.iter()
+			.map(|(_, join)| join.join_type.includes_right())
+			.collect::<Vec<bool>>();
+		let predicates = constraint.predicates(&columns);
+		let mut offset = 0;
+
 		let mut joins = requested_joins
 			.into_iter()
-			.map(|(_, join)| {
+			.enumerate()
+			.map(|(position, (_, join))| {
 				let index_filter = index_filters.remove(&join.table);
-				JoinExecute::new(join, &columns, index_filter)
+				let own_columns = offset..(offset + join.columns.len());
+				offset = own_columns.end;
+				let may_be_null = fills_right[position]
+					|| fills_left.iter().skip(position + 1).any(|fills| *fills);
+				let own_predicates = predicates
+					.iter()
+					.filter(|(column, predicate)| {
+						own_columns.contains(column) && !(may_be_null && predicate.accepts_null())
+					})
+					.map(|(_, predicate)| predicate.clone())
+					.collect();
+				JoinExecute::new(join, &columns, index_filter, own_predicates)
 			})
 			.collect::<Result<Vec<JoinExecute>>>()?;
 
@@ -57,14 +235,19 @@ impl Plan {
 
 		let include_table = joins.len() != 1;
 		let select_items = refine_items(select_items, &columns, include_table)?;
-		let (select_items, labels) = select_items.into_iter().unzip();
+		let (select_items, labels): (Vec<PlannedRecipe>, Vec<String>) =
+			select_items.into_iter().unzip();
 
 		let group_constraint = PlannedRecipe::new(group_constraint, &columns)?;
 		let groups = groups
 			.into_iter()
 			.map(|group| PlannedRecipe::new(group, &columns))
 			.collect::<Result<Vec<PlannedRecipe>>>()?;
-		let order_by = Order::new(order_by, &columns)?;
+		let order_by = Order::new(order_by, &columns, &labels, &glue.functions)?;
+		let distinct_on = distinct_on
+			.into_iter()
+			.map(|key| PlannedRecipe::new(key, &columns))
+			.collect::<Result<Vec<PlannedRecipe>>>()?;
 
 		Ok(Plan {
 			joins,
@@ -73,7 +256,11 @@ impl Plan {
 			groups,
 			group_constraint,
 			order_by,
+			top: None,
+			distinct,
+			distinct_on,
 			labels,
+			outer_references,
 		})
 	}
 }
@@ -1,6 +1,9 @@
 use {
 	crate::{
-		executor::query::select::join::{JoinManual, JoinPlan},
+		executor::query::{
+			select::join::{JoinManual, JoinPlan},
+			Scope,
+		},
 		types::ColumnInfo,
 		Glue, Result,
 	},
@@ -11,11 +14,12 @@ impl Glue {
 	pub(crate) async fn arrange_joins(
 		&self,
 		joins: Vec<JoinManual>,
+		scope: &Scope,
 	) -> Result<(Vec<(usize, JoinPlan)>, Vec<ColumnInfo>)> {
 		let mut joins: Vec<JoinPlan> = join_all(
 			joins
 				.into_iter()
-				.map(|join| JoinPlan::new(join, self))
+				.map(|join| JoinPlan::new(join, self, scope))
 				.collect::<Vec<_>>(),
 		)
 		.await
@@ -1,6 +1,10 @@
 use {
 	super::SelectItem,
-	crate::{recipe::PlannedRecipe, types::ColumnInfo, Error, PlanError, Result},
+	crate::{
+		recipe::PlannedRecipe,
+		types::{ColumnInfo, ObjectName},
+		Error, PlanError, Result,
+	},
 };
 
 pub(crate) fn refine_item(
@@ -15,44 +19,44 @@ pub(crate) fn refine_item(
 			let label = alias.unwrap_or_else(|| recipe.get_label(index, include_table, &columns));
 			vec![(recipe, label)]
 		}
-		SelectItem::Wildcard(specifier) => {
-			let specified_table = specifier.and_then(|specifier| specifier.get(0).cloned());
-			let matches_table = |column: &ColumnInfo| {
-				specified_table
-					.clone()
-					.map(|specified_table| {
-						column.table.name == specified_table
-							|| column
-								.table
-								.alias
-								.clone()
-								.map(|alias| alias == specified_table)
-								.unwrap_or(false)
-					})
-					.unwrap_or(true)
-			};
-			columns
-				.iter()
-				.enumerate()
-				.filter_map(|(index, column)| {
-					if matches_table(column) {
-						Some((
-							PlannedRecipe::of_index(index),
-							if include_table {
-								format!("{}.{}", column.table.name, column.name)
-							} else {
-								column.name.clone()
-							},
-						))
-					} else {
-						None
-					}
-				})
-				.collect()
-		}
+		SelectItem::Wildcard(specifier) => columns
+			.iter()
+			.enumerate()
+			.filter_map(|(index, column)| {
+				if wildcard_includes(&specifier, column) {
+					Some((
+						PlannedRecipe::of_index(index),
+						if include_table {
+							format!("{}.{}", column.table.name, column.name)
+						} else {
+							column.name.clone()
+						},
+					))
+				} else {
+					None
+				}
+			})
+			.collect(),
 	})
 }
 
+/// Whether `*`, or `table.*` where specified, includes the column
+pub(crate) fn wildcard_includes(specifier: &Option<ObjectName>, column: &ColumnInfo) -> bool {
+	specifier
+		.as_ref()
+		.and_then(|specifier| specifier.get(0))
+		.map(|specified_table| {
+			&column.table.name == specified_table
+				|| column
+					.table
+					.alias
+					.as_ref()
+					.map(|alias| alias == specified_table)
+					.unwrap_or(false)
+		})
+		.unwrap_or(true)
+}
+
 pub(crate) fn refine_items(
 	select_items: Vec<SelectItem>,
 	columns: &[ColumnInfo],
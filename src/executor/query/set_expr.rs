@@ -1,14 +1,13 @@
 use {
-	super::QueryError,
+	super::{select::Order, set_operation::set_operation, Outer, QueryError, Scope},
 	crate::{
-		macros::warning,
 		recipe::{MetaRecipe, RecipeUtilities},
 		result::Result,
-		types::LabelsAndRows,
+		types::{ColumnInfo, LabelsAndRows},
 		Error, Glue, Payload, Value,
 	},
 	async_recursion::async_recursion,
-	sqlparser::ast::{OrderByExpr, SetExpr, SetOperator, Statement},
+	sqlparser::ast::{OrderByExpr, SetExpr, Statement},
 };
 
 impl Glue {
@@ -17,16 +16,41 @@ impl Glue {
 		&mut self,
 		body: SetExpr,
 		order_by: Vec<OrderByExpr>,
+		scope: &Scope,
+	) -> Result<LabelsAndRows> {
+		match body {
+			SetExpr::Insert(Statement::Insert {
+				table_name,
+				columns,
+				source,
+				..
+			}) => {
+				let inserted = self
+					.ast_insert(&table_name, &columns, &source, true)
+					.await?;
+				if let Payload::Select { labels, rows } = inserted {
+					Ok((labels, rows.into_iter().map(|row| row.0).collect()))
+				} else {
+					unreachable!(); // TODO: Handle
+				}
+			}
+			body => self.from_set_expr(body, order_by, scope).await,
+		}
+	}
+	/// Bodies which do not alter data, as such they may be nested within other queries
+	#[async_recursion(?Send)]
+	pub async fn from_set_expr(
+		&self,
+		body: SetExpr,
+		order_by: Vec<OrderByExpr>,
+		scope: &Scope,
 	) -> Result<LabelsAndRows> {
 		match body {
 			SetExpr::Select(query) => {
-				let (labels, rows) = self.select_query(*query, order_by).await?;
+				let (labels, rows) = self.select_query(*query, order_by, scope).await?;
 				Ok((labels, rows))
 			}
 			SetExpr::Values(values) => {
-				if !order_by.is_empty() {
-					warning!("VALUES does not currently support ordering");
-				}
 				let values = values.0;
 				values
 					.into_iter()
@@ -34,7 +58,7 @@ impl Glue {
 						values_row
 							.into_iter()
 							.map(|cell| {
-								MetaRecipe::new(cell)?
+								MetaRecipe::new(cell, &self.functions)?
 									.simplify_by_tempdb(&self.tempdb)?
 									.confirm_or_err(QueryError::MissingComponentsForValues.into())
 							})
@@ -49,6 +73,7 @@ impl Glue {
 							values,
 						)
 					})
+					.and_then(|data| self.order_labelled(data, order_by))
 			}
 			SetExpr::SetOperation {
 				op,
@@ -56,46 +81,27 @@ impl Glue {
 				left,
 				right,
 			} => {
-				use SetOperator::*;
-				if !order_by.is_empty() {
-					warning!(
-					"set operations (UNION, EXCEPT & INTERSECT) do not currently support ordering"
-				);
-				}
-				let (left_labels, left) = self.from_body(*left, vec![]).await?;
-				let (right_labels, right) = self.from_body(*right, vec![]).await?;
-				if left_labels.len() != right_labels.len() {
-					return Err(QueryError::OperationColumnsMisaligned.into());
-				}
-				let mut rows = match op {
-					Union => [left, right].concat(),
-					Except => left
-						.into_iter()
-						.filter(|row| !right.contains(row))
-						.collect(),
-					Intersect => left.into_iter().filter(|row| right.contains(row)).collect(),
-				};
-				if !all {
-					rows.dedup();
-				}
-				Ok((left_labels, rows))
-			}
-			SetExpr::Insert(Statement::Insert {
-				table_name,
-				columns,
-				source,
-				..
-			}) => {
-				let inserted = self
-					.ast_insert(&table_name, &columns, &source, true)
-					.await?;
-				if let Payload::Select { labels, rows } = inserted {
-					Ok((labels, rows.into_iter().map(|row| row.0).collect()))
-				} else {
-					unreachable!(); // TODO: Handle
-				}
+				let left = self.from_set_expr(*left, vec![], scope).await?;
+				let right = self.from_set_expr(*right, vec![], scope).await?;
+				let data = set_operation(op, all, left, right)?;
+				self.order_labelled(data, order_by)
 			}
+			SetExpr::Query(query) => self.nested_query(*query, scope, &Outer::None).await,
 			_ => Err(Error::Query(QueryError::QueryNotSupported)),
 		}
 	}
+	/// Orders by the resulting columns, as for `ORDER BY` following `VALUES` or `UNION`
+	fn order_labelled(
+		&self,
+		(labels, rows): LabelsAndRows,
+		order_by: Vec<OrderByExpr>,
+	) -> Result<LabelsAndRows> {
+		let columns = labels
+			.iter()
+			.cloned()
+			.map(ColumnInfo::of_name)
+			.collect::<Vec<ColumnInfo>>();
+		let rows = Order::new(order_by, &columns, &labels, &self.functions)?.execute(rows)?;
+		Ok((labels, rows))
+	}
 }
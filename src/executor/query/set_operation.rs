@@ -0,0 +1,124 @@
+use {
+	super::QueryError,
+	crate::{
+		types::{LabelsAndRows, Row},
+		DistinctKey, Result, ValueType,
+	},
+	sqlparser::ast::SetOperator,
+	std::collections::{HashMap, HashSet},
+};
+
+/// Combines rows of either side; duplicates are removed unless `all`, otherwise as many are kept as a multiset would.
+/// Rows keep the order in which they are first found.
+pub fn set_operation(
+	op: SetOperator,
+	all: bool,
+	(labels, left): LabelsAndRows,
+	(right_labels, right): LabelsAndRows,
+) -> Result<LabelsAndRows> {
+	if labels.len() != right_labels.len() {
+		return Err(QueryError::OperationColumnsMisaligned.into());
+	}
+	check_types(&labels, &left, &right)?;
+
+	let rows = match op {
+		SetOperator::Union => {
+			let rows = left.into_iter().chain(right);
+			if all {
+				rows.collect()
+			} else {
+				distinct(rows)
+			}
+		}
+		SetOperator::Intersect => {
+			let mut right_counts = count(&right);
+			// Each matching row on the right is used once where `all`, otherwise all are used by the first
+			left.into_iter()
+				.filter(
+					|row| match right_counts.get_mut(&DistinctKey::of_row(row)) {
+						Some(count) if *count > 0 => {
+							*count = if all { *count - 1 } else { 0 };
+							true
+						}
+						_ => false,
+					},
+				)
+				.collect()
+		}
+		SetOperator::Except => {
+			let mut right_counts = count(&right);
+			let rows = left.into_iter().filter(|row| {
+				match right_counts.get_mut(&DistinctKey::of_row(row)) {
+					Some(count) if *count > 0 => {
+						if all {
+							*count -= 1;
+						}
+						false
+					}
+					_ => true,
+				}
+			});
+			if all {
+				rows.collect()
+			} else {
+				distinct(rows)
+			}
+		}
+	};
+	Ok((labels, rows))
+}
+
+/// Rows without duplicates, as by `DISTINCT`
+pub fn distinct(rows: impl IntoIterator<Item = Row>) -> Vec<Row> {
+	let mut found = HashSet::new();
+	rows.into_iter()
+		.filter(|row| found.insert(DistinctKey::of_row(row)))
+		.collect()
+}
+
+fn count(rows: &[Row]) -> HashMap<Vec<DistinctKey>, usize> {
+	rows.iter().fold(HashMap::new(), |mut counts, row| {
+		*counts.entry(DistinctKey::of_row(row)).or_insert(0) += 1;
+		counts
+	})
+}
+
+/// Columns of either side should be of the same type, though any numeric types may be combined.
+/// Each side's column is of one type, see [column_type]; columns of [ValueType::Any] may be combined with any other.
+fn check_types(labels: &[String], left: &[Row], right: &[Row]) -> Result<()> {
+	labels.iter().enumerate().try_for_each(|(index, label)| {
+		match (column_type(left, index), column_type(right, index)) {
+			(ValueType::Any, _) | (_, ValueType::Any) => Ok(()),
+			(left, right) if left == right || (is_numeric(&left) && is_numeric(&right)) => Ok(()),
+			(left, right) => Err(QueryError::OperationTypesMismatched {
+				column: label.clone(),
+				left,
+				right,
+			}
+			.into()),
+		}
+	})
+}
+
+/// The type of a column's values, `NULL`s aside; numbers of different types are of [ValueType::F64],
+/// and values of other different types, or only `NULL`s, are of [ValueType::Any]
+fn column_type(rows: &[Row], index: usize) -> ValueType {
+	rows.iter()
+		.filter_map(|row| row.get(index))
+		.map(ValueType::from)
+		.filter(|value_type| value_type != &ValueType::Any)
+		.reduce(|column_type, value_type| {
+			if column_type == value_type {
+				column_type
+			} else if is_numeric(&column_type) && is_numeric(&value_type) {
+				ValueType::F64
+			} else {
+				ValueType::Any
+			}
+		})
+		.unwrap_or(ValueType::Any)
+}
+
+fn is_numeric(value_type: &ValueType) -> bool {
+	matches!(value_type, ValueType::U64 | ValueType::I64 | ValueType::F64)
+}
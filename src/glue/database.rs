@@ -1,4 +1,5 @@
 use {
+	super::TEMPORARY_DATABASE,
 	crate::{Database, DatabaseInner, Glue, InterfaceError, Result},
 	std::sync::MutexGuard,
 };
@@ -6,17 +7,39 @@ use {
 impl Glue {
 	// TODO: None ref should give a primary
 	pub fn get_database(&self, db_ref: &Option<String>) -> Result<MutexGuard<Box<DatabaseInner>>> {
+		if db_ref.as_deref() == Some(TEMPORARY_DATABASE) {
+			return self
+				.tempdb
+				.database()
+				.ok_or(InterfaceError::DatabaseNotFound.into())
+				.map(Database::get);
+		}
 		self.databases
 			.get(db_ref.as_ref().unwrap_or(&self.primary))
 			.ok_or(InterfaceError::DatabaseNotFound.into())
 			.map(|db| db.get())
 	}
 	pub fn get_mut_database(&mut self, db_ref: &Option<String>) -> Result<&mut Box<DatabaseInner>> {
+		if db_ref.as_deref() == Some(TEMPORARY_DATABASE) {
+			return self
+				.tempdb
+				.database_mut()
+				.ok_or(InterfaceError::DatabaseNotFound.into())
+				.map(Database::get_mut);
+		}
 		self.databases
 			.get_mut(db_ref.as_ref().unwrap_or(&self.primary))
 			.ok_or(InterfaceError::DatabaseNotFound.into())
 			.map(Database::get_mut)
 	}
+	/// Where no database is given, a temporary table of the name takes precedence over the primary database
+	pub(crate) fn resolve_database(&self, database: Option<String>, table: &str) -> Option<String> {
+		database.or_else(|| {
+			self.tempdb
+				.has_table(table)
+				.then(|| TEMPORARY_DATABASE.to_string())
+		})
+	}
 	pub fn get_database_list(&self) -> Vec<&String> {
 		self.databases.keys().collect()
 	}
@@ -1,11 +1,13 @@
 use {
 	crate::{
-		parse, parse_single, CSVSettings, Connection, Database, ExecuteError, Payload, Query,
-		Result, Value, WIPError,
+		parse, parse_single, scheme_of, Accumulator, CSVCompression, CSVDirectoryDatabase,
+		CSVSettings, Cast, Connection, Database, DatabaseError, DatabaseFactory, DatabaseRegistry,
+		ExecuteError, FunctionRegistry, Payload, Query, Result, Value, ValueError, WIPError,
 	},
 	futures::executor::block_on,
 	sqlparser::ast::{
-		Expr, Ident, ObjectName, Query as AstQuery, SetExpr, Statement, Value as AstValue, Values,
+		Array, DataType, Expr, Ident, ObjectName, Query as AstQuery, SetExpr, Statement,
+		Value as AstValue, Values,
 	},
 	std::collections::HashMap,
 };
@@ -17,7 +19,11 @@ mod payload;
 mod select;
 mod tempdb;
 
-pub use {error::InterfaceError, insert::*, tempdb::TempDB};
+pub use {
+	error::InterfaceError,
+	insert::*,
+	tempdb::{TempDB, TEMPORARY_DATABASE},
+};
 
 /// # Glue
 /// Glue is *the* interface for interacting with MultiSQL; a Glue instance comprises any number of stores, each with their own identifier.
@@ -34,8 +40,14 @@ pub struct Glue {
 	pub primary: String,
 	databases: HashMap<String, Database>,
 	pub tempdb: TempDB,
+	pub(crate) functions: FunctionRegistry,
+	pub(crate) database_registry: DatabaseRegistry,
+	pub(crate) recursion_limit: usize,
 }
 
+/// Default for [`Glue::set_recursion_limit()`]
+pub const DEFAULT_RECURSION_LIMIT: usize = 1000;
+
 /// ## Creation of new interfaces
 impl Glue {
 	/// Creates a [Glue] instance with just one [Database].
@@ -51,6 +63,9 @@ impl Glue {
 		Self {
 			databases,
 			tempdb: TempDB::default(),
+			functions: FunctionRegistry::default(),
+			database_registry: DatabaseRegistry::default(),
+			recursion_limit: DEFAULT_RECURSION_LIMIT,
 			primary,
 		}
 	}
@@ -89,36 +104,90 @@ impl Glue {
 	/// ```
 	///
 	pub fn extend_many_glues(&mut self, glues: Vec<Glue>) {
+		glues.into_iter().for_each(|glue| self.extend_glue(glue))
+	}
+	/// Databases of the name [TEMPORARY_DATABASE] are left out, see [Glue::extend]
+	pub fn extend_glue(&mut self, glue: Glue) {
 		self.databases.extend(
-			glues
+			glue.databases
 				.into_iter()
-				.reduce(|mut main, other| {
-					main.databases.extend(other.databases);
-					main
-				})
-				.unwrap()
-				.databases,
+				.filter(|(database_name, _)| database_name != TEMPORARY_DATABASE),
 		)
 	}
-	pub fn extend_glue(&mut self, glue: Glue) {
-		self.databases.extend(glue.databases)
-	}
 
 	/// Extend using a ~~[Path]~~ [String] which represents a path
-	/// Guesses the type of database based on the extension
+	/// Locations with a registered scheme (`scheme://...`, see [Glue::register_database]) use that scheme's database,
+	/// `file://` locations are taken as paths;
+	/// otherwise guesses the type of database based on the extension.
+	/// Directories holding CSV files, or given as `directory/*.csv`, are of a table for each file.
+	/// CSV files may be compressed, as `.csv.gz` or `.csv.zst`; `.jsonl` and `.ndjson` files are of JSON objects, one to a line.
+	/// With the `parquet-database` feature, directories holding Parquet files, or given as `directory/*.parquet`, are of a table for each file (or directory of files).
+	/// With the `sqlite-database` feature, `.sqlite`, `.sqlite3` and `.db` files are SQLite databases.
+	/// CSV locations may be followed by options, as in `file.csv?delimiter=;&null=NA`, see [CSVSettings::from_options].
 	/// Returns [bool] of whether action was taken
 	pub fn try_extend_from_path(
 		&mut self,
 		database_name: String,
 		database_path: String,
 	) -> Result<bool> {
+		if database_name == TEMPORARY_DATABASE {
+			return Err(ExecuteError::ReservedDatabaseName(database_name).into());
+		}
 		if self.databases.contains_key(&database_name) {
 			return Ok(false);
 		}
-		let connection = if database_path.ends_with('/') {
+		let database_path = match scheme_of(&database_path) {
+			Some(scheme) if scheme.eq_ignore_ascii_case("file") => {
+				database_path[scheme.len() + "://".len()..].to_string()
+			}
+			Some(scheme) if !self.database_registry.is_registered(scheme) => {
+				return Err(DatabaseError::UnknownScheme(scheme.to_string()).into());
+			}
+			Some(_) => {
+				let database = self
+					.database_registry
+					.open(Connection::Custom(database_path))?;
+				return Ok(self.extend(database_name, database));
+			}
+			None => database_path,
+		};
+		#[cfg(feature = "parquet-database")]
+		{
+			let directory = match database_path.strip_suffix("*.parquet") {
+				Some(directory) => Some(directory.to_string()),
+				None => crate::ParquetDatabase::is_parquet_directory(&database_path)
+					.then(|| database_path.clone()),
+			};
+			if let Some(directory) = directory {
+				let database = Connection::Parquet(directory).try_into()?;
+				return Ok(self.extend(database_name, database));
+			}
+		}
+		#[cfg(feature = "sqlite-database")]
+		if crate::SQLiteDatabase::is_sqlite_path(&database_path) {
+			let database = Connection::SQLite(database_path).try_into()?;
+			return Ok(self.extend(database_name, database));
+		}
+		let (database_path, options) = match database_path.split_once('?') {
+			Some((database_path, options)) => (database_path.to_string(), Some(options)),
+			None => (database_path, None),
+		};
+		let csv_settings = || match options {
+			Some(options) => CSVSettings::from_options(options),
+			None => Ok(CSVSettings::default()),
+		};
+		let connection = if let Some(directory) = database_path.strip_suffix("*.csv") {
+			Connection::CSVDirectory(directory.to_string(), csv_settings()?)
+		} else if CSVDirectoryDatabase::is_csv_directory(&database_path) {
+			Connection::CSVDirectory(database_path, csv_settings()?)
+		} else if CSVCompression::is_csv_path(&database_path) {
+			Connection::CSV(database_path, csv_settings()?)
+		} else if options.is_some() {
+			return Err(ExecuteError::InvalidDatabaseLocation.into());
+		} else if database_path.ends_with('/') {
 			Connection::Sled(database_path)
-		} else if database_path.ends_with(".csv") {
-			Connection::CSV(database_path, CSVSettings::default())
+		} else if database_path.ends_with(".jsonl") || database_path.ends_with(".ndjson") {
+			Connection::NDJSON(database_path)
 		} else if database_path.ends_with(".xlsx") {
 			Connection::Sheet(database_path)
 		} else {
@@ -129,9 +198,11 @@ impl Glue {
 	}
 
 	/// Extend [Glue] by single database
-	/// Returns [bool] of whether action was taken
+	/// Returns [bool] of whether action was taken; databases aren't added where one is of the name,
+	/// nor where the name is [TEMPORARY_DATABASE], as that would be hidden by temporary tables.
 	pub fn extend(&mut self, database_name: String, database: Database) -> bool {
-		let database_present = self.databases.contains_key(&database_name);
+		let database_present =
+			database_name == TEMPORARY_DATABASE || self.databases.contains_key(&database_name);
 		if !database_present {
 			self.databases.insert(database_name, database);
 		}
@@ -149,6 +220,80 @@ impl Glue {
 	}
 }
 
+/// ## Custom functions
+impl Glue {
+	/// Registers a scalar function, callable from any query by (case insensitive) name.
+	/// Custom functions take precedence over built-in functions of the same name.
+	/// For example:
+	/// ```
+	/// use multisql::{Connection, Glue, Value};
+	/// let mut glue = Glue::new(String::from("main"), Connection::Memory.try_into().unwrap());
+	///
+	/// glue.register_function("double", |arguments| match arguments.as_slice() {
+	///   [Value::I64(number)] => Ok(Value::I64(number * 2)),
+	///   _ => Ok(Value::Null),
+	/// });
+	///
+	/// glue.execute_many("
+	///   CREATE TABLE numbers (number INTEGER);
+	///   INSERT INTO numbers VALUES (21);
+	/// ").unwrap();
+	///
+	/// assert_eq!(
+	///   glue.select_as_csv("SELECT DOUBLE(number) AS answer FROM numbers"),
+	///   Ok(String::from("answer\n42\n"))
+	/// );
+	/// ```
+	pub fn register_function<F>(&mut self, name: &str, function: F)
+	where
+		F: Fn(Vec<Value>) -> Result<Value> + Send + Sync + 'static,
+	{
+		self.functions.register_function(name, function)
+	}
+	/// Registers an aggregate function which takes one argument, see [Accumulator].
+	pub fn register_aggregate<A: Accumulator + 'static>(&mut self, name: &str, accumulator: A) {
+		self.functions.register_aggregate(name, accumulator)
+	}
+}
+
+/// ## Custom databases
+impl Glue {
+	/// Registers a factory for locations of the (case insensitive) scheme, as in `scheme://...`.
+	/// Once registered, such locations may be used by [Glue::try_extend_from_path] and so `CREATE DATABASE name LOCATION 'scheme://...'`.
+	/// Registering a scheme again replaces its factory.
+	///
+	/// For example:
+	/// ```
+	/// use multisql::{Connection, DatabaseInner, Glue, MemoryDatabase};
+	/// let mut glue = Glue::new(String::from("main"), Connection::Memory.try_into().unwrap());
+	///
+	/// glue.register_database("scratch", |_location: &str| {
+	///   Ok(Box::new(MemoryDatabase::new()) as Box<DatabaseInner>)
+	/// });
+	///
+	/// glue.execute_many("
+	///   CREATE DATABASE other LOCATION 'scratch://other';
+	///   CREATE TABLE other.test (id INTEGER);
+	/// ").unwrap();
+	/// ```
+	pub fn register_database<F: DatabaseFactory + 'static>(&mut self, scheme: &str, factory: F) {
+		self.database_registry.register_database(scheme, factory)
+	}
+	/// Factories registered by [Glue::register_database], as to reopen [Connection::Custom]s
+	pub fn database_registry(&self) -> &DatabaseRegistry {
+		&self.database_registry
+	}
+}
+
+/// ## Settings
+impl Glue {
+	/// Sets how many times the recursive part of a `WITH RECURSIVE` CTE may be run before failing,
+	/// this prevents cycles in data from running forever.
+	pub fn set_recursion_limit(&mut self, limit: usize) {
+		self.recursion_limit = limit;
+	}
+}
+
 impl Glue {
 	pub fn into_connections(self) -> Vec<(String, Connection)> {
 		self.databases
@@ -184,6 +329,12 @@ impl Glue {
 	pub fn pre_parse(query: &str) -> Result<Vec<Query>> {
 		parse(query).map_err(|error| WIPError::Debug(format!("{:?}", error)).into())
 	}
+	/// Will send a query, unparsed, to a database which has a language of its own (such as that of an ODBC source).
+	/// Rows come back as [Payload::Select].
+	pub fn execute_native(&mut self, database: &str, query: &str) -> Result<Payload> {
+		let database = self.get_mut_database(&Some(database.to_string()))?;
+		block_on(database.execute_native(query))
+	}
 }
 
 /// ## Insert (`INSERT`)
@@ -208,21 +359,8 @@ impl Glue {
 			.collect();
 		let sqlparser_rows: Vec<Vec<Expr>> = rows
 			.into_iter()
-			.map(|row| {
-				row.into_iter()
-					.map(|cell| {
-						Expr::Value(match cell {
-							Value::Null => AstValue::Null,
-							Value::Bool(value) => AstValue::Boolean(value),
-							Value::I64(value) => AstValue::Number(value.to_string(), false),
-							Value::F64(value) => AstValue::Number(value.to_string(), false),
-							Value::Str(value) => AstValue::SingleQuotedString(value),
-							_ => unimplemented!(),
-						})
-					})
-					.collect()
-			})
-			.collect();
+			.map(|row| row.into_iter().map(value_expr).collect())
+			.collect::<Result<_>>()?;
 		let body = SetExpr::Values(Values(sqlparser_rows));
 		let query = Query(Statement::Insert {
 			table_name, // !
@@ -246,3 +384,28 @@ impl Glue {
 		self.execute_parsed(query)
 	}
 }
+
+/// A value as an expression which is of it
+fn value_expr(value: Value) -> Result<Expr> {
+	Ok(match value {
+		Value::Null => Expr::Value(AstValue::Null),
+		Value::Bool(value) => Expr::Value(AstValue::Boolean(value)),
+		Value::U64(value) => Expr::Value(AstValue::Number(value.to_string(), false)),
+		Value::I64(value) => Expr::Value(AstValue::Number(value.to_string(), false)),
+		Value::F64(value) => Expr::Value(AstValue::Number(value.to_string(), false)),
+		Value::Str(value) => Expr::Value(AstValue::SingleQuotedString(value)),
+		Value::Bytes(value) => Expr::Value(AstValue::HexStringLiteral(hex::encode(value))),
+		Value::Timestamp(_) => Expr::Cast {
+			expr: Box::new(Expr::Value(AstValue::SingleQuotedString(value.cast()?))),
+			data_type: DataType::Timestamp,
+		},
+		Value::Array(values) => Expr::Array(Array {
+			elem: values
+				.into_iter()
+				.map(value_expr)
+				.collect::<Result<Vec<Expr>>>()?,
+			named: true,
+		}),
+		Value::Internal(_) => return Err(ValueError::CannotConvert(value, "SQL").into()),
+	})
+}
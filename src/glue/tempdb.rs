@@ -1,16 +1,28 @@
-use {crate::Value, std::collections::HashMap};
+use {
+	crate::{Database, Value},
+	std::collections::{HashMap, HashSet},
+};
 
-#[derive(Clone)]
+/// Name by which temporary tables may also be referenced, for example: `SELECT * FROM temporary.results`.
+/// No database may be of this name, see [Glue::extend](crate::Glue::extend).
+pub const TEMPORARY_DATABASE: &str = "temporary";
+
+/// State of a session which is not kept by any database: variables and temporary tables.
 pub struct TempDB {
 	variables: HashMap<String, Value>,
-	tables: HashMap<String, (Vec<String>, Vec<Vec<Value>>)>,
+	/// Tables made by `CREATE TEMPORARY TABLE`, these last as long as the [Glue](crate::Glue)
+	#[cfg(feature = "memory-database")]
+	tables: Database,
+	table_names: HashSet<String>,
 }
 
 impl Default for TempDB {
 	fn default() -> Self {
 		TempDB {
 			variables: HashMap::new(),
-			tables: HashMap::new(),
+			#[cfg(feature = "memory-database")]
+			tables: Database::new(Box::new(crate::MemoryDatabase::new())),
+			table_names: HashSet::new(),
 		}
 	}
 }
@@ -22,14 +34,30 @@ impl TempDB {
 	pub fn set_variable(&mut self, name: String, value: Value) -> Option<Value> {
 		self.variables.insert(name, value)
 	}
-	pub fn get_table(&self, name: &str) -> Option<&(Vec<String>, Vec<Vec<Value>>)> {
-		self.tables.get(name)
+	pub fn has_table(&self, name: &str) -> bool {
+		self.table_names.contains(name)
+	}
+	pub(crate) fn set_table_exists(&mut self, name: &str, exists: bool) {
+		if exists {
+			self.table_names.insert(name.to_string());
+		} else {
+			self.table_names.remove(name);
+		}
+	}
+	#[cfg(feature = "memory-database")]
+	pub(crate) fn database(&self) -> Option<&Database> {
+		Some(&self.tables)
+	}
+	#[cfg(not(feature = "memory-database"))]
+	pub(crate) fn database(&self) -> Option<&Database> {
+		None
+	}
+	#[cfg(feature = "memory-database")]
+	pub(crate) fn database_mut(&mut self) -> Option<&mut Database> {
+		Some(&mut self.tables)
 	}
-	pub fn set_table(
-		&mut self,
-		name: String,
-		data: (Vec<String>, Vec<Vec<Value>>),
-	) -> Option<(Vec<String>, Vec<Vec<Value>>)> {
-		self.tables.insert(name, data)
+	#[cfg(not(feature = "memory-database"))]
+	pub(crate) fn database_mut(&mut self) -> Option<&mut Database> {
+		None
 	}
 }
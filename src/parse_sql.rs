@@ -1,12 +1,189 @@
 pub use sqlparser::parser::ParserError;
-use sqlparser::{ast::Statement, dialect::GenericDialect, parser::Parser};
+use sqlparser::{
+	ast::Statement,
+	dialect::GenericDialect,
+	parser::Parser,
+	tokenizer::{Token, Tokenizer, Whitespace},
+};
 
 pub struct Query(pub Statement);
 
 pub fn parse(sql: &str) -> Result<Vec<Query>, ParserError> {
 	let dialect = GenericDialect {};
 
-	Parser::parse_sql(&dialect, sql).map(|parsed| parsed.into_iter().map(Query).collect())
+	let tokens = rewrite_regexp(Tokenizer::new(&dialect, sql).tokenize()?);
+	let tokens = rewrite_distinct_on(tokens);
+	let mut parser = Parser::new(tokens, &dialect);
+
+	// As with Parser::parse_sql
+	let mut statements = vec![];
+	let mut expecting_statement_delimiter = false;
+	loop {
+		while parser.consume_token(&Token::SemiColon) {
+			expecting_statement_delimiter = false;
+		}
+		if parser.peek_token() == Token::EOF {
+			break;
+		}
+		if expecting_statement_delimiter {
+			return Err(ParserError::ParserError(format!(
+				"Expected end of statement, found: {}",
+				parser.peek_token()
+			)));
+		}
+		statements.push(Query(parser.parse_statement()?));
+		expecting_statement_delimiter = true;
+	}
+	Ok(statements)
+}
+
+/// Words after which an expression begins, rather than ends
+const LEADING_KEYWORDS: [&str; 26] = [
+	"SELECT",
+	"WHERE",
+	"BY",
+	"AND",
+	"OR",
+	"NOT",
+	"ON",
+	"HAVING",
+	"WHEN",
+	"THEN",
+	"ELSE",
+	"CASE",
+	"DISTINCT",
+	"ALL",
+	"SET",
+	"COLUMN",
+	"TABLE",
+	"INTO",
+	"INDEX",
+	"RETURNING",
+	"AS",
+	"IS",
+	"IN",
+	"LIKE",
+	"BETWEEN",
+	"RECURSIVE",
+];
+/// Words which follow an expression, rather than begin one
+const TRAILING_KEYWORDS: [&str; 24] = [
+	"FROM",
+	"WHERE",
+	"GROUP",
+	"ORDER",
+	"HAVING",
+	"LIMIT",
+	"OFFSET",
+	"UNION",
+	"EXCEPT",
+	"INTERSECT",
+	"AS",
+	"ON",
+	"JOIN",
+	"INNER",
+	"LEFT",
+	"RIGHT",
+	"FULL",
+	"CROSS",
+	"AND",
+	"OR",
+	"THEN",
+	"ELSE",
+	"END",
+	"WHEN",
+];
+
+/// Supports syntax which sqlparser does not; `a REGEXP b` becomes `a ~ b`, and `a NOT REGEXP b` becomes `a !~ b`.
+/// Only words `REGEXP` between two expressions are operators, others being names.
+fn rewrite_regexp(mut tokens: Vec<Token>) -> Vec<Token> {
+	let is_word_of = |token: &Token, words: &[&str]| matches!(token, Token::Word(word) if word.quote_style.is_none() && words.iter().any(|keyword| word.value.eq_ignore_ascii_case(keyword)));
+	let is_significant = |index: &usize| !matches!(tokens[*index], Token::Whitespace(_));
+	let regexps: Vec<(usize, Option<usize>)> = (0..tokens.len())
+		.filter(|index| is_word_of(&tokens[*index], &["REGEXP"]))
+		.filter_map(|index| {
+			let before = (0..index).rev().find(is_significant);
+			let not = before.filter(|before| is_word_of(&tokens[*before], &["NOT"]));
+			let before = match not {
+				Some(not) => (0..not).rev().find(is_significant),
+				None => before,
+			};
+			let after = (index + 1..tokens.len()).find(is_significant);
+			let ends_expression = match before.map(|before| &tokens[before]) {
+				Some(token @ Token::Word(_)) => !is_word_of(token, &LEADING_KEYWORDS),
+				Some(token) => is_literal(token) || token == &Token::RParen,
+				None => false,
+			};
+			let begins_expression = match after.map(|after| &tokens[after]) {
+				Some(token @ Token::Word(_)) => !is_word_of(token, &TRAILING_KEYWORDS),
+				Some(token) => {
+					is_literal(token) || matches!(token, Token::LParen | Token::Placeholder(_))
+				}
+				None => false,
+			};
+			(ends_expression && begins_expression).then_some((index, not))
+		})
+		.collect();
+	regexps.into_iter().for_each(|(index, not)| match not {
+		Some(not) => {
+			tokens[not] = Token::Whitespace(Whitespace::Space);
+			tokens[index] = Token::ExclamationMarkTilde;
+		}
+		None => tokens[index] = Token::Tilde,
+	});
+	tokens
+}
+
+fn is_literal(token: &Token) -> bool {
+	matches!(
+		token,
+		Token::Number(..)
+			| Token::SingleQuotedString(_)
+			| Token::NationalStringLiteral(_)
+			| Token::HexStringLiteral(_)
+	)
+}
+
+/// Name of the function which `DISTINCT ON (...)` is rewritten into, see [rewrite_distinct_on].
+/// Unquoted words are never of spaces, so no function or column written in a query may be of this name.
+pub(crate) const DISTINCT_ON: &str = "DISTINCT ON";
+
+/// `SELECT DISTINCT ON (a, b) c` becomes `SELECT DISTINCT <DISTINCT ON>(a, b), c`, of a word which can't otherwise be made;
+/// the first item is then taken as the keys rather than as a column.
+fn rewrite_distinct_on(mut tokens: Vec<Token>) -> Vec<Token> {
+	let is_keyword = |token: &Token, keyword: &str| matches!(token, Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case(keyword));
+	let next_significant = |tokens: &[Token], from: usize| {
+		(from..tokens.len()).find(|index| !matches!(tokens[*index], Token::Whitespace(_)))
+	};
+	let mut index = 0;
+	while index < tokens.len() {
+		if is_keyword(&tokens[index], "DISTINCT") {
+			if let Some(on) =
+				next_significant(&tokens, index + 1).filter(|on| is_keyword(&tokens[*on], "ON"))
+			{
+				if let Some(open) =
+					next_significant(&tokens, on + 1).filter(|open| tokens[*open] == Token::LParen)
+				{
+					let mut depth = 0;
+					let close = (open..tokens.len()).find(|index| {
+						match tokens[*index] {
+							Token::LParen => depth += 1,
+							Token::RParen => depth -= 1,
+							_ => (),
+						}
+						depth == 0
+					});
+					if let Some(close) = close {
+						tokens[on] = Token::make_word(DISTINCT_ON, None);
+						tokens.insert(close + 1, Token::Comma);
+						index = close;
+					}
+				}
+			}
+		}
+		index += 1;
+	}
+	tokens
 }
 
 pub fn parse_single(sql: &str) -> Result<Query, ParserError> {
@@ -29,6 +29,11 @@ pub enum Error {
 	#[serde(with = "stringify")]
 	ODBC(#[from] odbc_api::Error),
 
+	#[cfg(feature = "sqlite-database")]
+	#[error(transparent)]
+	#[serde(with = "stringify")]
+	SQLite(#[from] rusqlite::Error),
+
 	#[error(transparent)]
 	Execute(#[from] ExecuteError),
 	#[error(transparent)]
@@ -67,6 +72,18 @@ pub enum Error {
 	SheetDatabase(#[from] SheetDatabaseError),
 	#[error(transparent)]
 	MemoryDatabase(#[from] MemoryDatabaseError),
+	#[cfg(feature = "odbc-database")]
+	#[error(transparent)]
+	ODBCDatabase(#[from] crate::ODBCDatabaseError),
+	#[cfg(feature = "ndjson-database")]
+	#[error(transparent)]
+	NDJSONDatabase(#[from] crate::NDJSONDatabaseError),
+	#[cfg(feature = "parquet-database")]
+	#[error(transparent)]
+	ParquetDatabase(#[from] crate::ParquetDatabaseError),
+	#[cfg(feature = "sqlite-database")]
+	#[error(transparent)]
+	SQLiteDatabase(#[from] crate::SQLiteDatabaseError),
 	#[error(transparent)]
 	Interface(#[from] InterfaceError),
 }
@@ -102,6 +119,14 @@ impl PartialEq for Error {
 			(SheetDatabase(l), SheetDatabase(r)) => l == r,
 			(Interface(l), Interface(r)) => l == r,
 			(MemoryDatabase(l), MemoryDatabase(r)) => l == r,
+			#[cfg(feature = "odbc-database")]
+			(ODBCDatabase(l), ODBCDatabase(r)) => l == r,
+			#[cfg(feature = "ndjson-database")]
+			(NDJSONDatabase(l), NDJSONDatabase(r)) => l == r,
+			#[cfg(feature = "parquet-database")]
+			(ParquetDatabase(l), ParquetDatabase(r)) => l == r,
+			#[cfg(feature = "sqlite-database")]
+			(SQLiteDatabase(l), SQLiteDatabase(r)) => l == r,
 			_ => false,
 		}
 	}
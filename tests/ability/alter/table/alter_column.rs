@@ -0,0 +1,82 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	glue.execute_many(
+		"
+		CREATE TABLE altered (code TEXT, label TEXT NULL);
+		INSERT INTO altered (code, label) VALUES ('1', 'one'), ('2', NULL), ('3', 'three');
+	",
+	)
+	.unwrap();
+
+	assert_eq!(
+		glue.execute("ALTER TABLE altered ALTER COLUMN label SET NOT NULL"),
+		Err(multisql::AlterError::NullValuesInColumn(String::from("label"), 1).into())
+	);
+	assert_success!(glue, "UPDATE altered SET label = 'two' WHERE code = '2'");
+	assert_success!(glue, "ALTER TABLE altered ALTER COLUMN label SET NOT NULL");
+	assert_error!(glue, "INSERT INTO altered (code, label) VALUES ('4', NULL)");
+	assert_success!(glue, "ALTER TABLE altered ALTER COLUMN label DROP NOT NULL");
+	assert_success!(glue, "INSERT INTO altered (code, label) VALUES ('4', NULL)");
+
+	assert_success!(
+		glue,
+		"ALTER TABLE altered ALTER COLUMN label SET DEFAULT 'unknown'"
+	);
+	assert_success!(glue, "INSERT INTO altered (code) VALUES ('5')");
+	assert_success!(glue, "ALTER TABLE altered ALTER COLUMN label DROP DEFAULT");
+	assert_eq!(
+		glue.select_as_csv("SELECT code, label FROM altered ORDER BY code"),
+		Ok(String::from(
+			"code,label\n1,one\n2,two\n3,three\n4,NULL\n5,unknown\n"
+		))
+	);
+
+	assert_success!(
+		glue,
+		"ALTER TABLE altered ALTER COLUMN code SET DATA TYPE INTEGER"
+	);
+	assert_select!(glue, "SELECT code FROM altered WHERE code > 3 ORDER BY code" => code = I64: (4), (5));
+	assert_eq!(
+		glue.execute("ALTER TABLE altered ALTER COLUMN label SET DATA TYPE INTEGER"),
+		Err(multisql::AlterError::ColumnConversionFailed(
+			String::from("label"),
+			String::from("Int"),
+			vec![
+				String::from("one"),
+				String::from("three"),
+				String::from("two"),
+				String::from("unknown"),
+			]
+		)
+		.into())
+	);
+
+	assert_eq!(
+		glue.execute("ALTER TABLE altered ADD CONSTRAINT altered_label_key UNIQUE (label)"),
+		Ok(multisql::Payload::AlterTable)
+	);
+	assert_error!(glue, "INSERT INTO altered (code, label) VALUES (6, 'one')");
+	assert_success!(
+		glue,
+		"ALTER TABLE altered DROP CONSTRAINT altered_label_key"
+	);
+	assert_success!(glue, "INSERT INTO altered (code, label) VALUES (6, 'one')");
+	assert_eq!(
+		glue.execute("ALTER TABLE altered ADD UNIQUE (label)"),
+		Err(multisql::AlterError::DuplicateValuesInColumn(String::from("label")).into())
+	);
+	assert_eq!(
+		glue.execute("ALTER TABLE altered DROP CONSTRAINT altered_label_key"),
+		Err(multisql::AlterError::ConstraintNotFound(String::from("altered_label_key")).into())
+	);
+	assert_success!(
+		glue,
+		"ALTER TABLE altered DROP CONSTRAINT IF EXISTS altered_label_key"
+	);
+	assert_success!(glue, "ALTER TABLE altered ADD PRIMARY KEY (code)");
+	assert_error!(
+		glue,
+		"INSERT INTO altered (code, label) VALUES (1, 'another')"
+	);
+}
@@ -0,0 +1,33 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	glue.execute_many(
+		"
+		CREATE TABLE converted (code TEXT, label TEXT, extra TEXT);
+		INSERT INTO converted (code, label, extra) VALUES ('1', 'one', 'x'), ('20', 'twenty', 'y');
+	",
+	)
+	.unwrap();
+
+	assert_eq!(
+		glue.execute("ALTER TABLE converted ALTER COLUMN label SET DATA TYPE INTEGER"),
+		Err(multisql::AlterError::ColumnConversionFailed(
+			String::from("label"),
+			String::from("Int"),
+			vec![String::from("one"), String::from("twenty")]
+		)
+		.into())
+	);
+	assert_success!(
+		glue,
+		"ALTER TABLE converted ALTER COLUMN code SET DATA TYPE INTEGER"
+	);
+
+	assert_success!(glue, "ALTER TABLE converted RENAME COLUMN label TO name");
+	assert_success!(glue, "ALTER TABLE converted DROP COLUMN extra");
+	assert_success!(glue, "ALTER TABLE converted ADD COLUMN note TEXT NULL");
+	assert_eq!(
+		glue.select_as_csv("SELECT code, name FROM converted ORDER BY code"),
+		Ok(String::from("code,name\n1,one\n20,twenty\n"))
+	);
+}
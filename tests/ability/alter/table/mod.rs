@@ -1,3 +1,6 @@
+mod alter_column;
 mod alter_table;
+mod column_type;
 mod create_table;
 mod drop_table;
+mod schema_diff;
@@ -0,0 +1,68 @@
+use {
+	crate::util::*,
+	futures::executor::block_on,
+	multisql::{Column, DatabaseError, SchemaChange, SchemaDiff, ValueType},
+};
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	glue.execute_many(
+		"
+		CREATE TABLE diffed (a TEXT, b TEXT, c TEXT, d TEXT);
+		INSERT INTO diffed (a, b, c, d) VALUES ('a1', 'b1', 'c1', 'd1'), ('a2', 'b2', 'c2', 'd2');
+	",
+	)
+	.unwrap();
+
+	let renamed = Column {
+		name: String::from("renamed"),
+		data_type: ValueType::Str,
+		is_nullable: true,
+		..Column::default()
+	};
+	let added = Column {
+		name: String::from("added"),
+		data_type: ValueType::Str,
+		is_nullable: true,
+		..Column::default()
+	};
+
+	// A change naming a column which isn't there spoils the whole diff
+	let database = glue.get_mut_database(&None).unwrap();
+	assert_eq!(
+		block_on(database.alter_table(
+			"diffed",
+			SchemaDiff::new(vec![
+				SchemaChange::ColumnRemove(String::from("a")),
+				SchemaChange::ColumnRemove(String::from("a")),
+			])
+		)),
+		Err(DatabaseError::ColumnNotFound(String::from("a")).into())
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM diffed ORDER BY a"),
+		Ok(String::from("a,b,c,d\na1,b1,c1,d1\na2,b2,c2,d2\n"))
+	);
+
+	let database = glue.get_mut_database(&None).unwrap();
+	assert_eq!(
+		block_on(database.alter_table(
+			"diffed",
+			SchemaDiff::new(vec![
+				SchemaChange::ColumnRemove(String::from("a")),
+				SchemaChange::ColumnRemove(String::from("c")),
+				SchemaChange::ColumnUpdate(String::from("d"), renamed),
+				SchemaChange::ColumnAdd(added),
+			])
+		)),
+		Ok(())
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT b, renamed FROM diffed ORDER BY b"),
+		Ok(String::from("b,renamed\nb1,d1\nb2,d2\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM diffed WHERE b = 'b1'")
+			.map(|csv| csv.lines().next().map(String::from)),
+		Ok(Some(String::from("b,renamed,added")))
+	);
+}
@@ -0,0 +1,42 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	glue.execute_many(
+		"
+		CREATE TABLE altered_rows (code TEXT, label TEXT);
+		INSERT INTO altered_rows (code, label) VALUES ('a', 'first'), ('b', 'second'), ('c', 'third'), ('d', 'fourth');
+	",
+	)
+	.unwrap();
+
+	assert_eq!(
+		glue.execute("UPDATE altered_rows SET label = 'changed' WHERE code = 'b'"),
+		Ok(multisql::Payload::Update(1))
+	);
+	assert_eq!(
+		glue.execute("DELETE FROM altered_rows WHERE code = 'a'"),
+		Ok(multisql::Payload::Delete(1))
+	);
+	// Rows are still found as they were, after those before them are gone
+	assert_eq!(
+		glue.execute("UPDATE altered_rows SET label = 'also changed' WHERE code = 'c'"),
+		Ok(multisql::Payload::Update(1))
+	);
+	assert_success!(
+		glue,
+		"INSERT INTO altered_rows (code, label) VALUES ('e', 'fifth')"
+	);
+	assert_eq!(
+		glue.execute("DELETE FROM altered_rows WHERE code = 'd' OR code = 'e'"),
+		Ok(multisql::Payload::Delete(2))
+	);
+	assert_eq!(
+		glue.execute("UPDATE altered_rows SET code = 'z' WHERE code = 'c'"),
+		Ok(multisql::Payload::Update(1))
+	);
+
+	assert_eq!(
+		glue.select_as_csv("SELECT code, label FROM altered_rows ORDER BY code"),
+		Ok(String::from("code,label\nb,changed\nz,also changed\n"))
+	);
+}
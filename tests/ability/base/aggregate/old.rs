@@ -35,6 +35,7 @@ fn test(mut glue: multisql::Glue) {
 	assert_select!(glue, "SELECT SUM(age) + SUM(quantity) FROM Item" => unnamed_0 = I64: (151));
 	// TODO: #73 assert_select!(glue, "SELECT COUNT(quantity) + COUNT(age) FROM Item" => unnamed_0 = I64: (8));
 	assert_select!(glue, "SELECT AVG(quantity) FROM Item" => unnamed_0 = I64: (9));
+	assert_select!(glue, "SELECT avg(quantity) FROM Item" => unnamed_0 = I64: (9));
 	assert_select!(glue, "SELECT SUM(1 + 2) FROM Item" => unnamed_0 = I64: (15));
 
 	assert_error!(
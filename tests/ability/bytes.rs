@@ -0,0 +1,22 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(glue, "CREATE TABLE Attachment (data BYTEA)");
+	execute!(glue, "INSERT INTO Attachment VALUES (X'0102FF')");
+
+	assert_select!(glue, "SELECT data FROM Attachment" => data = Bytes: (vec![1_u8, 2, 255]));
+	assert_select!(glue, "SELECT ENCODE(data, 'hex') AS hex, LENGTH(data) AS length FROM Attachment" => hex = Str, length = I64: (String::from("0102ff"), 3));
+
+	glue.insert_vec(
+		String::from("Attachment"),
+		vec![String::from("data")],
+		vec![vec![multisql::Value::Bytes(vec![0, 10])]],
+	)
+	.unwrap();
+	assert_select!(glue, "SELECT LENGTH(data) AS length FROM Attachment" => length = I64: (3), (2));
+
+	// Of base64 which could be taken as a number, `1E10`
+	execute!(glue, "DELETE FROM Attachment");
+	execute!(glue, "INSERT INTO Attachment VALUES (X'D44D74')");
+	assert_select!(glue, "SELECT data FROM Attachment" => data = Bytes: (vec![0xD4_u8, 0x4D, 0x74]));
+}
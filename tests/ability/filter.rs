@@ -0,0 +1,22 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(glue, "CREATE TABLE Item (name TEXT, colour TEXT)");
+	execute!(
+		glue,
+		"INSERT INTO Item VALUES ('apple', 'red'), ('Apricot', 'orange'), ('banana', 'yellow'), ('cherry', 'red'), ('date', 'brown')"
+	);
+
+	assert_select!(glue, "SELECT name FROM Item WHERE colour = 'red'" => name = Str: (String::from("apple")), (String::from("cherry")));
+	assert_select!(glue, "SELECT name FROM Item WHERE colour = 'red' AND name > 'b'" => name = Str: (String::from("cherry")));
+	assert_select!(glue, "SELECT name FROM Item WHERE 'c' < name" => name = Str: (String::from("cherry")), (String::from("date")));
+	assert_select!(glue, "SELECT name FROM Item WHERE colour <> 'red' AND name <= 'banana'" => name = Str: (String::from("Apricot")), (String::from("banana")));
+	assert_select!(glue, "SELECT name FROM Item WHERE colour IN ('orange', 'brown', 'blue')" => name = Str: (String::from("Apricot")), (String::from("date")));
+	assert_select!(glue, "SELECT name FROM Item WHERE colour NOT IN ('red', 'yellow')" => name = Str: (String::from("Apricot")), (String::from("date")));
+	assert_select!(glue, "SELECT name FROM Item WHERE name LIKE 'a%'" => name = Str: (String::from("apple")));
+	assert_select!(glue, "SELECT name FROM Item WHERE name ILIKE 'a%'" => name = Str: (String::from("apple")), (String::from("Apricot")));
+	assert_select!(glue, "SELECT name FROM Item WHERE name LIKE '_a%' AND colour = 'brown'" => name = Str: (String::from("date")));
+	assert_select!(glue, "SELECT name FROM Item WHERE name NOT LIKE '%a%'" => name = Str: (String::from("Apricot")), (String::from("cherry")));
+	assert_select!(glue, "SELECT name FROM Item WHERE colour = 'brown' OR name LIKE 'b%'" => name = Str: (String::from("banana")), (String::from("date")));
+	assert_select!(glue, "SELECT name FROM Item WHERE name IS NOT NULL AND colour = 'yellow'" => name = Str: (String::from("banana")));
+}
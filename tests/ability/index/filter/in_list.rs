@@ -0,0 +1,28 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(
+		glue,
+		"CREATE TABLE indexed (a INTEGER, b TEXT, c TEXT NULL)"
+	);
+	execute!(
+		glue,
+		"INSERT INTO indexed (a, b) VALUES (1, 'one'), (2, 'two'), (3, 'three'), (3, 'three'), (-4, 'minus four'), (100, 'hundred')"
+	);
+	execute!(glue, "CREATE INDEX index_b ON indexed (b)");
+
+	assert_select!(glue, "SELECT a FROM indexed WHERE 'two' = b" => a = I64: (2));
+	assert_select!(glue, "SELECT a FROM indexed WHERE b IN ('three', 'minus four', 'four')" => a = I64: (3),(3),(-4));
+	assert_select!(glue, "SELECT a FROM indexed WHERE b IN ('one', 'two') AND a > 1" => a = I64: (2));
+
+	execute!(glue, "INSERT INTO indexed VALUES (5, 'two', 'new')");
+	assert_select!(glue, "SELECT a FROM indexed WHERE b IN ('two')" => a = I64: (2),(5));
+	assert_eq!(
+		glue.select_as_csv("SELECT c FROM indexed WHERE a > 4 ORDER BY a"),
+		Ok(String::from("c\nnew\nNULL\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT c FROM indexed WHERE b = 'two' ORDER BY a"),
+		Ok(String::from("c\nNULL\nnew\n"))
+	);
+}
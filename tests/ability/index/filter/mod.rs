@@ -1,2 +1,3 @@
 mod general;
 mod gt_lt;
+mod in_list;
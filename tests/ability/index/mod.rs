@@ -1,3 +1,4 @@
 mod create;
 mod create_table;
 mod filter;
+mod rename;
@@ -0,0 +1,16 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(glue, "CREATE TABLE named (a INTEGER, b TEXT)");
+	execute!(
+		glue,
+		"INSERT INTO named (a, b) VALUES (1, 'one'), (2, 'two'), (3, 'three')"
+	);
+	execute!(glue, "CREATE INDEX index_a ON named (a)");
+	execute!(glue, "CREATE INDEX index_b ON named (b)");
+	execute!(glue, "ALTER TABLE named RENAME TO renamed");
+
+	assert_select!(glue, "SELECT a FROM renamed WHERE b = 'two'" => a = I64: (2));
+	assert_select!(glue, "SELECT b FROM renamed WHERE a > 1 ORDER BY a" => b = Str: (String::from("two")),(String::from("three")));
+	assert_select!(glue, "SELECT b FROM renamed WHERE a < 2" => b = Str: (String::from("one")));
+}
@@ -1,5 +1,9 @@
 mod alter;
+mod alter_row;
 mod base;
+mod bytes;
 mod column_option;
+mod filter;
 mod index;
 mod insert;
+mod projection;
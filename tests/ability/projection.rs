@@ -0,0 +1,16 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(
+		glue,
+		"CREATE TABLE Wide (name TEXT, colour TEXT, shape TEXT, taste TEXT)"
+	);
+	execute!(
+		glue,
+		"INSERT INTO Wide VALUES ('apple', 'red', 'round', 'sweet'), ('lemon', 'yellow', 'oval', 'sour'), ('cherry', 'red', 'round', 'sour')"
+	);
+
+	assert_select!(glue, "SELECT taste FROM Wide" => taste = Str: (String::from("sweet")), (String::from("sour")), (String::from("sour")));
+	assert_select!(glue, "SELECT name FROM Wide WHERE colour = 'red' AND taste = 'sour'" => name = Str: (String::from("cherry")));
+	assert_select!(glue, "SELECT shape, name FROM Wide WHERE taste = 'sour'" => shape = Str, name = Str: (String::from("oval"), String::from("lemon")), (String::from("round"), String::from("cherry")));
+}
@@ -0,0 +1,43 @@
+use {crate::util::*, multisql::Glue, std::io::Write};
+
+testcase!(test);
+fn test(mut glue: Glue) {
+	std::fs::create_dir("data").ok();
+
+	let gzip_path = "data/csv_compressed.csv.gz";
+	std::fs::remove_file(gzip_path).ok();
+	glue.execute_many(&format!(
+		"
+		CREATE DATABASE gzipped LOCATION '{}';
+		CREATE TABLE gzipped.data (id INTEGER, name TEXT);
+		INSERT INTO gzipped.data VALUES (1, 'Ann');
+		INSERT INTO gzipped.data VALUES (2, 'Bob');
+	",
+		gzip_path
+	))
+	.unwrap();
+	assert_eq!(&std::fs::read(gzip_path).unwrap()[..2], &[0x1f, 0x8b]);
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM gzipped.data ORDER BY id"),
+		Ok(String::from("id,name\n1,Ann\n2,Bob\n"))
+	);
+	assert_success!(glue, "UPDATE gzipped.data SET name = 'Bea' WHERE id = 2");
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM gzipped.data ORDER BY id"),
+		Ok(String::from("id,name\n1,Ann\n2,Bea\n"))
+	);
+
+	let zstd_path = "data/csv_compressed.csv.zst";
+	let mut encoder = zstd::Encoder::new(std::fs::File::create(zstd_path).unwrap(), 0).unwrap();
+	encoder.write_all(b"id,name\n1,Cat\n").unwrap();
+	encoder.finish().unwrap();
+	assert_eq!(
+		glue.try_extend_from_path(String::from("zstandard"), String::from(zstd_path)),
+		Ok(true)
+	);
+	assert_success!(glue, "INSERT INTO zstandard.data VALUES (2, 'Dan')");
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM zstandard.data ORDER BY id"),
+		Ok(String::from("id,name\n1,Cat\n2,Dan\n"))
+	);
+}
@@ -0,0 +1,81 @@
+use {
+	crate::util::*,
+	multisql::{CSVDatabaseError, Glue},
+	std::path::Path,
+};
+
+testcase!(test);
+fn test(mut glue: Glue) {
+	let path = "data/csv_directory";
+	std::fs::remove_dir_all(path).ok();
+	std::fs::create_dir_all(path).unwrap();
+	std::fs::write(format!("{}/people.csv", path), "id,name\n1,Ann\n2,Bob\n").unwrap();
+	std::fs::write(
+		format!("{}/pets.csv", path),
+		"owner,pet\n1,Cat\n2,Dog\n1,Fish\n",
+	)
+	.unwrap();
+	std::fs::write(format!("{}/notes.txt", path), "Not a table").unwrap();
+	std::fs::write(format!("{}/loud.CSV", path), "id\n1\n2\n").unwrap();
+
+	assert_eq!(
+		glue.try_extend_from_path(String::from("folder"), String::from(path)),
+		Ok(true)
+	);
+	assert_eq!(
+		glue.select_as_csv(
+			"SELECT name, pet FROM folder.people INNER JOIN folder.pets ON people.id = pets.owner WHERE name = 'Ann'"
+		),
+		Ok(String::from("name,pet\nAnn,Cat\nAnn,Fish\n"))
+	);
+
+	// Files of extensions of other cases are written where they are
+	assert_success!(glue, "DELETE FROM folder.loud WHERE id = 1");
+	assert_eq!(
+		std::fs::read_to_string(format!("{}/loud.CSV", path)).unwrap(),
+		"id\n2\n"
+	);
+
+	glue.execute_many(
+		"
+		CREATE TABLE folder.visits (name TEXT, day TEXT);
+		INSERT INTO folder.visits VALUES ('Ann', 'Monday');
+		ALTER TABLE folder.visits RENAME TO appointments;
+		DELETE FROM folder.pets WHERE pet = 'Dog';
+		DROP TABLE folder.people;
+	",
+	)
+	.unwrap();
+
+	assert_eq!(
+		std::fs::read_to_string(format!("{}/appointments.csv", path)).unwrap(),
+		"name,day\nAnn,Monday\n"
+	);
+	assert!(!Path::new(&format!("{}/visits.csv", path)).exists());
+	assert!(!Path::new(&format!("{}/people.csv", path)).exists());
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM folder.pets"),
+		Ok(String::from("owner,pet\n1,Cat\n1,Fish\n"))
+	);
+
+	// New directories are given as `directory/*.csv`
+	let new_path = "data/csv_directory_new";
+	std::fs::remove_dir_all(new_path).ok();
+	assert_eq!(
+		glue.try_extend_from_path(String::from("new"), format!("{}/*.csv", new_path)),
+		Ok(true)
+	);
+	assert_success!(glue, "CREATE TABLE new.empty (id INTEGER)");
+	assert_eq!(
+		std::fs::read_to_string(format!("{}/empty.csv", new_path)).unwrap(),
+		"id\n"
+	);
+
+	// Names aren't of files outside the directory
+	std::fs::remove_file("data/outside.csv").ok();
+	assert_eq!(
+		glue.execute(r#"CREATE TABLE new."../outside" (id INTEGER)"#),
+		Err(CSVDatabaseError::InvalidTableName(String::from("../outside")).into())
+	);
+	assert!(!Path::new("data/outside.csv").exists());
+}
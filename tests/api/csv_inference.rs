@@ -0,0 +1,99 @@
+use {
+	crate::util::*,
+	multisql::{Glue, Payload, Row, Value},
+};
+
+testcase!(test);
+fn test(mut glue: Glue) {
+	let path = "data/csv_inference.csv";
+	std::fs::create_dir("data").ok();
+	std::fs::remove_file(path).ok();
+	let (text, _, _) = encoding_rs::WINDOWS_1252.encode(
+		"Exported by some system\n\
+		id;name;joined;active;balance;note\n\
+		# Comments are skipped\n\
+		1;José;2024-01-02;yes;1,234.50;NA\n\
+		2;Zoë;2024-02-03 04:05:06;No;12;first\n",
+	);
+	std::fs::write(path, &text).unwrap();
+
+	assert_success!(
+		glue,
+		&format!(
+			"CREATE DATABASE inferred LOCATION '{}?delimiter=;&encoding=latin1&null=NA&comment=#&skip_rows=1&type.id=TEXT'",
+			path
+		)
+	);
+	assert_eq!(
+		glue.execute("SELECT id, name, joined, active, balance FROM inferred.data ORDER BY id"),
+		Ok(Payload::Select {
+			labels: ["id", "name", "joined", "active", "balance"]
+				.iter()
+				.map(|label| label.to_string())
+				.collect(),
+			rows: vec![
+				Row(vec![
+					Value::Str(String::from("1")),
+					Value::Str(String::from("José")),
+					Value::Timestamp(1704153600),
+					Value::Bool(true),
+					Value::F64(1234.5),
+				]),
+				Row(vec![
+					Value::Str(String::from("2")),
+					Value::Str(String::from("Zoë")),
+					Value::Timestamp(1706933106),
+					Value::Bool(false),
+					Value::F64(12.0),
+				]),
+			],
+		})
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT note FROM inferred.data ORDER BY id"),
+		Ok(String::from("note\nNULL\nfirst\n"))
+	);
+
+	// Files of comments aren't rewritten, as the comments would be lost
+	assert_error!(
+		glue,
+		"UPDATE inferred.data SET note = NULL WHERE name = 'Zoë'",
+		multisql::CSVDatabaseError::UnwritableComments
+	);
+	let (text, _, _) = encoding_rs::WINDOWS_1252.encode(
+		"Exported by some system\n\
+		id;name;joined;active;balance;note\n\
+		1;José;2024-01-02;yes;1,234.50;NA\n\
+		2;Zoë;2024-02-03 04:05:06;No;12;first\n",
+	);
+	std::fs::write(path, &text).unwrap();
+	assert_success!(
+		glue,
+		&format!(
+			"CREATE DATABASE rewritten LOCATION '{}?delimiter=;&encoding=latin1&null=NA&skip_rows=1&type.id=TEXT'",
+			path
+		)
+	);
+
+	// Rewritten in the same encoding, with skipped rows kept and nulls written as the token;
+	// updated rows are written as their values are
+	assert_success!(
+		glue,
+		"UPDATE rewritten.data SET note = NULL WHERE name = 'Zoë'"
+	);
+	let text = std::fs::read(path).unwrap();
+	let (text, _, _) = encoding_rs::WINDOWS_1252.decode(&text);
+	assert_eq!(
+		text,
+		"Exported by some system\n\
+		id;name;joined;active;balance;note\n\
+		1;José;2024-01-02;yes;1,234.50;NA\n\
+		2;Zoë;2024-02-03 04:05:06;false;12.0;NA\n"
+	);
+
+	assert_error!(
+		glue,
+		"CREATE DATABASE unknown LOCATION 'data/csv_inference.csv?colour=blue'",
+		multisql::CSVDatabaseError::UnknownOption(String::from("colour"))
+	);
+}
@@ -0,0 +1,39 @@
+use {
+	crate::util::*,
+	multisql::{CSVSettings, Connection, Glue},
+};
+
+testcase!(test);
+fn test(mut glue: Glue) {
+	let path = "data/csv_rewrite.csv";
+	std::fs::create_dir("data").ok();
+	std::fs::write(path, "name;note\n\"x;y\";one\nplain;two\nother;three\n").unwrap();
+
+	glue.extend(
+		String::from("file"),
+		Connection::CSV(
+			String::from(path),
+			CSVSettings {
+				delimiter: b';',
+				..CSVSettings::default()
+			},
+		)
+		.try_into()
+		.unwrap(),
+	);
+	glue.execute_many(
+		"
+		UPDATE file.data SET note = 'first' WHERE name = 'x;y';
+		DELETE FROM file.data WHERE name = 'plain';
+		UPDATE file.data SET note = 'last' WHERE name = 'other';
+	",
+	)
+	.unwrap();
+
+	// Header, delimiter and quoting are as they were
+	assert_eq!(
+		std::fs::read_to_string(path).unwrap(),
+		"name;note\n\"x;y\";first\nother;last\n"
+	);
+	assert!(!std::path::Path::new(&format!("{}.tmp", path)).exists());
+}
@@ -0,0 +1,50 @@
+use {
+	crate::util::*,
+	futures::executor::block_on,
+	multisql::{CSVDatabase, CSVDatabaseError, DBBase, DBMut, Glue, Row, Value},
+};
+
+testcase!(test);
+fn test(_glue: Glue) {
+	let path = "data/csv_row_keys.csv";
+	std::fs::create_dir("data").ok();
+	std::fs::write(path, "code,label\na,first\nb,second\nc,third\n").unwrap();
+	let mut database = CSVDatabase::new(path).unwrap();
+	let mut other = CSVDatabase::new(path).unwrap();
+	let key_of = |database: &CSVDatabase, code: &str| {
+		block_on(database.scan_data("data"))
+			.unwrap()
+			.into_iter()
+			.find(|(_, Row(row))| row[0] == Value::Str(String::from(code)))
+			.map(|(key, _)| key)
+			.unwrap()
+	};
+	let changed = || {
+		Row(vec![
+			Value::Str(String::from("c")),
+			Value::Str(String::from("changed")),
+		])
+	};
+
+	// Keys are of the file as it was read, which another has since written
+	let key = key_of(&database, "c");
+	let other_key = key_of(&other, "a");
+	block_on(other.delete_data("data", vec![other_key])).unwrap();
+	assert_eq!(
+		block_on(database.update_data("data", vec![(key, changed())])),
+		Err(CSVDatabaseError::ChangedSinceRead.into())
+	);
+	assert_eq!(
+		std::fs::read_to_string(path).unwrap(),
+		"code,label\nb,second\nc,third\n"
+	);
+
+	let key = key_of(&database, "c");
+	block_on(database.update_data("data", vec![(key, changed())])).unwrap();
+	assert_eq!(
+		std::fs::read_to_string(path).unwrap(),
+		"code,label\nb,second\nc,changed\n"
+	);
+	// The lock is of no file beside
+	assert!(!std::path::Path::new("data/csv_row_keys.csv.lock").exists());
+}
@@ -0,0 +1,69 @@
+use {
+	crate::util::*,
+	multisql::{Connection, Database, DatabaseError, DatabaseInner, Glue, MemoryDatabase},
+};
+
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	glue.register_database("teststore", |location: &str| {
+		assert!(location.to_lowercase().starts_with("teststore://"));
+		Ok(Box::new(MemoryDatabase::new()) as Box<DatabaseInner>)
+	});
+
+	glue.execute_many(
+		"
+		CREATE DATABASE store LOCATION 'TestStore://somewhere';
+		CREATE TABLE store.things (name TEXT);
+		INSERT INTO store.things VALUES ('a'), ('b');
+	",
+	)
+	.unwrap();
+	assert_eq!(
+		glue.select_as_csv("SELECT name FROM store.things ORDER BY name"),
+		Ok(String::from("name\na\nb\n"))
+	);
+	assert_success!(
+		glue,
+		"CREATE DATABASE IF NOT EXISTS store LOCATION 'teststore://elsewhere'"
+	);
+
+	assert_eq!(
+		glue.execute("CREATE DATABASE unknown LOCATION 'nostore://somewhere'"),
+		Err(DatabaseError::UnknownScheme(String::from("nostore")).into())
+	);
+
+	let mut other_glue = Glue::new(String::from("main"), Connection::Memory.try_into().unwrap());
+	assert_eq!(
+		other_glue.execute("CREATE DATABASE store LOCATION 'teststore://somewhere'"),
+		Err(DatabaseError::UnknownScheme(String::from("teststore")).into())
+	);
+
+	std::fs::create_dir("data").ok();
+	std::fs::write("data/custom_database_file.csv", "id\n1\n").unwrap();
+	assert_success!(
+		other_glue,
+		"CREATE DATABASE plain LOCATION 'file://data/custom_database_file.csv'"
+	);
+	assert_eq!(
+		other_glue.select_as_csv("SELECT id FROM plain.data"),
+		Ok(String::from("id\n1\n"))
+	);
+
+	let registry = glue.database_registry().clone();
+	let connections = glue.into_connections();
+	let serialised = serde_json::to_string(&connections).unwrap();
+	assert!(serialised.contains("TestStore://somewhere"));
+
+	let connections: Vec<(String, Connection)> = serde_json::from_str(&serialised).unwrap();
+	assert!(connections
+		.into_iter()
+		.any(|(_, connection)| Database::from_source(connection).is_err()));
+
+	let connections: Vec<(String, Connection)> = serde_json::from_str(&serialised).unwrap();
+	let databases = connections
+		.into_iter()
+		.map(|(name, connection)| (name, registry.open(connection).unwrap()))
+		.collect();
+	let mut glue = Glue::new_multi(databases);
+	assert_success!(glue, "CREATE TABLE store.others (name TEXT)");
+}
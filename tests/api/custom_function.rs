@@ -0,0 +1,89 @@
+use {
+	crate::util::*,
+	multisql::{Accumulator, Cast, Value},
+};
+
+struct Product;
+impl Accumulator for Product {
+	fn initial(&self, value: Value) -> multisql::Result<Value> {
+		Ok(value)
+	}
+	fn merge(&self, left: Value, right: Value) -> multisql::Result<Value> {
+		left.generic_multiply(right)
+	}
+}
+
+/// Keeps `[sum, count]`, giving the average once finished
+struct Mean;
+impl Accumulator for Mean {
+	fn initial(&self, value: Value) -> multisql::Result<Value> {
+		Ok(Value::Array(vec![value, Value::I64(1)]))
+	}
+	fn merge(&self, left: Value, right: Value) -> multisql::Result<Value> {
+		match (left, right) {
+			(Value::Array(left), Value::Array(right)) => Ok(Value::Array(vec![
+				left[0].clone().generic_add(right[0].clone())?,
+				left[1].clone().generic_add(right[1].clone())?,
+			])),
+			_ => unreachable!(),
+		}
+	}
+	fn finish(&self, state: Value) -> multisql::Result<Value> {
+		match state {
+			Value::Array(state) => {
+				let sum: f64 = state[0].clone().cast()?;
+				let count: f64 = state[1].clone().cast()?;
+				Ok(Value::F64(sum / count))
+			}
+			_ => unreachable!(),
+		}
+	}
+}
+
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	glue.register_function("checksum", |arguments| {
+		let sum: i64 = arguments
+			.into_iter()
+			.map(|argument| -> multisql::Result<i64> {
+				let argument: String = argument.cast()?;
+				Ok(argument.bytes().map(i64::from).sum())
+			})
+			.sum::<multisql::Result<i64>>()?;
+		Ok(Value::I64(sum % 256))
+	});
+	glue.register_function("UPPER", |_| Ok(Value::Str(String::from("overridden"))));
+	glue.register_aggregate("product", Product);
+	glue.register_aggregate("mean", Mean);
+
+	execute!(glue, "CREATE TABLE numbers (category TEXT, number INTEGER)");
+	execute!(
+		glue,
+		"INSERT INTO numbers VALUES ('a', 2), ('a', 3), ('a', 4), ('b', 5), ('b', 6)"
+	);
+
+	assert_select!(glue, "SELECT CHECKSUM('ab', 'c') AS checksum FROM numbers WHERE number = 2" => checksum = I64: (38));
+	assert_select!(glue, "SELECT checksum(category) AS checksum FROM numbers WHERE number = 5" => checksum = I64: (98));
+	assert_select!(glue, "SELECT UPPER(category) AS upper FROM numbers WHERE number = 2" => upper = Str: (String::from("overridden")));
+
+	assert_select!(glue, "
+		SELECT category, PRODUCT(number) AS product, MEAN(number) AS mean
+		FROM numbers
+		GROUP BY category
+		ORDER BY category
+	" =>
+		category = Str, product = I64, mean = F64:
+		(String::from("a"), 24, 3.0),
+		(String::from("b"), 30, 5.5)
+	);
+	assert_select!(glue, "SELECT PRODUCT(number) + 1 AS product FROM numbers" => product = I64: (721));
+
+	assert_error!(
+		glue,
+		"SELECT PRODUCT(number, category) FROM numbers",
+		multisql::ValueError::NumberOfFunctionParamsNotMatching {
+			expected: 1,
+			found: 2
+		}
+	);
+}
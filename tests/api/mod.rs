@@ -1,3 +1,18 @@
 mod cast_any;
 mod csv;
+mod csv_compressed;
+mod csv_directory;
+mod csv_inference;
+mod csv_rewrite;
+mod csv_row_keys;
+mod custom_database;
+mod custom_function;
 mod insert;
+mod ndjson;
+#[cfg(feature = "odbc-database")]
+mod odbc;
+#[cfg(feature = "parquet-database")]
+mod parquet;
+mod qualified_ddl;
+#[cfg(feature = "sqlite-database")]
+mod sqlite;
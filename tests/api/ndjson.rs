@@ -0,0 +1,46 @@
+use {crate::util::*, multisql::Glue};
+
+testcase!(test);
+fn test(mut glue: Glue) {
+	let path = "data/ndjson.jsonl";
+	std::fs::create_dir("data").ok();
+	std::fs::write(
+		path,
+		r#"{"id": 1, "event": "login", "detail": {"ip": "10.0.0.1"}, "duration": 1.5}
+{"id": 2, "event": "logout", "duration": 2}
+
+{"id": 3, "event": null, "duration": 0.25}
+"#,
+	)
+	.unwrap();
+
+	assert_eq!(
+		glue.try_extend_from_path(String::from("events"), String::from(path)),
+		Ok(true)
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM events.log ORDER BY id"),
+		Ok(String::from(
+			"id,event,detail,duration\n1,login,\"{\"\"ip\"\":\"\"10.0.0.1\"\"}\",1.5\n2,logout,NULL,2.0\n3,NULL,NULL,0.25\n"
+		))
+	);
+
+	assert_success!(
+		glue,
+		"INSERT INTO events.log VALUES (4, 'login', NULL, 3.5)"
+	);
+	assert_eq!(
+		std::fs::read_to_string(path).unwrap().lines().last(),
+		Some(r#"{"id":4,"event":"login","detail":null,"duration":3.5}"#)
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT id FROM events.log WHERE event = 'login' ORDER BY id"),
+		Ok(String::from("id\n1\n4\n"))
+	);
+
+	// Values are of their column's type, as discerned, where they can be
+	let mut text = std::fs::read_to_string(path).unwrap();
+	text.push_str("{\"id\": \"5\", \"event\": \"late\", \"duration\": \"soon\"}\n");
+	std::fs::write(path, text).unwrap();
+	assert_select!(glue, "SELECT id, duration FROM events.log WHERE event = 'late'" => id = I64, duration = Str: (5, String::from("soon")));
+}
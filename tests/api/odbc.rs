@@ -0,0 +1,109 @@
+use {
+	crate::util::*,
+	multisql::{Connection, Glue, Payload, Row, Value},
+};
+
+// Runs against the data source of `MULTISQL_ODBC_TEST`, or a SQLite file by the SQLite ODBC driver
+testcase!(test);
+fn test(mut glue: Glue) {
+	let connection_string = std::env::var("MULTISQL_ODBC_TEST").unwrap_or_else(|_| {
+		std::fs::create_dir("data").ok();
+		std::fs::remove_file("data/odbc.db").ok();
+		String::from("Driver={SQLite3};Database=data/odbc.db")
+	});
+	glue.extend(
+		String::from("remote"),
+		Connection::ODBC(connection_string).try_into().unwrap(),
+	);
+	glue.execute_native("remote", "DROP TABLE IF EXISTS items")
+		.unwrap();
+	assert_eq!(
+		glue.execute_native(
+			"remote",
+			"CREATE TABLE items (id INTEGER PRIMARY KEY, label TEXT, price FLOAT, active BIT)"
+		),
+		Ok(Payload::Success)
+	);
+
+	glue.execute_many(
+		"
+		INSERT INTO remote.items (id, label, price, active) VALUES
+			(1, 'one', 1.5, TRUE),
+			(2, NULL, NULL, FALSE),
+			(3, 'three', 3.25, NULL);
+		UPDATE remote.items SET label = 'two', price = 2.0 WHERE id = 2;
+		DELETE FROM remote.items WHERE id = 3;
+	",
+	)
+	.unwrap();
+	assert_select!(glue, "SELECT id, label, price, active FROM remote.items ORDER BY id" => id = I64, label = Str, price = F64, active = Bool:
+		(1, String::from("one"), 1.5, true),
+		(2, String::from("two"), 2.0, false)
+	);
+	// Ordered comparisons of strings, and patterns of sets, are left to the engine
+	assert_select!(glue, "SELECT id FROM remote.items WHERE label > 'one' ORDER BY id" => id = I64: (2));
+	assert_select_count!(
+		glue,
+		"SELECT id FROM remote.items WHERE label LIKE '[ot]%'",
+		0
+	);
+	assert_select!(glue, "SELECT id FROM remote.items WHERE label LIKE 't%' AND price >= 2" => id = I64: (2));
+	// NULL <> 1.5 is true, so rows of NULL are kept; booleans are written as the remote database writes them
+	assert_success!(
+		glue,
+		"INSERT INTO remote.items (id, label, price, active) VALUES (4, 'four', NULL, NULL)"
+	);
+	assert_select!(glue, "SELECT id FROM remote.items WHERE price <> 1.5 ORDER BY id" => id = I64: (2), (4));
+	assert_select!(glue, "SELECT id FROM remote.items WHERE active = TRUE" => id = I64: (1));
+	assert_success!(glue, "DELETE FROM remote.items WHERE id = 4");
+
+	assert_eq!(glue.execute("EXPLAIN REFRESH"), Ok(Payload::Success));
+	assert_eq!(
+		glue.execute_native("remote", "SELECT id, label FROM items WHERE id = 2"),
+		Ok(Payload::Select {
+			labels: vec![String::from("id"), String::from("label")],
+			rows: vec![Row(vec![Value::I64(2), Value::Str(String::from("two"))])]
+		})
+	);
+
+	glue.execute_many(
+		"
+		CREATE TABLE remote.made (id INTEGER, label TEXT NULL);
+		INSERT INTO remote.made (id, label) VALUES (1, NULL);
+	",
+	)
+	.unwrap();
+	assert_eq!(
+		glue.select_as_csv("SELECT id, label FROM remote.made"),
+		Ok(String::from("id,label\n1,NULL\n"))
+	);
+	// Of no primary key, nor any column needed
+	assert_select!(glue, "SELECT 1 AS one FROM remote.made" => one = I64: (1));
+	assert_success!(glue, "DROP TABLE remote.made");
+
+	// Of names which are keywords
+	glue.execute_many(
+		r#"
+		CREATE TABLE remote."order" ("select" INTEGER, "group" TEXT NULL);
+		INSERT INTO remote."order" ("select", "group") VALUES (1, 'a');
+	"#,
+	)
+	.unwrap();
+	assert_select!(glue, r#"SELECT "group" FROM remote."order" WHERE "select" = 1"# => group = Str: (String::from("a")));
+	assert_success!(glue, r#"DROP TABLE remote."order""#);
+
+	// Of names with underscores, which are of no schema
+	glue.execute_many(
+		"
+		CREATE TABLE remote.order_items (id INTEGER);
+		INSERT INTO remote.order_items (id) VALUES (1);
+	",
+	)
+	.unwrap();
+	assert_select!(glue, "SELECT id FROM remote.order_items" => id = I64: (1));
+	assert_success!(glue, "DROP TABLE remote.order_items");
+	assert_eq!(
+		glue.execute_native("remote", "DROP TABLE items"),
+		Ok(Payload::Success)
+	);
+}
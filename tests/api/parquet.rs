@@ -0,0 +1,205 @@
+use {
+	crate::util::*,
+	multisql::Glue,
+	parquet::{
+		data_type::{ByteArray, ByteArrayType, Int32Type, Int64Type},
+		file::{
+			properties::{EnabledStatistics, WriterProperties},
+			writer::SerializedFileWriter,
+		},
+		schema::parser::parse_message_type,
+	},
+	std::{fs::File, path::Path, sync::Arc},
+};
+
+/// Row groups of `(id, name, day)`
+type RowGroups<'a> = [(&'a [i64], &'a [Option<&'a str>], &'a [i32])];
+
+fn write_readings(path: &str, row_groups: &RowGroups) {
+	let schema = parse_message_type(
+		"message schema {
+			REQUIRED INT64 id;
+			OPTIONAL BYTE_ARRAY name (UTF8);
+			REQUIRED INT32 day (DATE);
+		}",
+	)
+	.unwrap();
+	// Row groups are skipped by their statistics, as where no id of one is that of a query
+	let properties = WriterProperties::builder()
+		.set_statistics_enabled(EnabledStatistics::Chunk)
+		.build();
+	let mut writer = SerializedFileWriter::new(
+		File::create(path).unwrap(),
+		Arc::new(schema),
+		Arc::new(properties),
+	)
+	.unwrap();
+	for (ids, names, days) in row_groups {
+		let mut row_group = writer.next_row_group().unwrap();
+
+		let mut column = row_group.next_column().unwrap().unwrap();
+		column
+			.typed::<Int64Type>()
+			.write_batch(ids, None, None)
+			.unwrap();
+		column.close().unwrap();
+
+		let mut column = row_group.next_column().unwrap().unwrap();
+		let levels: Vec<i16> = names.iter().map(|name| name.is_some() as i16).collect();
+		let names: Vec<ByteArray> = names.iter().flatten().map(|name| (*name).into()).collect();
+		column
+			.typed::<ByteArrayType>()
+			.write_batch(&names, Some(&levels), None)
+			.unwrap();
+		column.close().unwrap();
+
+		let mut column = row_group.next_column().unwrap().unwrap();
+		column
+			.typed::<Int32Type>()
+			.write_batch(days, None, None)
+			.unwrap();
+		column.close().unwrap();
+
+		row_group.close().unwrap();
+	}
+	writer.close().unwrap();
+}
+
+/// A table of times of day, which can't be read
+fn write_times(path: &str) {
+	let schema = parse_message_type(
+		"message schema {
+			REQUIRED INT64 id;
+			REQUIRED INT32 at (TIME_MILLIS);
+		}",
+	)
+	.unwrap();
+	let mut writer = SerializedFileWriter::new(
+		File::create(path).unwrap(),
+		Arc::new(schema),
+		Arc::new(WriterProperties::builder().build()),
+	)
+	.unwrap();
+	let mut row_group = writer.next_row_group().unwrap();
+	let mut column = row_group.next_column().unwrap().unwrap();
+	column
+		.typed::<Int64Type>()
+		.write_batch(&[1], None, None)
+		.unwrap();
+	column.close().unwrap();
+	let mut column = row_group.next_column().unwrap().unwrap();
+	column
+		.typed::<Int32Type>()
+		.write_batch(&[1_000], None, None)
+		.unwrap();
+	column.close().unwrap();
+	row_group.close().unwrap();
+	writer.close().unwrap();
+}
+
+testcase!(test);
+fn test(mut glue: Glue) {
+	let path = "data/parquet";
+	std::fs::remove_dir_all(path).ok();
+	std::fs::create_dir_all(path).unwrap();
+	write_readings(
+		&format!("{}/readings.parquet", path),
+		&[
+			(&[1, 2, 3], &[Some("Ann"), None, Some("Cat")], &[0, 1, 2]),
+			(&[4, 5], &[Some("Dan"), Some("Eve")], &[3, 4]),
+		],
+	);
+
+	// Groups of one name and NULLs, and of only NULLs
+	write_readings(
+		&format!("{}/mixed.parquet", path),
+		&[
+			(&[1, 2], &[Some("Ann"), None], &[0, 1]),
+			(&[3], &[None], &[2]),
+		],
+	);
+	write_times(&format!("{}/times.parquet", path));
+
+	assert_eq!(
+		glue.try_extend_from_path(String::from("store"), String::from(path)),
+		Ok(true)
+	);
+
+	// Times of day can't be read, and so are left out, and can't be written
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM store.times"),
+		Ok(String::from("id\n1\n"))
+	);
+	assert_error!(
+		glue,
+		"INSERT INTO store.times VALUES (2)",
+		multisql::ParquetDatabaseError::UnwritableColumn(String::from("at"))
+	);
+	assert!(Path::new(&format!("{}/times.parquet", path)).exists());
+
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM store.readings WHERE id = 2"),
+		Ok(String::from("id,name,day\n2,NULL,1970-01-02 00:00:00\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT id, name FROM store.readings WHERE id < 4 ORDER BY id"),
+		Ok(String::from("id,name\n1,Ann\n2,NULL\n3,Cat\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT name FROM store.readings WHERE id >= 4 ORDER BY id"),
+		Ok(String::from("name\nDan\nEve\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT id FROM store.readings WHERE name IS NULL"),
+		Ok(String::from("id\n2\n"))
+	);
+	// NULL <> 'Ann' is true, so groups of NULLs are kept
+	assert_eq!(
+		glue.select_as_csv("SELECT id FROM store.mixed WHERE name <> 'Ann' ORDER BY id"),
+		Ok(String::from("id\n2\n3\n"))
+	);
+
+	// Rows are appended as new parts, a table of one file being made a directory
+	assert_success!(
+		glue,
+		"INSERT INTO store.readings (id, name, day) VALUES (6, 'Fay', CAST('1970-01-06' AS TIMESTAMP))"
+	);
+	assert!(!Path::new(&format!("{}/readings.parquet", path)).exists());
+	assert!(Path::new(&format!("{}/readings/part-00000.parquet", path)).exists());
+	assert!(Path::new(&format!("{}/readings/part-00001.parquet", path)).exists());
+	assert_eq!(
+		glue.select_as_csv("SELECT id, name FROM store.readings WHERE id > 4 ORDER BY id"),
+		Ok(String::from("id,name\n5,Eve\n6,Fay\n"))
+	);
+
+	glue.execute_many(
+		"
+		CREATE TABLE store.payments (id INTEGER, payer TEXT NULL, amount FLOAT, paid BOOLEAN NULL);
+		INSERT INTO store.payments VALUES (1, 'Ann', 1.5, TRUE), (2, 'Bob', 20, FALSE);
+		INSERT INTO store.payments VALUES (3, NULL, 300.25, NULL);
+	",
+	)
+	.unwrap();
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM store.payments WHERE amount > 10 ORDER BY id"),
+		Ok(String::from(
+			"id,payer,amount,paid\n2,Bob,20.0,false\n3,NULL,300.25,NULL\n"
+		))
+	);
+
+	assert_success!(glue, "DROP TABLE store.payments");
+	assert!(!Path::new(&format!("{}/payments", path)).exists());
+
+	// New directories are given as `directory/*.parquet`
+	let new_path = "data/parquet_new";
+	std::fs::remove_dir_all(new_path).ok();
+	assert_eq!(
+		glue.try_extend_from_path(String::from("new"), format!("{}/*.parquet", new_path)),
+		Ok(true)
+	);
+	assert_success!(glue, "CREATE TABLE new.empty (id INTEGER)");
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM new.empty"),
+		Ok(String::from("id\n"))
+	);
+}
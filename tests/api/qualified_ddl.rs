@@ -0,0 +1,49 @@
+use {
+	crate::util::*,
+	multisql::{Connection, Glue},
+};
+
+testcase!(test);
+fn test(mut glue: Glue) {
+	glue.extend(
+		String::from("other"),
+		Connection::Memory.try_into().unwrap(),
+	);
+	glue.execute_many(
+		"
+		CREATE TABLE main.shared (id INTEGER, label TEXT);
+		CREATE TABLE other.shared (id INTEGER, label TEXT);
+		INSERT INTO main.shared VALUES (1, 'main');
+		INSERT INTO other.shared VALUES (2, 'other');
+	",
+	)
+	.unwrap();
+
+	glue.execute_many(
+		"
+		ALTER TABLE other.shared ADD COLUMN extra TEXT NULL;
+		ALTER TABLE other.shared DROP COLUMN label;
+		CREATE INDEX other_id ON other.shared (id);
+		ALTER TABLE other.shared RENAME TO renamed;
+	",
+	)
+	.unwrap();
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM other.renamed"),
+		Ok(String::from("id,extra\n2,NULL\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM main.shared"),
+		Ok(String::from("id,label\n1,main\n"))
+	);
+
+	assert_success!(glue, "TRUNCATE TABLE other.renamed");
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM other.renamed"),
+		Ok(String::from("id,extra\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM main.shared"),
+		Ok(String::from("id,label\n1,main\n"))
+	);
+}
@@ -0,0 +1,156 @@
+use {
+	crate::util::*,
+	multisql::{Glue, Payload, Row, Value},
+	rusqlite::Connection,
+};
+
+testcase!(test);
+fn test(mut glue: Glue) {
+	let path = "data/sqlite.db";
+	std::fs::create_dir("data").ok();
+	std::fs::remove_file(path).ok();
+	let connection = Connection::open(path).unwrap();
+	connection
+		.execute_batch(
+			"
+			CREATE TABLE readings (
+				id INTEGER PRIMARY KEY,
+				name VARCHAR(20),
+				score DOUBLE,
+				passed BOOLEAN,
+				taken DATETIME,
+				note
+			);
+			CREATE INDEX readings_score ON readings (score);
+			INSERT INTO readings VALUES
+				(1, 'Ann', 1.5, 1, '1970-01-02 00:00:00', 'first'),
+				(2, 'Bob', 2.5, 0, NULL, 7),
+				(3, 'bea', 3.5, 1, '1970-01-03 00:00:00', NULL);
+			CREATE TABLE pairs (left TEXT, right TEXT, weight INTEGER, PRIMARY KEY (left, right)) WITHOUT ROWID;
+			INSERT INTO pairs VALUES ('a', 'b', 1), ('a', 'c', 2);
+			CREATE TABLE b (id INTEGER, x INTEGER);
+			INSERT INTO b VALUES (1, NULL), (2, 5);
+		",
+		)
+		.unwrap();
+
+	assert_eq!(
+		glue.try_extend_from_path(String::from("store"), String::from(path)),
+		Ok(true)
+	);
+
+	// Types are of the affinities of declared types; columns of none hold values as they are
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM store.readings WHERE id = 1"),
+		Ok(String::from(
+			"id,name,score,passed,taken,note\n1,Ann,1.5,true,1970-01-02 00:00:00,first\n"
+		))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT passed, taken, note FROM store.readings WHERE id = 2"),
+		Ok(String::from("passed,taken,note\nfalse,NULL,7\n"))
+	);
+
+	// SQLite's own indexes are used, the engine's constraint being left to them
+	assert_eq!(
+		glue.select_as_csv("SELECT id FROM store.readings WHERE score >= 2.5 ORDER BY id"),
+		Ok(String::from("id\n2\n3\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT id FROM store.readings WHERE score = 1.5"),
+		Ok(String::from("id\n1\n"))
+	);
+
+	// SQLite's `LIKE` ignores case, so rows are confirmed by the engine
+	assert_eq!(
+		glue.select_as_csv("SELECT name FROM store.readings WHERE name LIKE 'b%'"),
+		Ok(String::from("name\nbea\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT id FROM store.readings WHERE taken IS NULL"),
+		Ok(String::from("id\n2\n"))
+	);
+	// NULL <> 5 is true, so rows of NULL are kept, which SQLite's `<>` would leave out
+	assert_eq!(
+		glue.select_as_csv("SELECT id FROM store.b WHERE x <> 5"),
+		Ok(String::from("id\n1\n"))
+	);
+
+	glue.execute_many(
+		"
+		UPDATE store.readings SET passed = TRUE, score = 4.5 WHERE id = 2;
+		DELETE FROM store.readings WHERE id = 3;
+		INSERT INTO store.readings (id, name, score, passed, taken, note)
+			VALUES (4, 'Dan', 0.5, FALSE, CAST('1970-01-05' AS TIMESTAMP), NULL);
+	",
+	)
+	.unwrap();
+	assert_eq!(
+		connection
+			.prepare("SELECT id, passed, score, taken FROM readings ORDER BY id")
+			.unwrap()
+			.query_map([], |row| {
+				Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+			})
+			.unwrap()
+			.collect::<rusqlite::Result<Vec<(i64, i64, f64, Option<String>)>>>()
+			.unwrap(),
+		vec![
+			(1, 1, 1.5, Some(String::from("1970-01-02 00:00:00"))),
+			(2, 1, 4.5, None),
+			(4, 0, 0.5, Some(String::from("1970-01-05 00:00:00"))),
+		]
+	);
+
+	// Rows of tables without a rowid are found by their primary key
+	assert_success!(glue, "UPDATE store.pairs SET weight = 3 WHERE right = 'c'");
+	assert_success!(glue, "DELETE FROM store.pairs WHERE right = 'b'");
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM store.pairs"),
+		Ok(String::from("left,right,weight\na,c,3\n"))
+	);
+
+	glue.execute_many(
+		"
+		CREATE TABLE store.made (id INTEGER, label TEXT NULL, amount FLOAT);
+		INSERT INTO store.made VALUES (1, 'one', 10), (2, NULL, 20);
+		CREATE INDEX made_amount ON store.made (amount);
+		ALTER TABLE store.made RENAME COLUMN label TO title;
+	",
+	)
+	.unwrap();
+	assert_eq!(
+		connection
+			.query_row(
+				"SELECT sql FROM sqlite_schema WHERE name = 'made_amount'",
+				[],
+				|row| row.get::<_, String>(0)
+			)
+			.unwrap(),
+		"CREATE INDEX \"made_amount\" ON \"made\" (\"amount\")"
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT id, title FROM store.made WHERE amount >= 15"),
+		Ok(String::from("id,title\n2,NULL\n"))
+	);
+
+	assert_eq!(
+		glue.execute_native("store", "SELECT id, title FROM made WHERE id = 1"),
+		Ok(Payload::Select {
+			labels: vec![String::from("id"), String::from("title")],
+			rows: vec![Row(vec![Value::I64(1), Value::Str(String::from("one"))])]
+		})
+	);
+
+	assert_success!(glue, "DROP TABLE store.made");
+	assert_eq!(
+		connection
+			.query_row(
+				"SELECT COUNT(*) FROM sqlite_schema WHERE tbl_name = 'made'",
+				[],
+				|row| row.get::<_, i64>(0)
+			)
+			.unwrap(),
+		0
+	);
+}
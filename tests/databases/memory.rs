@@ -1,7 +1,16 @@
 inventory::submit!(crate::TestDatabase {
 	init: database,
 	name: "Memory",
-	exceptions: &["base", "alter", "column_option", "index"]
+	exceptions: &[
+		"base",
+		"alter::database",
+		"alter::table::alter_table",
+		"alter::table::create_table",
+		"alter::table::drop_table",
+		"alter::view",
+		"column_option",
+		"index"
+	]
 });
 pub fn database(_name: &str) -> multisql::Glue {
 	use multisql::*;
@@ -2,7 +2,11 @@ inventory::submit!(crate::TestDatabase {
 	init: database,
 	name: "Sheet",
 	exceptions: &[
-		"alter",
+		"alter::database",
+		"alter::table::alter_table",
+		"alter::table::create_table",
+		"alter::table::drop_table",
+		"alter::view",
 		"column_option::auto_increment",
 		"index",
 		"base::generic_complex"
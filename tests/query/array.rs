@@ -0,0 +1,51 @@
+use {crate::util::*, multisql::Value};
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(glue, "CREATE TABLE Post (id INTEGER, tags TEXT[] NULL)");
+	execute!(
+		glue,
+		"INSERT INTO Post VALUES (1, ARRAY['red', 'blue']), (2, ARRAY['green']), (3, NULL)"
+	);
+
+	let tags = |tags: &[&str]| -> Vec<Value> {
+		tags.iter()
+			.map(|tag| Value::Str(String::from(*tag)))
+			.collect()
+	};
+
+	assert_select!(glue, "SELECT ARRAY[1, 2, 3] AS numbers FROM Post WHERE id = 1" => numbers = Array: (vec![Value::I64(1), Value::I64(2), Value::I64(3)]));
+	assert_select!(glue, "SELECT tags[1] AS first FROM Post WHERE id < 3" => first = Str: (String::from("red")), (String::from("green")));
+	assert_select!(glue, "SELECT tags[2] AS second FROM Post WHERE id = 2" => second = Str: (_));
+
+	assert_select!(glue, "SELECT id FROM Post WHERE 'blue' = ANY(tags)" => id = I64: (1));
+	assert_select!(glue, "SELECT id FROM Post WHERE 'blue' <> ALL(tags)" => id = I64: (2));
+	assert_select!(glue, "SELECT id FROM Post WHERE id > ALL(ARRAY[1, 2])" => id = I64: (3));
+
+	assert_select!(glue, "SELECT ARRAY_AGG(id) AS ids FROM Post ORDER BY id" => ids = Array: (vec![Value::I64(1), Value::I64(2), Value::I64(3)]));
+
+	assert_select!(glue, "SELECT number FROM UNNEST(ARRAY[1, 2, 3]) AS numbers(number)" => number = I64: (1), (2), (3));
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM UNNEST(ARRAY[1, 2], ARRAY['a'])"),
+		Ok(String::from("unnest_0,unnest_1\n1,a\n2,NULL\n"))
+	);
+	assert_select!(glue, "SELECT Post.id, tag.unnest FROM Post, UNNEST(Post.tags) AS tag" => "Post.id" = I64, "UNNEST.unnest" = Str: (1, String::from("red")), (1, String::from("blue")), (2, String::from("green")));
+	assert_eq!(
+		glue.select_as_csv(
+			"SELECT id, tag FROM Post LEFT JOIN UNNEST(tags) AS tags(tag) ON true ORDER BY id"
+		),
+		Ok(String::from("id,tag\n1,red\n1,blue\n2,green\n3,NULL\n"))
+	);
+
+	assert_select!(glue, "SELECT ARRAY_AGG(tag) AS tags FROM Post, UNNEST(tags) AS tags(tag) WHERE id = 1" => tags = Array: (tags(&["red", "blue"])));
+
+	assert_error!(glue, "SELECT * FROM UNNEST(1)");
+	assert_error!(glue, "SELECT * FROM NOT_A_FUNCTION(1)");
+
+	glue.insert_vec(
+		String::from("Post"),
+		vec![String::from("id"), String::from("tags")],
+		vec![vec![Value::I64(4), Value::Array(tags(&["red", "green"]))]],
+	)
+	.unwrap();
+	assert_select!(glue, "SELECT tags FROM Post WHERE id = 4" => tags = Array: (tags(&["red", "green"])));
+}
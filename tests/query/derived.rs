@@ -0,0 +1,79 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(glue, "CREATE TABLE Author (id INTEGER, name TEXT)");
+	execute!(
+		glue,
+		"CREATE TABLE Book (id INTEGER, author_id INTEGER, pages INTEGER)"
+	);
+	execute!(
+		glue,
+		"INSERT INTO Author VALUES (1, 'Ann'), (2, 'Ben'), (3, 'Cat')"
+	);
+	execute!(
+		glue,
+		"INSERT INTO Book VALUES (1, 1, 100), (2, 1, 300), (3, 2, 50)"
+	);
+
+	assert_select!(glue, "SELECT id FROM (SELECT id FROM Author WHERE id > 1) AS later" => id = I64: (2), (3));
+	assert_select!(glue, "SELECT number FROM (SELECT id AS identifier FROM Author) AS ids(number) WHERE number = 3" => number = I64: (3));
+	assert_select!(glue, "SELECT total FROM (SELECT author_id, SUM(pages) AS total FROM Book GROUP BY author_id) AS totals WHERE author_id = 1" => total = I64: (400));
+	assert_select!(glue, "SELECT counted.unnamed_0 AS value FROM (VALUES (7)) AS counted" => value = I64: (7));
+	assert_select!(glue, "SELECT id FROM (SELECT id FROM (SELECT id FROM Author) AS inner_table WHERE id < 3) AS outer_table WHERE id > 1" => id = I64: (2));
+	// Labels of the inner query lose their table names
+	assert_select!(glue, "SELECT name, pages FROM (SELECT Author.name, Book.pages FROM Author INNER JOIN Book ON Book.author_id = Author.id) AS written WHERE pages > 60" =>
+		name = Str, pages = I64:
+		(String::from("Ann"), 100),
+		(String::from("Ann"), 300)
+	);
+	assert_select!(glue, "
+		SELECT Author.name, totals.total
+		FROM Author
+		INNER JOIN (SELECT author_id, SUM(pages) AS total FROM Book GROUP BY author_id) AS totals
+			ON totals.author_id = Author.id
+	" =>
+		"Author.name" = Str, "totals.total" = I64:
+		(String::from("Ann"), 400),
+		(String::from("Ben"), 50)
+	);
+
+	assert_select!(glue, "
+		SELECT Author.name, longest.pages
+		FROM Author, LATERAL (
+			SELECT pages FROM Book WHERE Book.author_id = Author.id ORDER BY pages DESC LIMIT 1
+		) AS longest
+	" =>
+		"Author.name" = Str, "longest.pages" = I64:
+		(String::from("Ann"), 300),
+		(String::from("Ben"), 50)
+	);
+	assert_eq!(
+		glue.select_as_csv("
+			SELECT Author.name, books.pages
+			FROM Author
+			LEFT JOIN LATERAL (SELECT pages FROM Book WHERE author_id = Author.id AND pages < 200) AS books ON true
+			ORDER BY Author.id
+		"),
+		Ok(String::from("Author.name,books.pages\nAnn,100\nBen,50\nCat,NULL\n"))
+	);
+	// Arranged once, with the CTEs it sees, and bound to each row
+	assert_select!(glue, "
+		WITH Shelf AS (SELECT author_id, pages FROM Book)
+		SELECT Author.name, second.pages
+		FROM Author, LATERAL (
+			SELECT pages FROM Shelf WHERE Shelf.author_id = Author.id ORDER BY pages LIMIT 1 OFFSET 1
+		) AS second
+	" =>
+		"Author.name" = Str, "second.pages" = I64:
+		(String::from("Ann"), 300)
+	);
+
+	assert_error!(
+		glue,
+		"SELECT * FROM (SELECT pages FROM Book WHERE author_id = Author.id) AS books, Author"
+	);
+	assert_error!(
+		glue,
+		"SELECT * FROM LATERAL (SELECT missing FROM Book) AS books"
+	);
+}
@@ -0,0 +1,66 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(
+		glue,
+		"CREATE TABLE Reading (sensor TEXT, time INTEGER, value FLOAT NULL)"
+	);
+	execute!(
+		glue,
+		"
+		INSERT INTO Reading VALUES
+			('a', 1, 1.5), ('b', 1, 2.0), ('a', 2, 1.0),
+			('a', 3, 1.5), ('b', 2, 2.0), ('c', 1, NULL), ('c', 2, NULL)
+	"
+	);
+
+	assert_select!(glue, "SELECT DISTINCT sensor FROM Reading" =>
+		sensor = Str: (String::from("a")), (String::from("b")), (String::from("c"))
+	);
+	assert_select!(glue, "SELECT DISTINCT sensor, value FROM Reading WHERE value IS NOT NULL" =>
+		sensor = Str, value = F64:
+		(String::from("a"), 1.5),
+		(String::from("b"), 2.0),
+		(String::from("a"), 1.0)
+	);
+	// NULLs are not distinct from one another
+	assert_eq!(
+		glue.select_as_csv("SELECT DISTINCT value FROM Reading WHERE sensor = 'c'"),
+		Ok(String::from("value\nNULL\n"))
+	);
+
+	// The first row of each key is kept, by ORDER BY
+	assert_eq!(
+		glue.select_as_csv(
+			"SELECT DISTINCT ON (sensor) sensor, time FROM Reading ORDER BY sensor, time DESC"
+		),
+		Ok(String::from("sensor,time\na,3\nb,2\nc,2\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv(
+			"SELECT DISTINCT ON (sensor, value) sensor, time FROM Reading WHERE sensor = 'a' ORDER BY time"
+		),
+		Ok(String::from("sensor,time\na,1\na,2\n"))
+	);
+	// Functions and columns of the name are not mistaken for it
+	glue.register_function("distinct_on", |arguments| {
+		Ok(arguments
+			.into_iter()
+			.next()
+			.unwrap_or(multisql::Value::Null))
+	});
+	assert_select!(glue, "SELECT DISTINCT DISTINCT_ON(sensor) AS first FROM Reading WHERE time = 1" =>
+		first = Str: (String::from("a")), (String::from("b")), (String::from("c"))
+	);
+	execute!(glue, "CREATE TABLE Named (distinct_on INTEGER)");
+	execute!(glue, "INSERT INTO Named VALUES (1), (1), (2)");
+	assert_select!(glue, "SELECT DISTINCT ON (distinct_on) distinct_on FROM Named" =>
+		distinct_on = I64: (1), (2)
+	);
+
+	assert_error!(
+		glue,
+		"SELECT DISTINCT ON (sensor) sensor, MAX(time) FROM Reading GROUP BY sensor",
+		multisql::SelectError::UnimplementedDistinctOnAggregate
+	);
+}
@@ -62,4 +62,14 @@ fn test(mut glue: multisql::Glue) {
 		" => database = Str, table = Str:
 		(String::from("main"), String::from("simple"))
 	);
+
+	assert_eq!(
+		glue.execute("EXPLAIN REFRESH"),
+		Ok(multisql::Payload::Success)
+	);
+	assert_select!(glue, "
+			EXPLAIN ALL_TABLE
+		" => database = Str, table = Str:
+		(String::from("main"), String::from("simple"))
+	);
 }
@@ -0,0 +1,20 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(glue, "CREATE TABLE Item (name TEXT, colour TEXT)");
+	execute!(
+		glue,
+		"INSERT INTO Item VALUES ('apple', 'red'), ('Apricot', 'orange'), ('banana', 'yellow'), ('cherry', 'red'), ('date', 'brown')"
+	);
+
+	// Filters of a table which a join may fill with NULLs must remain after the join
+	execute!(glue, "CREATE TABLE Stock (item TEXT, shelf TEXT)");
+	execute!(
+		glue,
+		"INSERT INTO Stock VALUES ('apple', 'top'), ('banana', 'bottom'), ('date', 'top')"
+	);
+	assert_select!(glue, "SELECT name FROM Item LEFT JOIN Stock ON Stock.item = Item.name WHERE Stock.shelf IS NULL" => name = Str: (String::from("Apricot")), (String::from("cherry")));
+	assert_select!(glue, "SELECT name FROM Item LEFT JOIN Stock ON Stock.item = Item.name WHERE Stock.shelf = 'top'" => name = Str: (String::from("apple")), (String::from("date")));
+	// NULL <> 'top' is true, so rows the join fills are kept
+	assert_select!(glue, "SELECT name FROM Item LEFT JOIN Stock ON Stock.item = Item.name WHERE Stock.shelf <> 'top'" => name = Str: (String::from("Apricot")), (String::from("banana")), (String::from("cherry")));
+}
@@ -0,0 +1,50 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(glue, "CREATE TABLE SingleItem (id INTEGER PRIMARY KEY)");
+	execute!(glue, "INSERT INTO SingleItem VALUES (0)");
+
+	execute!(glue, "CREATE TABLE File (name TEXT, data BLOB NULL)");
+	execute!(
+		glue,
+		"INSERT INTO File VALUES ('empty', X''), ('small', X'CAFE'), ('missing', NULL)"
+	);
+
+	assert_select!(glue, "SELECT X'CAFE' AS test FROM SingleItem" => test = Bytes: (vec![202_u8, 254]));
+	assert_select!(glue, "SELECT ENCODE(X'CAFE', 'hex') AS test FROM SingleItem" => test = Str: (String::from("cafe")));
+	assert_select!(glue, "SELECT ENCODE(X'CAFE', 'base64') AS test FROM SingleItem" => test = Str: (String::from("yv4=")));
+	assert_select!(glue, "SELECT DECODE('cafe', 'hex') AS test FROM SingleItem" => test = Bytes: (vec![202_u8, 254]));
+	assert_select!(glue, "SELECT DECODE('yv4=', 'BASE64') AS test FROM SingleItem" => test = Bytes: (vec![202_u8, 254]));
+	assert_select!(glue, "SELECT LENGTH(X'CAFE') AS test FROM SingleItem" => test = I64: (2));
+
+	assert_select!(glue, "SELECT name, LENGTH(data) AS length FROM File WHERE data IS NOT NULL" => name = Str, length = I64: (String::from("empty"), 0), (String::from("small"), 2));
+	assert_select!(glue, "SELECT ENCODE(data, 'hex') AS test FROM File WHERE name = 'missing'" => test = Str: (_));
+	assert_select!(glue, "SELECT name FROM File WHERE data = X'CAFE'" => name = Str: (String::from("small")));
+
+	assert_error!(
+		glue,
+		"SELECT X'CAF' AS test FROM SingleItem",
+		multisql::ValueError::FailedToParseHex(String::from("CAF"))
+	);
+	assert_error!(
+		glue,
+		"SELECT DECODE('cafe', 'rot13') AS test FROM SingleItem",
+		multisql::ValueError::UnsupportedEncoding(String::from("rot13"))
+	);
+	assert_error!(
+		glue,
+		"SELECT ENCODE(X'CAFE') AS test FROM SingleItem",
+		multisql::ValueError::NumberOfFunctionParamsNotMatching {
+			expected: 2,
+			found: 1,
+		}
+	);
+	assert_error!(
+		glue,
+		"INSERT INTO File VALUES ('text', 1)",
+		multisql::ValueError::IncompatibleDataType {
+			data_type: String::from("BLOB"),
+			value: format!("{:?}", multisql::Value::I64(1)),
+		}
+	);
+}
@@ -0,0 +1,42 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(
+		glue,
+		"CREATE TABLE Contact (id INTEGER, mobile TEXT NULL, home TEXT NULL)"
+	);
+	execute!(
+		glue,
+		"INSERT INTO Contact VALUES (1, '021', '09'), (2, NULL, '04'), (3, NULL, NULL)"
+	);
+
+	assert_select!(glue, "SELECT id, COALESCE(mobile, home, 'none') AS phone FROM Contact" => id = I64, phone = Str:
+		(1, String::from("021")),
+		(2, String::from("04")),
+		(3, String::from("none"))
+	);
+	assert_select!(glue, "VALUES (COALESCE(NULL, NULL))" => unnamed_0 = I64: (_));
+
+	assert_select!(glue, "VALUES (GREATEST(1, 5, 3))" => unnamed_0 = I64: (5));
+	assert_select!(glue, "VALUES (LEAST(4, 2, 8))" => unnamed_0 = I64: (2));
+	assert_select!(glue, "VALUES (GREATEST('apple', 'pear'))" => unnamed_0 = Str: (String::from("pear")));
+	assert_select!(glue, "VALUES (GREATEST(1, NULL, 2))" => unnamed_0 = I64: (2));
+	assert_select!(glue, "VALUES (LEAST(NULL, NULL))" => unnamed_0 = I64: (_));
+
+	assert_error!(
+		glue,
+		"VALUES (COALESCE())",
+		multisql::ValueError::NumberOfFunctionParamsNotMatching {
+			expected: 1,
+			found: 0,
+		}
+	);
+	assert_error!(
+		glue,
+		"VALUES (GREATEST())",
+		multisql::ValueError::NumberOfFunctionParamsNotMatching {
+			expected: 1,
+			found: 0,
+		}
+	);
+}
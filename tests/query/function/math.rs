@@ -0,0 +1,61 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	assert_select!(glue, "VALUES (ABS(-3))" => unnamed_0 = I64: (3));
+	assert_select!(glue, "VALUES (ABS(-2.5))" => unnamed_0 = F64: (2.5));
+	assert_select!(glue, "VALUES (SIGN(-3))" => unnamed_0 = I64: (-1));
+	assert_select!(glue, "VALUES (SIGN(0.0))" => unnamed_0 = F64: (0.0));
+	assert_select!(glue, "VALUES (SIGN(4.2))" => unnamed_0 = F64: (1.0));
+
+	assert_select!(glue, "VALUES (CEIL(1.2))" => unnamed_0 = F64: (2.0));
+	assert_select!(glue, "VALUES (CEILING(-1.2))" => unnamed_0 = F64: (-1.0));
+	assert_select!(glue, "VALUES (FLOOR(1.8))" => unnamed_0 = F64: (1.0));
+	assert_select!(glue, "VALUES (FLOOR(5))" => unnamed_0 = I64: (5));
+
+	assert_select!(glue, "VALUES (SQRT(16))" => unnamed_0 = F64: (4.0));
+	assert_select!(glue, "VALUES (LN(1))" => unnamed_0 = F64: (0.0));
+	assert_select!(glue, "VALUES (EXP(0))" => unnamed_0 = F64: (1.0));
+	assert_select!(glue, "VALUES (LOG(1000))" => unnamed_0 = F64: (3.0));
+	assert_select!(glue, "VALUES (LOG(2, 8))" => unnamed_0 = F64: (3.0));
+
+	assert_select!(glue, "VALUES (MOD(10, 3))" => unnamed_0 = I64: (1));
+	assert_select!(glue, "VALUES (MOD(-10, 3))" => unnamed_0 = I64: (-1));
+	assert_select!(glue, "VALUES (MOD(5.5, 2))" => unnamed_0 = F64: (1.5));
+
+	assert_select!(glue, "VALUES (ABS(NULL))" => unnamed_0 = I64: (_));
+	assert_select!(glue, "VALUES (SQRT(NULL))" => unnamed_0 = F64: (_));
+	assert_select!(glue, "VALUES (MOD(NULL, 2))" => unnamed_0 = I64: (_));
+	assert_select!(glue, "VALUES (LOG(NULL, 2))" => unnamed_0 = F64: (_));
+
+	assert_error!(
+		glue,
+		"VALUES (MOD(1, 0))",
+		multisql::ValueError::DivisionByZero
+	);
+	assert_error!(
+		glue,
+		"VALUES (ABS('a'))",
+		multisql::ValueError::OnlySupportsNumeric(multisql::Value::Str(String::from("a")), "ABS")
+	);
+	assert_error!(
+		glue,
+		"VALUES (ABS(-9223372036854775807 - 1))",
+		multisql::ValueError::OutOfRange(multisql::Value::I64(i64::MIN), "ABS")
+	);
+	assert_error!(
+		glue,
+		"VALUES (LOG(1, 2, 3))",
+		multisql::ValueError::NumberOfFunctionParamsNotMatching {
+			expected: 1,
+			found: 3,
+		}
+	);
+	assert_error!(
+		glue,
+		"VALUES (FLOOR())",
+		multisql::ValueError::NumberOfFunctionParamsNotMatching {
+			expected: 1,
+			found: 0,
+		}
+	);
+}
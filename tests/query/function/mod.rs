@@ -1,9 +1,14 @@
+mod bytes;
 mod case;
+mod conditional;
 mod convert;
 mod convert_timestamp;
 mod func_cast;
 mod func_other;
 mod func_timestamp;
 mod left_right;
+mod math;
 mod misc_func_cast;
+mod regex;
+mod string;
 mod upper_lower;
@@ -0,0 +1,63 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(glue, "CREATE TABLE Note (id INTEGER, body TEXT NULL)");
+	execute!(
+		glue,
+		"INSERT INTO Note VALUES (1, 'Order AB-123 and AB-456'), (2, 'no codes here'), (3, NULL)"
+	);
+
+	assert_select!(glue, "SELECT id FROM Note WHERE REGEXP_LIKE(body, 'AB-[0-9]+')" => id = I64: (1));
+	assert_select!(glue, "SELECT id FROM Note WHERE body ~ '^no'" => id = I64: (2));
+	assert_select!(glue, "SELECT id FROM Note WHERE body REGEXP '^no'" => id = I64: (2));
+	assert_select!(glue, "SELECT id FROM Note WHERE body NOT REGEXP '^no'" => id = I64: (1));
+	assert_select!(glue, "SELECT id FROM Note WHERE body ~* '^ORDER'" => id = I64: (1));
+
+	// Only `REGEXP` between expressions is an operator, otherwise being a name
+	execute!(glue, "CREATE TABLE Pattern (regexp TEXT)");
+	execute!(glue, "INSERT INTO Pattern (regexp) VALUES ('^no')");
+	assert_select!(glue, "SELECT regexp FROM Pattern WHERE regexp REGEXP '^\\^'" => regexp = Str: (String::from("^no")));
+	assert_select!(glue, "SELECT id FROM Note, Pattern WHERE body REGEXP regexp" => id = I64: (2));
+	assert_select!(glue, "SELECT id FROM Note, Pattern WHERE body NOT REGEXP regexp" => id = I64: (1));
+	assert_select!(glue, "SELECT regexp FROM Pattern WHERE NOT regexp REGEXP 'x'" => regexp = Str: (String::from("^no")));
+	assert_select!(glue, "SELECT id FROM Note WHERE body !~ '^no'" => id = I64: (1));
+	assert_select!(glue, "SELECT id FROM Note WHERE REGEXP_LIKE(body, '(?i)^ORDER')" => id = I64: (1));
+	assert_select!(glue, "SELECT id FROM Note WHERE REGEXP_LIKE(body, CONCAT('^', 'no'))" => id = I64: (2));
+
+	assert_select!(glue, "SELECT REGEXP_COUNT(body, 'AB-[0-9]+') AS codes FROM Note WHERE id < 3" => codes = I64: (2), (0));
+	assert_select!(glue, "SELECT REGEXP_SUBSTR(body, 'AB-[0-9]+') AS code FROM Note WHERE id = 1" => code = Str: (String::from("AB-123")));
+	assert_select!(glue, "SELECT REGEXP_SUBSTR(body, 'AB-[0-9]+', 2) AS code FROM Note WHERE id = 1" => code = Str: (String::from("AB-456")));
+	assert_select!(glue, "SELECT REGEXP_SUBSTR(body, 'AB-[0-9]+', 3) AS code FROM Note WHERE id = 1" => code = Str: (_));
+	assert_select!(glue, "SELECT REGEXP_REPLACE(body, 'AB-([0-9]+)', '#$1') AS body FROM Note WHERE id = 1" => body = Str: (String::from("Order #123 and #456")));
+
+	assert_select!(glue, "SELECT REGEXP_LIKE(body, 'a') AS test FROM Note WHERE id = 3" => test = Bool: (_));
+	assert_select!(glue, "SELECT REGEXP_COUNT(body, 'a') AS test FROM Note WHERE id = 3" => test = I64: (_));
+	assert_select!(glue, "VALUES (REGEXP_LIKE('a', NULL))" => unnamed_0 = Bool: (_));
+	assert_select!(glue, "VALUES ('abc' ~ 'b')" => unnamed_0 = Bool: (true));
+
+	// Constant patterns are compiled when planning, so fail regardless of rows
+	let invalid_pattern = String::from("[");
+	assert_error!(
+		glue,
+		"SELECT id FROM Note WHERE id = 0 AND REGEXP_LIKE(body, '[')",
+		multisql::ValueError::InvalidPattern(
+			regex::Regex::new(&invalid_pattern).unwrap_err().to_string()
+		)
+	);
+	assert_error!(
+		glue,
+		"VALUES (REGEXP_LIKE('a'))",
+		multisql::ValueError::NumberOfFunctionParamsNotMatching {
+			expected: 2,
+			found: 1,
+		}
+	);
+	assert_error!(
+		glue,
+		"VALUES (REGEXP_REPLACE('a', 'a'))",
+		multisql::ValueError::NumberOfFunctionParamsNotMatching {
+			expected: 3,
+			found: 2,
+		}
+	);
+}
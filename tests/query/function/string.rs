@@ -0,0 +1,79 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(glue, "CREATE TABLE Item (name TEXT NULL)");
+	execute!(glue, "INSERT INTO Item VALUES ('  padded  '), (NULL)");
+
+	assert_select!(glue, "VALUES (SUBSTRING('multisql', 6))" => unnamed_0 = Str: (String::from("sql")));
+	assert_select!(glue, "VALUES (SUBSTRING('multisql', 1, 5))" => unnamed_0 = Str: (String::from("multi")));
+	assert_select!(glue, "VALUES (SUBSTRING('multisql' FROM 2 FOR 3))" => unnamed_0 = Str: (String::from("ult")));
+	assert_select!(glue, "VALUES (SUBSTRING('multisql' FOR 2))" => unnamed_0 = Str: (String::from("mu")));
+	assert_select!(glue, "VALUES (SUBSTRING('multisql', 0, 3))" => unnamed_0 = Str: (String::from("mu")));
+	assert_select!(glue, "VALUES (SUBSTR('multisql', 20))" => unnamed_0 = Str: (String::from("")));
+
+	assert_select!(glue, "SELECT TRIM(name) AS test FROM Item WHERE name IS NOT NULL" => test = Str: (String::from("padded")));
+	assert_select!(glue, "SELECT LTRIM(name) AS test FROM Item WHERE name IS NOT NULL" => test = Str: (String::from("padded  ")));
+	assert_select!(glue, "SELECT RTRIM(name) AS test FROM Item WHERE name IS NOT NULL" => test = Str: (String::from("  padded")));
+	assert_select!(glue, "VALUES (TRIM(BOTH 'x' FROM 'xxhixx'))" => unnamed_0 = Str: (String::from("hi")));
+	assert_select!(glue, "VALUES (TRIM(LEADING 'x' FROM 'xxhixx'))" => unnamed_0 = Str: (String::from("hixx")));
+	assert_select!(glue, "VALUES (TRIM(TRAILING 'x' FROM 'xxhixx'))" => unnamed_0 = Str: (String::from("xxhi")));
+	assert_select!(glue, "VALUES (RTRIM('hi!?!', '?!'))" => unnamed_0 = Str: (String::from("hi")));
+
+	assert_select!(glue, "VALUES (POSITION('sql', 'multisql'))" => unnamed_0 = I64: (6));
+	assert_select!(glue, "VALUES (POSITION('none', 'multisql'))" => unnamed_0 = I64: (0));
+	assert_select!(glue, "VALUES (INSTR('multisql', 'ti'))" => unnamed_0 = I64: (4));
+
+	assert_select!(glue, "VALUES (LPAD('7', 3, '0'))" => unnamed_0 = Str: (String::from("007")));
+	assert_select!(glue, "VALUES (RPAD('ab', 5, 'xy'))" => unnamed_0 = Str: (String::from("abxyx")));
+	assert_select!(glue, "VALUES (LPAD('abc', 2))" => unnamed_0 = Str: (String::from("ab")));
+	assert_select!(glue, "VALUES (RPAD('a', 3))" => unnamed_0 = Str: (String::from("a  ")));
+
+	assert_select!(glue, "VALUES (REVERSE('stressed'))" => unnamed_0 = Str: (String::from("desserts")));
+
+	assert_select!(glue, "VALUES (SPLIT_PART('a,b,c', ',', 2))" => unnamed_0 = Str: (String::from("b")));
+	assert_select!(glue, "VALUES (SPLIT_PART('a,b,c', ',', 4))" => unnamed_0 = Str: (String::from("")));
+	assert_select!(glue, "VALUES (SPLIT_PART('a,b', '', 1))" => unnamed_0 = Str: (String::from("a,b")));
+	assert_select!(glue, "VALUES (SPLIT_PART('a,b', '', 2))" => unnamed_0 = Str: (String::from("")));
+
+	assert_select!(glue, "SELECT TRIM(name) AS test FROM Item WHERE name IS NULL" => test = Str: (_));
+	assert_select!(glue, "SELECT REVERSE(name) AS test FROM Item WHERE name IS NULL" => test = Str: (_));
+	assert_select!(glue, "SELECT SUBSTRING(name, 1, 2) AS test FROM Item WHERE name IS NULL" => test = Str: (_));
+	assert_select!(glue, "SELECT LPAD(name, 2) AS test FROM Item WHERE name IS NULL" => test = Str: (_));
+	assert_select!(glue, "SELECT SPLIT_PART(name, ',', 1) AS test FROM Item WHERE name IS NULL" => test = Str: (_));
+	assert_select!(glue, "SELECT POSITION('a', name) AS test FROM Item WHERE name IS NULL" => test = I64: (_));
+
+	assert_error!(
+		glue,
+		"VALUES (REVERSE('a', 'b'))",
+		multisql::ValueError::NumberOfFunctionParamsNotMatching {
+			expected: 1,
+			found: 2,
+		}
+	);
+	assert_error!(
+		glue,
+		"VALUES (SPLIT_PART('a,b', ','))",
+		multisql::ValueError::NumberOfFunctionParamsNotMatching {
+			expected: 3,
+			found: 2,
+		}
+	);
+	assert_error!(
+		glue,
+		"VALUES (LPAD('a'))",
+		multisql::ValueError::NumberOfFunctionParamsNotMatching {
+			expected: 2,
+			found: 1,
+		}
+	);
+	assert_error!(
+		glue,
+		"VALUES (SUBSTRING('abc', 1, -1))",
+		multisql::ValueError::BadInput(multisql::Value::I64(-1))
+	);
+	assert_error!(
+		glue,
+		"VALUES (SPLIT_PART('a,b', ',', 0))",
+		multisql::ValueError::BadInput(multisql::Value::I64(0))
+	);
+}
@@ -1,3 +1,13 @@
+mod array;
+mod derived;
+mod distinct;
 mod explain;
+mod filter;
 mod function;
+mod order;
+mod projection;
+mod recursive;
+mod set_operation;
+mod table_function;
+mod temporary;
 mod with;
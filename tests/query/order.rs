@@ -0,0 +1,72 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(
+		glue,
+		"CREATE TABLE Runner (name TEXT, club TEXT NULL, time INTEGER)"
+	);
+	execute!(
+		glue,
+		"
+		INSERT INTO Runner VALUES
+			('Ann', 'North', 310), ('Ben', NULL, 295), ('Cat', 'South', 330),
+			('Dan', 'North', 280), ('Eve', NULL, 300)
+	"
+	);
+
+	assert_eq!(
+		glue.select_as_csv("SELECT name FROM Runner WHERE time > 290 ORDER BY time"),
+		Ok(String::from("name\nBen\nEve\nAnn\nCat\n"))
+	);
+
+	// By alias and by position
+	assert_eq!(
+		glue.select_as_csv("SELECT name, time - 280 AS behind FROM Runner ORDER BY behind DESC"),
+		Ok(String::from(
+			"name,behind\nCat,50\nAnn,30\nEve,20\nBen,15\nDan,0\n"
+		))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT time, name FROM Runner ORDER BY 2 DESC"),
+		Ok(String::from(
+			"time,name\n300,Eve\n280,Dan\n330,Cat\n295,Ben\n310,Ann\n"
+		))
+	);
+
+	// NULLs are least unless otherwise stated
+	assert_eq!(
+		glue.select_as_csv("SELECT name FROM Runner ORDER BY club, name"),
+		Ok(String::from("name\nBen\nEve\nAnn\nDan\nCat\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT name FROM Runner ORDER BY club NULLS LAST, name"),
+		Ok(String::from("name\nAnn\nDan\nCat\nBen\nEve\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT name FROM Runner ORDER BY club DESC NULLS FIRST, name"),
+		Ok(String::from("name\nBen\nEve\nCat\nAnn\nDan\n"))
+	);
+
+	// Only the first rows need be found where limited
+	assert_eq!(
+		glue.select_as_csv("SELECT name FROM Runner ORDER BY time LIMIT 2 OFFSET 1"),
+		Ok(String::from("name\nBen\nEve\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT name FROM Runner ORDER BY time DESC LIMIT 10"),
+		Ok(String::from("name\nCat\nAnn\nEve\nBen\nDan\n"))
+	);
+
+	assert_eq!(
+		glue.select_as_csv(
+			"SELECT club, MIN(time) AS best FROM Runner WHERE club IS NOT NULL GROUP BY club ORDER BY best"
+		),
+		Ok(String::from("club,best\nNorth,280\nSouth,330\n"))
+	);
+
+	assert_error!(
+		glue,
+		"SELECT name FROM Runner ORDER BY 3",
+		multisql::SelectError::OrderPositionOutOfRange(String::from("3"))
+	);
+}
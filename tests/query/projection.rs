@@ -0,0 +1,49 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(
+		glue,
+		"CREATE TABLE wide (id INTEGER, a TEXT, b INTEGER, c TEXT, d INTEGER)"
+	);
+	execute!(
+		glue,
+		"INSERT INTO wide VALUES (1, 'one', 10, 'x', 100), (2, 'two', 20, 'y', 200), (3, 'three', 30, 'x', 300)"
+	);
+	execute!(glue, "CREATE TABLE narrow (wide_id INTEGER, note TEXT)");
+	execute!(glue, "INSERT INTO narrow VALUES (1, 'first'), (3, 'third')");
+
+	// Columns which are only filtered, ordered, grouped or joined by are still read
+	assert_eq!(
+		glue.select_as_csv("SELECT a FROM wide WHERE d > 100 ORDER BY b DESC"),
+		Ok(String::from("a\nthree\ntwo\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT c, SUM(b) AS total FROM wide GROUP BY c ORDER BY c"),
+		Ok(String::from("c,total\nx,40\ny,20\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT note, wide.a FROM wide INNER JOIN narrow ON narrow.wide_id = wide.id ORDER BY wide.d"),
+		Ok(String::from("note,wide.a\nfirst,one\nthird,three\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT wide.*, note FROM wide LEFT JOIN narrow ON narrow.wide_id = wide.id WHERE wide.id < 3 ORDER BY id"),
+		Ok(String::from("wide.id,wide.a,wide.b,wide.c,wide.d,note\n1,one,10,x,100,first\n2,two,20,y,200,NULL\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT DISTINCT ON (c) id FROM wide ORDER BY c, id DESC"),
+		Ok(String::from("id\n3\n2\n"))
+	);
+
+	// No columns at all, the rows are still counted
+	assert_select!(glue, "SELECT COUNT(1) AS count FROM wide" => count = I64: (3));
+	assert_select!(glue, "SELECT 1 AS one FROM wide INNER JOIN narrow ON 1 = 1" => one = I64: (1), (1), (1), (1), (1), (1));
+
+	// Other sources of rows
+	execute!(glue, "CREATE VIEW wide_view AS (SELECT id, a, d FROM wide)");
+	assert_select!(glue, "SELECT a FROM wide_view WHERE d = 200" => a = Str: (String::from("two")));
+	assert_eq!(
+		glue.select_as_csv("WITH cte AS (SELECT * FROM wide) SELECT c FROM cte WHERE id = 2"),
+		Ok(String::from("c\ny\n"))
+	);
+	assert_select!(glue, "SELECT id FROM wide, LATERAL (SELECT note FROM narrow WHERE narrow.wide_id = wide.id) AS notes" => id = I64: (1), (3));
+}
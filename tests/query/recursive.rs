@@ -0,0 +1,104 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(
+		glue,
+		"CREATE TABLE Employee (id INTEGER, name TEXT, manager_id INTEGER NULL)"
+	);
+	execute!(
+		glue,
+		"INSERT INTO Employee VALUES (1, 'Ada', NULL), (2, 'Bo', 1), (3, 'Cy', 1), (4, 'Di', 3)"
+	);
+	execute!(glue, "CREATE TABLE Link (source INTEGER, target INTEGER)");
+	execute!(glue, "INSERT INTO Link VALUES (1, 2), (2, 3), (3, 1)");
+
+	assert_select!(glue, "
+		WITH RECURSIVE counter(n) AS (
+			VALUES (1)
+			UNION ALL
+			SELECT n + 1 FROM counter WHERE n < 5
+		)
+		SELECT n FROM counter
+	" => n = I64: (1), (2), (3), (4), (5));
+
+	assert_select!(glue, "
+		WITH RECURSIVE chain(id, name, depth) AS (
+			SELECT id, name, 0 FROM Employee WHERE manager_id IS NULL
+			UNION ALL
+			SELECT Employee.id, Employee.name, chain.depth + 1
+			FROM Employee
+			INNER JOIN chain ON Employee.manager_id = chain.id
+		)
+		SELECT name, depth FROM chain
+	" =>
+		name = Str, depth = I64:
+		(String::from("Ada"), 0),
+		(String::from("Bo"), 1),
+		(String::from("Cy"), 1),
+		(String::from("Di"), 2)
+	);
+
+	// UNION stops once no new rows are found, even where data is cyclic
+	assert_select!(glue, "
+		WITH RECURSIVE reachable(node) AS (
+			VALUES (1)
+			UNION
+			SELECT Link.target FROM Link INNER JOIN reachable ON Link.source = reachable.node
+		)
+		SELECT node FROM reachable
+	" => node = I64: (1), (2), (3));
+
+	// Columns of the CTE's name aren't references to it
+	execute!(glue, "CREATE TABLE Seed (v INTEGER)");
+	execute!(glue, "INSERT INTO Seed VALUES (1)");
+	execute!(glue, "CREATE TABLE Amount (total INTEGER)");
+	execute!(glue, "INSERT INTO Amount VALUES (2)");
+	assert_select!(glue, "
+		WITH RECURSIVE total AS (
+			SELECT v FROM Seed
+			UNION ALL
+			SELECT total FROM Amount
+		)
+		SELECT v FROM total
+	" => v = I64: (1), (2));
+
+	glue.set_recursion_limit(10);
+	assert_error!(
+		glue,
+		"
+		WITH RECURSIVE walk(node) AS (
+			VALUES (1)
+			UNION ALL
+			SELECT Link.target FROM Link INNER JOIN walk ON Link.source = walk.node
+		)
+		SELECT node FROM walk
+	",
+		multisql::QueryError::RecursionLimitReached(10)
+	);
+
+	// Not self-referencing, so not repeated
+	assert_select!(glue, "
+		WITH RECURSIVE pair(value) AS (
+			VALUES (1)
+			UNION ALL
+			VALUES (2)
+		)
+		SELECT value FROM pair
+	" => value = I64: (1), (2));
+
+	assert_select!(glue, "
+		WITH renamed(identifier, label) AS (SELECT id, name FROM Employee WHERE id = 2)
+		SELECT identifier, label FROM renamed
+	" => identifier = I64, label = Str: (2, String::from("Bo")));
+	assert_error!(
+		glue,
+		"
+		WITH renamed(a, b, c) AS (SELECT id, name FROM Employee)
+		SELECT a FROM renamed
+	",
+		multisql::QueryError::CTEColumnsMisaligned {
+			expected: 2,
+			found: 3
+		}
+	);
+}
@@ -0,0 +1,62 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	assert_select!(glue, "VALUES (1), (2), (1) UNION VALUES (2), (3)" =>
+		unnamed_0 = I64: (1), (2), (3)
+	);
+	assert_select!(glue, "VALUES (1), (1), (1), (2) INTERSECT VALUES (1), (1), (3)" =>
+		unnamed_0 = I64: (1)
+	);
+	assert_select!(glue, "VALUES (1), (1), (1), (2) INTERSECT ALL VALUES (1), (1), (3)" =>
+		unnamed_0 = I64: (1), (1)
+	);
+	assert_select!(glue, "VALUES (1), (1), (2) EXCEPT VALUES (3)" =>
+		unnamed_0 = I64: (1), (2)
+	);
+	assert_select!(glue, "VALUES (1), (1), (1), (2) EXCEPT ALL VALUES (1)" =>
+		unnamed_0 = I64: (1), (1), (2)
+	);
+
+	// NULLs are not distinct from one another, numbers of different types may be equal
+	assert_eq!(
+		glue.select_as_csv("VALUES (NULL, 1), (NULL, 1) UNION VALUES (NULL, 1.0)"),
+		Ok(String::from("unnamed_0,unnamed_1\nNULL,1\n"))
+	);
+
+	assert_eq!(
+		glue.select_as_csv("VALUES (3), (1) UNION VALUES (2), (1) ORDER BY unnamed_0 DESC LIMIT 2"),
+		Ok(String::from("unnamed_0\n3\n2\n"))
+	);
+
+	execute!(glue, "CREATE TABLE Cat (name TEXT, age INTEGER)");
+	execute!(glue, "CREATE TABLE Dog (name TEXT, age INTEGER)");
+	execute!(glue, "INSERT INTO Cat VALUES ('Tom', 4), ('Kit', 1)");
+	execute!(glue, "INSERT INTO Dog VALUES ('Rex', 7), ('Tom', 4)");
+	assert_eq!(
+		glue.select_as_csv(
+			"SELECT name, age FROM Cat UNION SELECT name, age FROM Dog ORDER BY age"
+		),
+		Ok(String::from("name,age\nKit,1\nTom,4\nRex,7\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT name FROM Cat INTERSECT SELECT name FROM Dog"),
+		Ok(String::from("name\nTom\n"))
+	);
+	// Columns of values of different types may be combined with any other
+	assert_eq!(
+		glue.select_as_csv(
+			"SELECT age, CASE WHEN age = 1 THEN 'young' ELSE age END AS stage FROM Cat UNION SELECT age, CASE WHEN age = 1 THEN 'young' ELSE age END AS stage FROM Cat ORDER BY age"
+		),
+		Ok(String::from("age,stage\n1,young\n4,4\n"))
+	);
+
+	assert_error!(
+		glue,
+		"SELECT name FROM Cat UNION SELECT age FROM Dog",
+		multisql::QueryError::OperationTypesMismatched {
+			column: String::from("name"),
+			left: multisql::ValueType::Str,
+			right: multisql::ValueType::I64,
+		}
+	);
+}
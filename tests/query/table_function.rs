@@ -0,0 +1,87 @@
+use {crate::util::*, multisql::Value, std::fs::write};
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	assert_select!(glue, "SELECT * FROM GENERATE_SERIES(1, 4)" => generate_series = I64: (1), (2), (3), (4));
+	assert_select!(glue, "SELECT number FROM GENERATE_SERIES(10, 1, -4) AS numbers(number)" => number = I64: (10), (6), (2));
+	assert_select!(glue, "SELECT * FROM GENERATE_SERIES(0, 1, 0.5)" => generate_series = F64: (0.0), (0.5), (1.0));
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM GENERATE_SERIES(5, 1)"),
+		Ok(String::from("generate_series\n"))
+	);
+	assert_select!(glue, "SELECT SUM(a.generate_series * b.generate_series) AS total FROM GENERATE_SERIES(1, 3) AS a, GENERATE_SERIES(1, 2) AS b" => total = I64: (18));
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM GENERATE_SERIES(CAST('2022-01-31' AS TIMESTAMP), CAST('2022-04-30' AS TIMESTAMP), '1 MONTH')"),
+		Ok(String::from("generate_series\n2022-01-31 00:00:00\n2022-02-28 00:00:00\n2022-03-31 00:00:00\n2022-04-30 00:00:00\n"))
+	);
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM GENERATE_SERIES(CAST('2022-01-01 22:00' AS TIMESTAMP), CAST('2022-01-02 01:00' AS TIMESTAMP), '90 minutes')"),
+		Ok(String::from("generate_series\n2022-01-01 22:00:00\n2022-01-01 23:30:00\n2022-01-02 01:00:00\n"))
+	);
+	assert_error!(
+		glue,
+		"SELECT * FROM GENERATE_SERIES(1, 5, 0)",
+		multisql::JoinError::InvalidSeriesStep(String::from("0"))
+	);
+	assert_error!(glue, "SELECT * FROM GENERATE_SERIES(CAST('2022-01-01' AS TIMESTAMP), CAST('2022-01-02' AS TIMESTAMP), '1 fortnight')", multisql::JoinError::InvalidSeriesStep(String::from("1 fortnight")));
+	assert_error!(
+		glue,
+		"SELECT * FROM GENERATE_SERIES(1, 9223372036854775807)",
+		multisql::JoinError::SeriesTooLong(1_000_000)
+	);
+
+	write(
+		"data/query_table_function.csv",
+		"id;name;score\n1;Alice;4.5\n2;Bob;3\n3;Carol;5\n",
+	)
+	.unwrap();
+	assert_select!(glue, "SELECT name FROM READ_CSV('data/query_table_function.csv', ';') WHERE score > 4 ORDER BY id" => name = Str: (String::from("Alice")), (String::from("Carol")));
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM READ_CSV('data/query_table_function.csv', ';', false) AS people(identifier)"),
+		Ok(String::from("identifier,column_1,column_2\nid,name,score\n1,Alice,4.5\n2,Bob,3\n3,Carol,5\n"))
+	);
+	execute!(glue, "CREATE TABLE grade (id INTEGER, grade TEXT)");
+	execute!(glue, "INSERT INTO grade VALUES (1, 'A'), (2, 'C')");
+	assert_select!(glue, "SELECT people.name, grade.grade FROM READ_CSV('data/query_table_function.csv', ';') AS people INNER JOIN grade ON grade.id = people.id ORDER BY people.id" => "READ_CSV.name" = Str, "grade.grade" = Str: (String::from("Alice"), String::from("A")), (String::from("Bob"), String::from("C")));
+
+	write(
+		"data/query_table_function.json",
+		r#"[{"id": 1, "name": "Alice", "tags": ["red"]}, {"id": 2, "extra": {"nested": true}}]"#,
+	)
+	.unwrap();
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM READ_JSON('data/query_table_function.json')"),
+		Ok(String::from(
+			"id,name,tags,extra\n1,Alice,\"[\"\"red\"\"]\",NULL\n2,NULL,NULL,\"{\"\"nested\"\":true}\"\n"
+		))
+	);
+	// Columns are in the order of the file, not of their names
+	write(
+		"data/query_table_function_order.json",
+		r#"{"b": 1, "a": 2}"#,
+	)
+	.unwrap();
+	assert_eq!(
+		glue.select_as_csv("SELECT * FROM READ_JSON('data/query_table_function_order.json')"),
+		Ok(String::from("b,a\n1,2\n"))
+	);
+	assert_select!(glue, "SELECT tags FROM READ_JSON('data/query_table_function.json') WHERE id = 1" => tags = Array: (vec![Value::Str(String::from("red"))]));
+	write(
+		"data/query_table_function.ndjson",
+		"{\"id\": 1}\n{\"id\": 2}\n",
+	)
+	.unwrap();
+	assert_select!(glue, "SELECT SUM(id) AS total FROM READ_JSON('data/query_table_function.ndjson')" => total = I64: (3));
+
+	assert_error!(glue, "SELECT * FROM READ_JSON('data/does_not_exist.json')");
+	// Columns of files are only known once they are read
+	assert_error!(
+		glue,
+		"SELECT * FROM grade, READ_CSV(grade.grade)",
+		multisql::JoinError::UnimplementedLateralFunction(String::from("READ_CSV"))
+	);
+	assert_error!(
+		glue,
+		"SELECT * FROM READ_CSV('data/query_table_function.csv', ';;')",
+		multisql::JoinError::InvalidDelimiter(String::from(";;"))
+	);
+}
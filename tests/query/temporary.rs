@@ -0,0 +1,50 @@
+use crate::util::*;
+testcase!(test);
+fn test(mut glue: multisql::Glue) {
+	execute!(glue, "CREATE TABLE Item (id INTEGER, name TEXT)");
+	execute!(glue, "INSERT INTO Item VALUES (1, 'persistent')");
+
+	execute!(
+		glue,
+		"CREATE TEMPORARY TABLE Scratch (id INTEGER, name TEXT)"
+	);
+	execute!(
+		glue,
+		"INSERT INTO Scratch VALUES (1, 'one'), (2, 'two'), (3, 'three')"
+	);
+	execute!(glue, "UPDATE Scratch SET name = 'TWO' WHERE id = 2");
+	execute!(glue, "DELETE FROM Scratch WHERE id = 3");
+	assert_select!(glue, "SELECT id, name FROM Scratch ORDER BY id" =>
+		id = I64, name = Str:
+		(1, String::from("one")),
+		(2, String::from("TWO"))
+	);
+	assert_select!(glue, "
+		SELECT Item.name, Scratch.name
+		FROM Item
+		INNER JOIN Scratch ON Scratch.id = Item.id
+	" => "Item.name" = Str, "Scratch.name" = Str: (String::from("persistent"), String::from("one")));
+
+	// Temporary tables take precedence over others of the same name
+	execute!(glue, "CREATE TEMPORARY TABLE Item (id INTEGER, name TEXT)");
+	execute!(glue, "INSERT INTO Item VALUES (2, 'temporary')");
+	assert_select!(glue, "SELECT name FROM Item" => name = Str: (String::from("temporary")));
+	assert_select!(glue, "SELECT name FROM temporary.Item" => name = Str: (String::from("temporary")));
+
+	execute!(glue, "DROP TABLE Item");
+	assert_select!(glue, "SELECT name FROM Item" => name = Str: (String::from("persistent")));
+
+	// No database may hide, or be hidden by, temporary tables
+	assert_error!(
+		glue,
+		"CREATE DATABASE temporary LOCATION 'data/query_temporary/'",
+		multisql::ExecuteError::ReservedDatabaseName(String::from("temporary"))
+	);
+	assert!(!glue.extend(
+		String::from("temporary"),
+		multisql::Connection::Memory.try_into().unwrap()
+	));
+
+	execute!(glue, "DROP TABLE Scratch");
+	assert_error!(glue, "SELECT id FROM Scratch");
+}
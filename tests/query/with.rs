@@ -80,4 +80,60 @@ fn test(mut glue: multisql::Glue) {
 		FROM
 			basic_insert
 	"# => a = I64: (1));
+
+	// CTEs last only for the query which defines them
+	assert_error!(glue, "SELECT a FROM cte_0");
+
+	assert_select!(glue, r#"
+		WITH basic AS (
+			SELECT
+				a + 1 AS a
+			FROM
+				basic
+		)
+		SELECT
+			a
+		FROM
+			basic
+	"# => a = I64: (2));
+
+	assert_select!(glue, r#"
+		WITH cte AS (
+			SELECT
+				a
+			FROM
+				basic
+		)
+		SELECT
+			a
+		FROM (
+			WITH cte AS (
+				SELECT
+					a + 10 AS a
+				FROM
+					cte
+			)
+			SELECT
+				a
+			FROM
+				cte
+		) AS shadowed
+	"# => a = I64: (11));
+
+	assert_select!(glue, r#"
+		WITH cte AS (
+			SELECT
+				a
+			FROM
+				basic
+		)
+		SELECT
+			a
+		FROM (
+			SELECT
+				a
+			FROM
+				cte
+		) AS inherited
+	"# => a = I64: (1));
 }